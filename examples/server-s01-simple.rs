@@ -0,0 +1,25 @@
+//! server-s01-simple - Basic MCP Server example over stdio
+//!
+//! Can be used from any MCP client, e.g., with the `client-c01-simple` example by
+//! replacing the `npx` command with `cargo run --example server-s01-simple`.
+
+use agentic::mcp::server::{Server, ServerStdioTransportConfig};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+	// NOTE: stdout is reserved for MCP messages, so logs must go to stderr.
+	tracing_subscriber::fmt()
+		.with_max_level(tracing::Level::WARN)
+		.with_writer(std::io::stderr)
+		.without_time()
+		.init();
+
+	// -- Create MCP Server
+	let mut server = Server::new("Demo Server", "0.1.0");
+	server.set_instructions("A demo server built with the agentic crate.");
+
+	// -- Serve over stdio (until stdin closes)
+	server.serve(ServerStdioTransportConfig::new()).await?;
+
+	Ok(())
+}
@@ -0,0 +1,69 @@
+//! server-s02-tools - MCP Server with typed tools over stdio
+//!
+//! Tools are plain `async fn(Args) -> Result<CallToolResult>`, where `Args` is deserialized from the tool call arguments,
+//! and gives the tool input schema (see `ToolArgs`).
+
+use agentic::mcp::server::{Server, ServerStdioTransportConfig, ToolArgs};
+use agentic::mcp::{CallToolResult, ToolInputProperty, ToolInputSchema};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct AddArgs {
+	a: f64,
+	b: f64,
+}
+
+impl ToolArgs for AddArgs {
+	fn input_schema() -> ToolInputSchema {
+		ToolInputSchema::object()
+			.append_required_property("a", ToolInputProperty::number().with_description("First number"))
+			.append_required_property("b", ToolInputProperty::number().with_description("Second number"))
+	}
+}
+
+async fn add(args: AddArgs) -> agentic::mcp::Result<CallToolResult> {
+	let sum = args.a + args.b;
+	Ok(CallToolResult::from_text(format!(
+		"The sum of {} and {} is {sum}.",
+		args.a, args.b
+	)))
+}
+
+#[derive(Deserialize)]
+struct EchoArgs {
+	message: String,
+}
+
+impl ToolArgs for EchoArgs {
+	fn input_schema() -> ToolInputSchema {
+		ToolInputSchema::object().append_required_property("message", ToolInputProperty::string())
+	}
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+	// NOTE: stdout is reserved for MCP messages, so logs must go to stderr.
+	tracing_subscriber::fmt()
+		.with_max_level(tracing::Level::WARN)
+		.with_writer(std::io::stderr)
+		.without_time()
+		.init();
+
+	// -- Create MCP Server
+	let mut server = Server::new("Demo Tools Server", "0.1.0");
+
+	// -- Register the tools
+	server.register_tool("add", "Adds two numbers", add);
+	server.register_tool(
+		"echo",
+		"Echoes back the input",
+		async |args: EchoArgs| -> agentic::mcp::Result<CallToolResult> {
+			Ok(CallToolResult::from_text(format!("Echo: {}", args.message)))
+		},
+	);
+
+	// -- Serve over stdio (until stdin closes)
+	server.serve(ServerStdioTransportConfig::new()).await?;
+
+	Ok(())
+}
@@ -0,0 +1,69 @@
+//! server-s03-resources - MCP Server with a resource provider over stdio
+//!
+//! A `counter://value` resource is incremented every few seconds, and subscribed clients get notified.
+
+use agentic::mcp::server::{ResourceProvider, Server, ServerStdioTransportConfig};
+use agentic::mcp::{Error, Resource, ResourceContents};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+const COUNTER_URI: &str = "counter://value";
+
+struct CounterProvider {
+	counter: Arc<AtomicU64>,
+}
+
+impl ResourceProvider for CounterProvider {
+	async fn list_resources(&self) -> agentic::mcp::Result<Vec<Resource>> {
+		let resource = Resource::new(COUNTER_URI, "Counter")
+			.with_description("A counter incremented every 5 seconds")
+			.with_mime_type("text/plain");
+		Ok(vec![resource])
+	}
+
+	async fn read_resource(&self, uri: &str) -> agentic::mcp::Result<Vec<ResourceContents>> {
+		if uri != COUNTER_URI {
+			return Err(Error::resource_not_found(uri));
+		}
+		Ok(vec![ResourceContents::Text {
+			uri: uri.to_string(),
+			mime_type: Some("text/plain".to_string()),
+			text: self.counter.load(Ordering::Relaxed).to_string(),
+		}])
+	}
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+	// NOTE: stdout is reserved for MCP messages, so logs must go to stderr.
+	tracing_subscriber::fmt()
+		.with_max_level(tracing::Level::WARN)
+		.with_writer(std::io::stderr)
+		.without_time()
+		.init();
+
+	// -- Create MCP Server
+	let mut server = Server::new("Demo Resources Server", "0.1.0");
+
+	// -- Register the resource provider
+	let counter = Arc::new(AtomicU64::new(0));
+	server.register_resource_provider(CounterProvider {
+		counter: counter.clone(),
+	});
+
+	// -- Update the counter and notify the subscribed clients
+	let notifier = server.resource_notifier();
+	tokio::spawn(async move {
+		loop {
+			tokio::time::sleep(Duration::from_secs(5)).await;
+			counter.fetch_add(1, Ordering::Relaxed);
+			notifier.notify_updated(COUNTER_URI);
+		}
+	});
+
+	// -- Serve over stdio (until stdin closes)
+	server.serve(ServerStdioTransportConfig::new()).await?;
+
+	Ok(())
+}
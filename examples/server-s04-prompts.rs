@@ -0,0 +1,53 @@
+//! server-s04-prompts - MCP Server with prompts and argument completion over stdio
+//!
+//! Prompt render functions are `async fn(Args) -> Result<GetPromptResult>`, where `Args` is deserialized from the prompt arguments.
+
+use agentic::mcp::server::{Server, ServerStdioTransportConfig};
+use agentic::mcp::{GetPromptResult, MessageContent, Prompt, PromptArgument, PromptMessage, Role};
+use serde::Deserialize;
+
+const LANGUAGES: &[&str] = &["go", "java", "javascript", "python", "rust", "typescript"];
+
+#[derive(Deserialize)]
+struct CodeReviewArgs {
+	code: String,
+	language: Option<String>,
+}
+
+async fn code_review(args: CodeReviewArgs) -> agentic::mcp::Result<GetPromptResult> {
+	let language = args.language.unwrap_or_else(|| "unknown".to_string());
+	let text = format!("Please review this {language} code:\n\n{}", args.code);
+	let message = PromptMessage::new(Role::User, MessageContent::new_text(text));
+	Ok(GetPromptResult::new(vec![message]).with_description("Code review prompt"))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+	// NOTE: stdout is reserved for MCP messages, so logs must go to stderr.
+	tracing_subscriber::fmt()
+		.with_max_level(tracing::Level::WARN)
+		.with_writer(std::io::stderr)
+		.without_time()
+		.init();
+
+	// -- Create MCP Server
+	let mut server = Server::new("Demo Prompts Server", "0.1.0");
+
+	// -- Register the prompt
+	let prompt = Prompt::new("code_review")
+		.with_description("Asks the LLM to review some code")
+		.append_argument(PromptArgument::new("code").with_required(true))
+		.append_argument(PromptArgument::new("language").with_description("The programming language"));
+	server.register_prompt(prompt, code_review);
+
+	// -- Register the completer for the `language` argument
+	server.register_prompt_completer("code_review", "language", async |value: String| {
+		let values = LANGUAGES.iter().filter(|lang| lang.starts_with(&value));
+		Ok(values.map(|lang| lang.to_string()).collect())
+	});
+
+	// -- Serve over stdio (until stdin closes)
+	server.serve(ServerStdioTransportConfig::new()).await?;
+
+	Ok(())
+}
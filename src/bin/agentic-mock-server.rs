@@ -0,0 +1,29 @@
+//! agentic-mock-server - The scriptable mock MCP server over stdio (see `agentic::mcp::testing::MockServer`).
+//!
+//! The `MockScript` (JSON) is the `AGENTIC_MOCK_SCRIPT` environment variable, or the file of the first argument.
+
+use agentic::mcp::testing::{MockScript, MockServer};
+
+type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+/// The environment variable of the script JSON.
+const SCRIPT_ENV: &str = "AGENTIC_MOCK_SCRIPT";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+	let script_json = match std::env::var(SCRIPT_ENV) {
+		Ok(script_json) => script_json,
+		Err(_) => {
+			let path = std::env::args()
+				.nth(1)
+				.ok_or_else(|| format!("Usage: agentic-mock-server <script.json> (or the {SCRIPT_ENV} env var)"))?;
+			std::fs::read_to_string(path)?
+		}
+	};
+	let script: MockScript = serde_json::from_str(&script_json)?;
+
+	// NOTE: stdout is reserved for the MCP messages.
+	MockServer::from_script(script).serve_stdio().await?;
+
+	Ok(())
+}
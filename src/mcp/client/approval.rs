@@ -0,0 +1,172 @@
+use crate::mcp::{CallToolParams, CreateMessageParams, Tool};
+use std::future::Future;
+use std::pin::Pin;
+
+/// The decision of an `ApprovalHandler` review.
+#[derive(Debug, Clone)]
+pub enum Approval<T> {
+	/// Proceed with the original params.
+	Approve,
+	/// Proceed with the edited params (e.g., rewritten messages or arguments).
+	Edit(T),
+	/// Do not proceed. The caller gets `Error::UserRejected` (sent back to the server for sampling).
+	Deny { reason: Option<String> },
+}
+
+/// Builders
+impl<T> Approval<T> {
+	pub fn deny(reason: impl Into<String>) -> Self {
+		Approval::Deny {
+			reason: Some(reason.into()),
+		}
+	}
+}
+
+// region:    --- ToolApprovalPolicy
+
+/// How a tool call is approved (see `ApprovalHandler::tool_call_policy`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolApprovalPolicy {
+	/// Sent without review.
+	Approve,
+	/// Reviewed with `ApprovalHandler::review_tool_call` before being sent.
+	Review,
+	/// Not sent.
+	Deny,
+}
+
+impl ToolApprovalPolicy {
+	/// The default policy, from the tool annotations (the tool being None when not listed by the client yet).
+	///
+	/// - `read_only_hint: true` → `Approve`
+	/// - `destructive_hint: false` (only additive updates) → `Approve`
+	/// - otherwise (destructive by default, or unknown tool) → `Review`
+	///
+	/// NOTE: The annotations are hints given by the server, so they should not be trusted for untrusted servers.
+	pub fn from_tool(tool: Option<&Tool>) -> Self {
+		let Some(annotations) = tool.and_then(|tool| tool.annotations.as_ref()) else {
+			return ToolApprovalPolicy::Review;
+		};
+		if annotations.read_only_hint == Some(true) || annotations.destructive_hint == Some(false) {
+			ToolApprovalPolicy::Approve
+		} else {
+			ToolApprovalPolicy::Review
+		}
+	}
+}
+
+// endregion: --- ToolApprovalPolicy
+
+/// Human-in-the-loop hooks of a `Client` (see `Client::set_approval_handler`).
+///
+/// - `review_sampling` is consulted before a `sampling/createMessage` is passed to the sampling handler.
+/// - `tool_call_policy` then `review_tool_call` are consulted before a `tools/call` is sent.
+///
+/// The tools (and their annotations) are the ones from the last `tools/list` responses received by the client
+/// (listed again when a called tool is not known, e.g., not listed yet, or after a `tools/list_changed`).
+pub trait ApprovalHandler: Send + Sync + 'static {
+	/// Reviews a sampling request from the server (default approve).
+	fn review_sampling(
+		&self,
+		params: &CreateMessageParams,
+	) -> impl Future<Output = Approval<CreateMessageParams>> + Send {
+		let _ = params;
+		async { Approval::Approve }
+	}
+
+	/// The policy for a tool call (default `ToolApprovalPolicy::from_tool`, from the tool annotations).
+	fn tool_call_policy(&self, tool: Option<&Tool>) -> ToolApprovalPolicy {
+		ToolApprovalPolicy::from_tool(tool)
+	}
+
+	/// Reviews a tool call with the `ToolApprovalPolicy::Review` policy.
+	///
+	/// Default deny, so that the non read-only tools are not called without review.
+	fn review_tool_call(
+		&self,
+		params: &CallToolParams,
+		tool: Option<&Tool>,
+	) -> impl Future<Output = Approval<CallToolParams>> + Send {
+		let _ = (params, tool);
+		async { Approval::deny("Tool call not reviewed") }
+	}
+}
+
+// region:    --- DynApprovalHandler
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Object safe version of the `ApprovalHandler` (what the client stores).
+pub(crate) trait DynApprovalHandler: Send + Sync {
+	fn review_sampling<'a>(&'a self, params: &'a CreateMessageParams) -> BoxFuture<'a, Approval<CreateMessageParams>>;
+	fn tool_call_policy(&self, tool: Option<&Tool>) -> ToolApprovalPolicy;
+	fn review_tool_call<'a>(
+		&'a self,
+		params: &'a CallToolParams,
+		tool: Option<&'a Tool>,
+	) -> BoxFuture<'a, Approval<CallToolParams>>;
+}
+
+impl<T: ApprovalHandler> DynApprovalHandler for T {
+	fn review_sampling<'a>(&'a self, params: &'a CreateMessageParams) -> BoxFuture<'a, Approval<CreateMessageParams>> {
+		Box::pin(ApprovalHandler::review_sampling(self, params))
+	}
+
+	fn tool_call_policy(&self, tool: Option<&Tool>) -> ToolApprovalPolicy {
+		ApprovalHandler::tool_call_policy(self, tool)
+	}
+
+	fn review_tool_call<'a>(
+		&'a self,
+		params: &'a CallToolParams,
+		tool: Option<&'a Tool>,
+	) -> BoxFuture<'a, Approval<CallToolParams>> {
+		Box::pin(ApprovalHandler::review_tool_call(self, params, tool))
+	}
+}
+
+// endregion: --- DynApprovalHandler
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mcp::{ToolAnnotations, ToolInputSchema};
+
+	#[test]
+	fn test_approval_tool_policy_from_annotations() {
+		// -- Setup & Fixtures
+		let fx_tool = |read_only: Option<bool>, destructive: Option<bool>| {
+			let mut annotations = ToolAnnotations::new();
+			annotations.read_only_hint = read_only;
+			annotations.destructive_hint = destructive;
+			Tool::new("fx_tool", ToolInputSchema::new()).with_annotations(annotations)
+		};
+
+		// -- Check
+		let read_only = fx_tool(Some(true), None);
+		assert_eq!(
+			ToolApprovalPolicy::from_tool(Some(&read_only)),
+			ToolApprovalPolicy::Approve
+		);
+		let additive = fx_tool(Some(false), Some(false));
+		assert_eq!(
+			ToolApprovalPolicy::from_tool(Some(&additive)),
+			ToolApprovalPolicy::Approve
+		);
+		let destructive_by_default = fx_tool(None, None);
+		assert_eq!(
+			ToolApprovalPolicy::from_tool(Some(&destructive_by_default)),
+			ToolApprovalPolicy::Review
+		);
+		let no_annotations = Tool::new("plain", ToolInputSchema::new());
+		assert_eq!(
+			ToolApprovalPolicy::from_tool(Some(&no_annotations)),
+			ToolApprovalPolicy::Review
+		);
+		assert_eq!(ToolApprovalPolicy::from_tool(None), ToolApprovalPolicy::Review);
+	}
+}
+
+// endregion: --- Tests
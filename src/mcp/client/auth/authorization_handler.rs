@@ -0,0 +1,69 @@
+use crate::mcp::client::auth::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Lets the user authorize the client: opens the authorization url (e.g., in the browser),
+/// and returns the redirect url received by the `redirect_uri` (with the `code` and `state` query params).
+///
+/// For a native app, typically opens the browser and listens on a `http://127.0.0.1:<port>/callback` redirect uri.
+pub trait AuthorizationHandlerAsyncFn: Send + Sync {
+	fn exec_fn(&self, authorization_url: String) -> Pin<Box<dyn Future<Output = Result<String>> + Send>>;
+}
+
+impl std::fmt::Debug for dyn AuthorizationHandlerAsyncFn {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "AuthorizationHandlerAsyncFn")
+	}
+}
+
+// region:    --- Adapter for generic async functions
+
+struct GenericFnAdapter<FN, FUT>
+where
+	FN: FnOnce(String) -> FUT + Send + Sync + Clone + 'static,
+	FUT: Future<Output = Result<String>> + Send + 'static,
+{
+	f: FN,
+	_phantom: std::marker::PhantomData<fn() -> FUT>,
+}
+
+impl<FN, FUT> AuthorizationHandlerAsyncFn for GenericFnAdapter<FN, FUT>
+where
+	FN: FnOnce(String) -> FUT + Send + Sync + Clone + 'static,
+	FUT: Future<Output = Result<String>> + Send + 'static,
+{
+	fn exec_fn(&self, authorization_url: String) -> Pin<Box<dyn Future<Output = Result<String>> + Send>> {
+		Box::pin((self.f.clone())(authorization_url))
+	}
+}
+
+// endregion: --- Adapter for generic async functions
+
+// region:    --- IntoAuthorizationHandlerAsyncFn Trait ---
+
+pub trait IntoAuthorizationHandlerAsyncFn {
+	fn into_authorization_handler(self) -> Arc<Box<dyn AuthorizationHandlerAsyncFn>>;
+}
+
+impl IntoAuthorizationHandlerAsyncFn for Arc<Box<dyn AuthorizationHandlerAsyncFn>> {
+	fn into_authorization_handler(self) -> Arc<Box<dyn AuthorizationHandlerAsyncFn>> {
+		self
+	}
+}
+
+impl<F, Fut> IntoAuthorizationHandlerAsyncFn for F
+where
+	F: FnOnce(String) -> Fut + Send + Sync + Clone + 'static,
+	Fut: Future<Output = Result<String>> + Send + 'static,
+{
+	fn into_authorization_handler(self) -> Arc<Box<dyn AuthorizationHandlerAsyncFn>> {
+		let adapter = GenericFnAdapter {
+			f: self,
+			_phantom: std::marker::PhantomData,
+		};
+		Arc::new(Box::new(adapter))
+	}
+}
+
+// endregion: --- IntoAuthorizationHandlerAsyncFn Trait ---
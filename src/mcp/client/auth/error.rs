@@ -0,0 +1,69 @@
+use derive_more::{Display, From};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Display, From)]
+#[display("{self:?}")]
+pub enum Error {
+	#[from(String, &String, &str)]
+	Custom(String),
+
+	// -- Discovery & Registration
+	/// The authorization server metadata could not be fetched (and the default endpoints do not apply).
+	MetadataDiscovery {
+		url: String,
+		cause: String,
+	},
+	/// No client id configured nor stored, and the authorization server does not support dynamic registration.
+	NoClientId,
+	ClientRegistration {
+		status: u16,
+		body: String,
+	},
+
+	// -- Authorization
+	/// An authorization is needed, but no authorization handler (to open the browser) was set.
+	NoAuthorizationHandler,
+	/// The authorization server redirected with an error (e.g., `access_denied`).
+	AuthorizationDenied {
+		error: String,
+		description: Option<String>,
+	},
+	/// The redirect `state` does not match the one of the authorization request.
+	AuthorizationStateMismatch,
+	AuthorizationNoCode,
+
+	// -- Token
+	/// The token endpoint responded with an error (e.g., `invalid_grant`).
+	TokenRequest {
+		status: u16,
+		error: Option<String>,
+		description: Option<String>,
+	},
+
+	// -- Externals
+	#[from]
+	Reqwest(reqwest::Error),
+	#[from]
+	SerdeJson(serde_json::Error),
+}
+
+// region:    --- Custom
+
+impl Error {
+	pub fn custom_from_err(err: impl std::error::Error) -> Self {
+		Self::Custom(err.to_string())
+	}
+
+	pub fn custom(val: impl Into<String>) -> Self {
+		Self::Custom(val.into())
+	}
+}
+
+// endregion: --- Custom
+
+// region:    --- Error Boilerplate
+
+impl std::error::Error for Error {}
+
+// endregion: --- Error Boilerplate
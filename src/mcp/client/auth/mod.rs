@@ -0,0 +1,18 @@
+//! OAuth 2.1 authorization for the HTTP transport (see `OAuthClient` and `ClientHttpTransportConfig::with_oauth`).
+
+// region:    --- Modules
+
+mod authorization_handler;
+mod error;
+mod oauth_client;
+mod oauth_types;
+mod pkce;
+mod token_store;
+
+pub use authorization_handler::*;
+pub use error::{Error, Result};
+pub use oauth_client::*;
+pub use oauth_types::{OAuthCredentials, OAuthTokens};
+pub use token_store::{InMemoryTokenStore, TokenStore};
+
+// endregion: --- Modules
@@ -0,0 +1,622 @@
+use crate::mcp::client::auth::oauth_types::{
+	AuthorizationServerMetadata, ClientRegistrationRequest, ClientRegistrationResponse, ProtectedResourceMetadata,
+	TokenErrorResponse, TokenResponse,
+};
+use crate::mcp::client::auth::pkce::{Pkce, random_urlsafe};
+use crate::mcp::client::auth::token_store::DynTokenStore;
+use crate::mcp::client::auth::{
+	AuthorizationHandlerAsyncFn, Error, InMemoryTokenStore, IntoAuthorizationHandlerAsyncFn, OAuthCredentials,
+	OAuthTokens, Result, TokenStore,
+};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::{debug, info, warn};
+
+/// The OAuth 2.1 authorization of the HTTP transport (see `ClientHttpTransportConfig::with_oauth`).
+///
+/// On a 401 from the MCP server:
+/// - Discovers the authorization server (from the `resource_metadata` of the `WWW-Authenticate` header,
+///   or the MCP server origin), and its metadata (falling back to the default `/authorize`, `/token`, `/register`).
+/// - Refreshes the token when there is a refresh token.
+/// - Otherwise, registers the client (dynamic client registration) if no client id,
+///   and runs the authorization code flow with PKCE through the authorization handler.
+///
+/// The credentials (client id and tokens) are kept in the `TokenStore` (in memory by default).
+pub struct OAuthClient {
+	client_name: String,
+	redirect_uri: String,
+	scopes: Vec<String>,
+	/// The pre-registered client (dynamic client registration is used when None).
+	client_id: Option<String>,
+	client_secret: Option<String>,
+
+	token_store: Arc<dyn DynTokenStore>,
+	authorization_handler: Option<Arc<Box<dyn AuthorizationHandlerAsyncFn>>>,
+	http_client: reqwest::Client,
+
+	/// The authorization server metadata (discovered on the first authorization).
+	metadata: RwLock<Option<Arc<AuthorizationServerMetadata>>>,
+	/// Serializes the refresh and authorization flows (so that concurrent 401s authorize once).
+	flow_lock: tokio::sync::Mutex<()>,
+}
+
+/// Constructor
+impl OAuthClient {
+	/// - `client_name` is the name given on dynamic client registration.
+	/// - `redirect_uri` is where the authorization server redirects (received by the authorization handler).
+	pub fn new(client_name: impl Into<String>, redirect_uri: impl Into<String>) -> Self {
+		Self {
+			client_name: client_name.into(),
+			redirect_uri: redirect_uri.into(),
+			scopes: Vec::new(),
+			client_id: None,
+			client_secret: None,
+			token_store: Arc::new(InMemoryTokenStore::default()),
+			authorization_handler: None,
+			http_client: reqwest::Client::new(),
+			metadata: RwLock::default(),
+			flow_lock: tokio::sync::Mutex::default(),
+		}
+	}
+}
+
+/// Builders
+impl OAuthClient {
+	pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		self.scopes = scopes.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Uses a pre-registered client (instead of the dynamic client registration).
+	pub fn with_client_credentials(mut self, client_id: impl Into<String>, client_secret: Option<String>) -> Self {
+		self.client_id = Some(client_id.into());
+		self.client_secret = client_secret;
+		self
+	}
+
+	pub fn with_token_store(mut self, token_store: impl TokenStore) -> Self {
+		self.token_store = Arc::new(token_store);
+		self
+	}
+
+	/// Sets the handler opening the authorization url and returning the redirect url (see `AuthorizationHandlerAsyncFn`).
+	pub fn with_authorization_handler(mut self, authorization_handler: impl IntoAuthorizationHandlerAsyncFn) -> Self {
+		self.authorization_handler = Some(authorization_handler.into_authorization_handler());
+		self
+	}
+
+	/// The `reqwest::Client` for the authorization server requests.
+	pub fn with_reqwest_client(mut self, http_client: reqwest::Client) -> Self {
+		self.http_client = http_client;
+		self
+	}
+}
+
+/// Tokens
+impl OAuthClient {
+	/// The access token for the MCP server requests (None before the first authorization).
+	///
+	/// An expired token is refreshed first (when possible, otherwise returned as is, so that the 401 re-authorizes).
+	pub async fn access_token(&self) -> Result<Option<String>> {
+		let Some(tokens) = self.tokens().await? else {
+			return Ok(None);
+		};
+		let metadata = self.metadata.read().unwrap_or_else(|e| e.into_inner()).clone();
+		let (true, Some(metadata)) = (tokens.is_expired(), metadata) else {
+			return Ok(Some(tokens.access_token));
+		};
+
+		let _flow_guard = self.flow_lock.lock().await;
+		// Another request might have refreshed the token while waiting for the lock.
+		let Some(credentials) = self.token_store.load().await? else {
+			return Ok(None);
+		};
+		let Some(tokens) = credentials.tokens.clone() else {
+			return Ok(None);
+		};
+		let (true, Some(refresh_token)) = (tokens.is_expired(), tokens.refresh_token.clone()) else {
+			return Ok(Some(tokens.access_token));
+		};
+		match self.refresh(&metadata, credentials, refresh_token).await {
+			Ok(tokens) => Ok(Some(tokens.access_token)),
+			Err(err) => {
+				warn!("OAuth token refresh failed. Cause: {err}");
+				Ok(Some(tokens.access_token))
+			}
+		}
+	}
+
+	/// Authorizes the client for the MCP server at `server_url` (e.g., before connecting).
+	pub async fn authorize(&self, server_url: &str) -> Result<()> {
+		let _flow_guard = self.flow_lock.lock().await;
+		let metadata = self.discover(server_url, None).await?;
+		let credentials = self.token_store.load().await?;
+		self.authorize_with(&metadata, server_url, credentials).await
+	}
+
+	/// Handles a 401 of the MCP server for the `rejected_token`: refreshes the token, or authorizes again.
+	pub(crate) async fn handle_unauthorized(
+		&self,
+		server_url: &str,
+		www_authenticate: Option<&str>,
+		rejected_token: Option<&str>,
+	) -> Result<()> {
+		let _flow_guard = self.flow_lock.lock().await;
+
+		// -- Another request got a new token while waiting for the lock
+		let credentials = self.token_store.load().await?;
+		let current_tokens = credentials.as_ref().and_then(|credentials| credentials.tokens.as_ref());
+		if let Some(current_tokens) = current_tokens {
+			if Some(current_tokens.access_token.as_str()) != rejected_token && !current_tokens.is_expired() {
+				return Ok(());
+			}
+		}
+
+		let metadata = self.discover(server_url, www_authenticate).await?;
+
+		// -- Refresh when possible
+		let refresh_token = current_tokens.and_then(|tokens| tokens.refresh_token.clone());
+		if let (Some(credentials), Some(refresh_token)) = (credentials.clone(), refresh_token) {
+			match self.refresh(&metadata, credentials, refresh_token).await {
+				Ok(_) => return Ok(()),
+				Err(err) => debug!("OAuth token refresh failed, authorizing again. Cause: {err}"),
+			}
+		}
+
+		self.authorize_with(&metadata, server_url, credentials).await
+	}
+
+	async fn tokens(&self) -> Result<Option<OAuthTokens>> {
+		let credentials = self.token_store.load().await?;
+		Ok(credentials.and_then(|credentials| credentials.tokens))
+	}
+}
+
+/// Flows
+impl OAuthClient {
+	/// The authorization code flow with PKCE (registering the client first if needed).
+	async fn authorize_with(
+		&self,
+		metadata: &AuthorizationServerMetadata,
+		server_url: &str,
+		credentials: Option<OAuthCredentials>,
+	) -> Result<()> {
+		let authorization_handler = self.authorization_handler.as_ref().ok_or(Error::NoAuthorizationHandler)?;
+		let mut credentials = self.client_credentials(metadata, credentials).await?;
+
+		// -- Build the authorization url
+		let pkce = Pkce::new()?;
+		let state = random_urlsafe(16)?;
+		let mut authorization_url = Url::parse(&metadata.authorization_endpoint).map_err(Error::custom_from_err)?;
+		{
+			let mut query = authorization_url.query_pairs_mut();
+			query
+				.append_pair("response_type", "code")
+				.append_pair("client_id", &credentials.client_id)
+				.append_pair("redirect_uri", &self.redirect_uri)
+				.append_pair("code_challenge", &pkce.challenge)
+				.append_pair("code_challenge_method", Pkce::METHOD)
+				.append_pair("state", &state)
+				.append_pair("resource", server_url);
+			if !self.scopes.is_empty() {
+				query.append_pair("scope", &self.scopes.join(" "));
+			}
+		}
+
+		// -- Let the user authorize, and get the code from the redirect
+		info!(client_id = %credentials.client_id, "OAuth authorization required");
+		let redirect_url = authorization_handler.exec_fn(authorization_url.to_string()).await?;
+		let redirect_url = Url::parse(&redirect_url).map_err(Error::custom_from_err)?;
+		let mut params: HashMap<String, String> = redirect_url.query_pairs().into_owned().collect();
+		if let Some(error) = params.remove("error") {
+			return Err(Error::AuthorizationDenied {
+				error,
+				description: params.remove("error_description"),
+			});
+		}
+		if params.get("state") != Some(&state) {
+			return Err(Error::AuthorizationStateMismatch);
+		}
+		let code = params.remove("code").ok_or(Error::AuthorizationNoCode)?;
+
+		// -- Exchange the code for the tokens
+		let mut form = vec![
+			("grant_type", "authorization_code".to_string()),
+			("code", code),
+			("redirect_uri", self.redirect_uri.clone()),
+			("client_id", credentials.client_id.clone()),
+			("code_verifier", pkce.verifier),
+			("resource", server_url.to_string()),
+		];
+		if let Some(client_secret) = credentials.client_secret.clone() {
+			form.push(("client_secret", client_secret));
+		}
+		let token_response = self.token_request(metadata, &form).await?;
+
+		credentials.tokens = Some(token_response.into_tokens(None));
+		self.token_store.save(credentials).await?;
+		info!("OAuth authorization completed");
+
+		Ok(())
+	}
+
+	async fn refresh(
+		&self,
+		metadata: &AuthorizationServerMetadata,
+		mut credentials: OAuthCredentials,
+		refresh_token: String,
+	) -> Result<OAuthTokens> {
+		let mut form = vec![
+			("grant_type", "refresh_token".to_string()),
+			("refresh_token", refresh_token.clone()),
+			("client_id", credentials.client_id.clone()),
+		];
+		if let Some(client_secret) = credentials.client_secret.clone() {
+			form.push(("client_secret", client_secret));
+		}
+		let token_response = self.token_request(metadata, &form).await?;
+
+		let tokens = token_response.into_tokens(Some(refresh_token));
+		credentials.tokens = Some(tokens.clone());
+		self.token_store.save(credentials).await?;
+		debug!("OAuth token refreshed");
+
+		Ok(tokens)
+	}
+
+	/// The stored client, or the configured one, or a newly registered one (dynamic client registration).
+	async fn client_credentials(
+		&self,
+		metadata: &AuthorizationServerMetadata,
+		credentials: Option<OAuthCredentials>,
+	) -> Result<OAuthCredentials> {
+		if let Some(credentials) = credentials {
+			return Ok(credentials);
+		}
+		if let Some(client_id) = self.client_id.clone() {
+			return Ok(OAuthCredentials {
+				client_id,
+				client_secret: self.client_secret.clone(),
+				tokens: None,
+			});
+		}
+
+		let registration_endpoint = metadata.registration_endpoint.as_deref().ok_or(Error::NoClientId)?;
+		let registration = ClientRegistrationRequest {
+			client_name: self.client_name.clone(),
+			redirect_uris: vec![self.redirect_uri.clone()],
+			grant_types: vec!["authorization_code".to_string(), "refresh_token".to_string()],
+			response_types: vec!["code".to_string()],
+			token_endpoint_auth_method: "none".to_string(),
+			scope: (!self.scopes.is_empty()).then(|| self.scopes.join(" ")),
+		};
+		let res = self
+			.http_client
+			.post(registration_endpoint)
+			.header(CONTENT_TYPE, "application/json")
+			.header(ACCEPT, "application/json")
+			.body(serde_json::to_string(&registration)?)
+			.send()
+			.await?;
+		let status = res.status();
+		let body = res.text().await?;
+		if !status.is_success() {
+			return Err(Error::ClientRegistration {
+				status: status.as_u16(),
+				body,
+			});
+		}
+		let registered: ClientRegistrationResponse = serde_json::from_str(&body)?;
+		debug!(client_id = %registered.client_id, "OAuth client registered");
+
+		let credentials = OAuthCredentials {
+			client_id: registered.client_id,
+			client_secret: registered.client_secret,
+			tokens: None,
+		};
+		self.token_store.save(credentials.clone()).await?;
+
+		Ok(credentials)
+	}
+
+	async fn token_request(
+		&self,
+		metadata: &AuthorizationServerMetadata,
+		form: &[(&str, String)],
+	) -> Result<TokenResponse> {
+		let res = self
+			.http_client
+			.post(&metadata.token_endpoint)
+			.header(ACCEPT, "application/json")
+			.form(form)
+			.send()
+			.await?;
+		let status = res.status();
+		let body = res.text().await?;
+		if !status.is_success() {
+			let error = serde_json::from_str::<TokenErrorResponse>(&body).ok();
+			return Err(Error::TokenRequest {
+				status: status.as_u16(),
+				error: error.as_ref().map(|error| error.error.clone()),
+				description: error.and_then(|error| error.error_description),
+			});
+		}
+		Ok(serde_json::from_str(&body)?)
+	}
+}
+
+/// Discovery
+impl OAuthClient {
+	/// The authorization server metadata (cached after the first discovery).
+	async fn discover(
+		&self,
+		server_url: &str,
+		www_authenticate: Option<&str>,
+	) -> Result<Arc<AuthorizationServerMetadata>> {
+		if let Some(metadata) = self.metadata.read().unwrap_or_else(|e| e.into_inner()).clone() {
+			return Ok(metadata);
+		}
+
+		let server_url = Url::parse(server_url).map_err(Error::custom_from_err)?;
+
+		// -- The authorization server (from the protected resource metadata, or the MCP server origin)
+		let mut issuer = origin_url(&server_url)?;
+		if let Some(resource_metadata_url) = www_authenticate.and_then(resource_metadata_url) {
+			match self.get_json::<ProtectedResourceMetadata>(&resource_metadata_url).await {
+				Ok(Some(resource_metadata)) => {
+					if let Some(authorization_server) = resource_metadata.authorization_servers.first() {
+						issuer = Url::parse(authorization_server).map_err(Error::custom_from_err)?;
+					}
+				}
+				Ok(None) => warn!(%resource_metadata_url, "OAuth protected resource metadata not found"),
+				Err(err) => warn!(%resource_metadata_url, "OAuth protected resource metadata failed. Cause: {err}"),
+			}
+		}
+
+		// -- The authorization server metadata (RFC 8414, with the default endpoints as fallback)
+		let origin = issuer.origin().ascii_serialization();
+		let path = issuer.path().trim_end_matches('/');
+		let metadata_url = format!("{origin}/.well-known/oauth-authorization-server{path}");
+		let metadata = match self.get_json::<AuthorizationServerMetadata>(&metadata_url).await {
+			Ok(Some(metadata)) => metadata,
+			Ok(None) => {
+				debug!(%metadata_url, "OAuth authorization server metadata not found, using the default endpoints");
+				AuthorizationServerMetadata {
+					authorization_endpoint: format!("{origin}/authorize"),
+					token_endpoint: format!("{origin}/token"),
+					registration_endpoint: Some(format!("{origin}/register")),
+					code_challenge_methods_supported: Vec::new(),
+				}
+			}
+			Err(err) => {
+				return Err(Error::MetadataDiscovery {
+					url: metadata_url,
+					cause: err.to_string(),
+				});
+			}
+		};
+		if !metadata.code_challenge_methods_supported.is_empty()
+			&& !metadata
+				.code_challenge_methods_supported
+				.iter()
+				.any(|method| method == Pkce::METHOD)
+		{
+			warn!("OAuth authorization server does not advertise the S256 PKCE method");
+		}
+
+		let metadata = Arc::new(metadata);
+		*self.metadata.write().unwrap_or_else(|e| e.into_inner()) = Some(metadata.clone());
+
+		Ok(metadata)
+	}
+
+	/// GETs a JSON document (None when not found).
+	async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<Option<T>> {
+		let res = self.http_client.get(url).header(ACCEPT, "application/json").send().await?;
+		match res.status() {
+			StatusCode::NOT_FOUND => Ok(None),
+			status if status.is_success() => Ok(Some(serde_json::from_str(&res.text().await?)?)),
+			status => Err(Error::custom(format!("GET '{url}' failed with status {status}"))),
+		}
+	}
+}
+
+// region:    --- Support
+
+/// The `resource_metadata` url of a `WWW-Authenticate: Bearer resource_metadata="..."` header (RFC 9728).
+fn resource_metadata_url(www_authenticate: &str) -> Option<String> {
+	let (_, rest) = www_authenticate.split_once("resource_metadata=")?;
+	let value = match rest.strip_prefix('"') {
+		Some(quoted) => quoted.split('"').next()?,
+		None => rest.split([',', ' ']).next()?,
+	};
+	(!value.is_empty()).then(|| value.to_string())
+}
+
+/// The url without the path (the authorization base url of the 2025-03-26 spec).
+fn origin_url(url: &Url) -> Result<Url> {
+	Url::parse(&url.origin().ascii_serialization()).map_err(Error::custom_from_err)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mcp::client::auth::pkce::s256_challenge;
+	use axum::extract::{Form, State};
+	use axum::routing::{get, post};
+	use axum::{Json, Router};
+	use serde_json::{Value, json};
+	use std::sync::Mutex;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use tokio::net::TcpListener;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	/// The state shared by the mock authorization server and the authorization handler.
+	#[derive(Default)]
+	struct MockAuthState {
+		code_challenge: Mutex<Option<String>>,
+		registrations: AtomicUsize,
+		authorizations: AtomicUsize,
+	}
+
+	/// A minimal authorization server, with the endpoints of the flow.
+	async fn start_mock_auth_server(state: Arc<MockAuthState>) -> Result<String> {
+		let listener = TcpListener::bind("127.0.0.1:0").await?;
+		let base_url = format!("http://{}", listener.local_addr()?);
+
+		let app = Router::new()
+			.route(
+				"/.well-known/oauth-protected-resource",
+				get(mock_auth_resource_metadata),
+			)
+			.route(
+				"/.well-known/oauth-authorization-server/auth",
+				get(mock_auth_server_metadata),
+			)
+			.route("/auth/register", post(mock_auth_register))
+			.route("/auth/token", post(mock_auth_token))
+			.with_state(MockAuthServer {
+				state,
+				base_url: base_url.clone(),
+			});
+		tokio::spawn(async move { axum::serve(listener, app).await });
+
+		Ok(base_url)
+	}
+
+	/// The state of the mock authorization server routes.
+	#[derive(Clone)]
+	struct MockAuthServer {
+		state: Arc<MockAuthState>,
+		base_url: String,
+	}
+
+	async fn mock_auth_resource_metadata(State(server): State<MockAuthServer>) -> Json<Value> {
+		let base_url = server.base_url;
+		Json(json!({
+			"resource": format!("{base_url}/mcp"),
+			"authorization_servers": [format!("{base_url}/auth")]
+		}))
+	}
+
+	async fn mock_auth_server_metadata(State(server): State<MockAuthServer>) -> Json<Value> {
+		let base_url = server.base_url;
+		Json(json!({
+			"issuer": format!("{base_url}/auth"),
+			"authorization_endpoint": format!("{base_url}/auth/authorize"),
+			"token_endpoint": format!("{base_url}/auth/token"),
+			"registration_endpoint": format!("{base_url}/auth/register"),
+			"code_challenge_methods_supported": ["S256"]
+		}))
+	}
+
+	async fn mock_auth_register(State(server): State<MockAuthServer>) -> Json<Value> {
+		server.state.registrations.fetch_add(1, Ordering::SeqCst);
+		Json(json!({"client_id": "client-1"}))
+	}
+
+	async fn mock_auth_token(
+		State(server): State<MockAuthServer>,
+		Form(form): Form<HashMap<String, String>>,
+	) -> (StatusCode, Json<Value>) {
+		let invalid_grant = (StatusCode::BAD_REQUEST, Json(json!({"error": "invalid_grant"})));
+		match form.get("grant_type").map(String::as_str) {
+			Some("authorization_code") => {
+				let challenge = server.state.code_challenge.lock().unwrap_or_else(|e| e.into_inner()).clone();
+				let verified = form.get("code_verifier").map(|verifier| s256_challenge(verifier)) == challenge;
+				if !verified || form.get("code").map(String::as_str) != Some("code-1") {
+					return invalid_grant;
+				}
+				let tokens = json!({"access_token": "access-1", "refresh_token": "refresh-1", "expires_in": 3600});
+				(StatusCode::OK, Json(tokens))
+			}
+			Some("refresh_token") if form.get("refresh_token").map(String::as_str) == Some("refresh-1") => (
+				StatusCode::OK,
+				Json(json!({"access_token": "access-2", "expires_in": 3600})),
+			),
+			_ => invalid_grant,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_oauth_client_authorize_and_refresh() -> Result<()> {
+		// -- Setup & Fixtures
+		let state = Arc::new(MockAuthState::default());
+		let base_url = start_mock_auth_server(state.clone()).await?;
+		let server_url = format!("{base_url}/mcp");
+		let www_authenticate = format!(r#"Bearer resource_metadata="{base_url}/.well-known/oauth-protected-resource""#);
+
+		let handler_state = state.clone();
+		let oauth = OAuthClient::new("Test Client", "http://127.0.0.1/callback")
+			.with_scopes(["mcp"])
+			.with_authorization_handler(move |authorization_url: String| {
+				let state = handler_state.clone();
+				async move {
+					// Plays the user authorizing in the browser (the server redirecting with the code and state).
+					state.authorizations.fetch_add(1, Ordering::SeqCst);
+					let url = reqwest::Url::parse(&authorization_url).map_err(Error::custom_from_err)?;
+					let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+					*state.code_challenge.lock().unwrap_or_else(|e| e.into_inner()) =
+						params.get("code_challenge").cloned();
+					let state_param = params.get("state").cloned().unwrap_or_default();
+					Ok(format!("http://127.0.0.1/callback?code=code-1&state={state_param}"))
+				}
+			});
+
+		// -- Exec
+		let before = oauth.access_token().await?;
+		oauth.handle_unauthorized(&server_url, Some(&www_authenticate), None).await?;
+		let authorized = oauth.access_token().await?;
+		oauth
+			.handle_unauthorized(&server_url, Some(&www_authenticate), authorized.as_deref())
+			.await?;
+		let refreshed = oauth.access_token().await?;
+		// The rejected token is not the current one anymore (no new flow).
+		oauth
+			.handle_unauthorized(&server_url, Some(&www_authenticate), authorized.as_deref())
+			.await?;
+
+		// -- Check
+		assert_eq!(before, None);
+		assert_eq!(authorized.as_deref(), Some("access-1"));
+		assert_eq!(refreshed.as_deref(), Some("access-2"));
+		assert_eq!(oauth.access_token().await?.as_deref(), Some("access-2"));
+		assert_eq!(state.registrations.load(Ordering::SeqCst), 1);
+		assert_eq!(state.authorizations.load(Ordering::SeqCst), 1);
+		let credentials = oauth.token_store.load().await?.ok_or("Should have credentials")?;
+		assert_eq!(credentials.client_id, "client-1");
+		assert_eq!(
+			credentials.tokens.and_then(|tokens| tokens.refresh_token).as_deref(),
+			Some("refresh-1")
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_oauth_client_resource_metadata_url() {
+		assert_eq!(
+			resource_metadata_url(r#"Bearer realm="mcp", resource_metadata="https://a.b/.well-known/x""#).as_deref(),
+			Some("https://a.b/.well-known/x")
+		);
+		assert_eq!(
+			resource_metadata_url("Bearer resource_metadata=https://a.b/x, error=\"invalid_token\"").as_deref(),
+			Some("https://a.b/x")
+		);
+		assert_eq!(resource_metadata_url(r#"Bearer realm="mcp""#), None);
+	}
+}
+
+// endregion: --- Tests
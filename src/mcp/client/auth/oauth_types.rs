@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The margin before the token expiration to consider it expired (so it is refreshed before being rejected).
+const EXPIRATION_MARGIN: Duration = Duration::from_secs(30);
+
+// region:    --- OAuthCredentials
+
+/// What the `TokenStore` persists for an MCP server: the client registration and the tokens.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthCredentials {
+	pub client_id: String,
+	pub client_secret: Option<String>,
+	pub tokens: Option<OAuthTokens>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthTokens {
+	pub access_token: String,
+	pub refresh_token: Option<String>,
+	/// The expiration time, in seconds since the unix epoch (None when unknown).
+	pub expires_at: Option<u64>,
+	pub scope: Option<String>,
+}
+
+impl OAuthTokens {
+	/// Returns true if the access token expires in less than 30 seconds (false when the expiration is unknown).
+	pub fn is_expired(&self) -> bool {
+		let Some(expires_at) = self.expires_at else {
+			return false;
+		};
+		now_secs() + EXPIRATION_MARGIN.as_secs() >= expires_at
+	}
+}
+
+// endregion: --- OAuthCredentials
+
+// region:    --- Metadata
+
+/// The OAuth 2.0 Protected Resource Metadata (RFC 9728) of the MCP server.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ProtectedResourceMetadata {
+	#[serde(default)]
+	pub authorization_servers: Vec<String>,
+}
+
+/// The OAuth 2.0 Authorization Server Metadata (RFC 8414), the fields used by the flow.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AuthorizationServerMetadata {
+	pub authorization_endpoint: String,
+	pub token_endpoint: String,
+	pub registration_endpoint: Option<String>,
+	#[serde(default)]
+	pub code_challenge_methods_supported: Vec<String>,
+}
+
+// endregion: --- Metadata
+
+// region:    --- Registration
+
+/// The Dynamic Client Registration request (RFC 7591).
+#[skip_serializing_none]
+#[derive(Debug, Serialize)]
+pub(crate) struct ClientRegistrationRequest {
+	pub client_name: String,
+	pub redirect_uris: Vec<String>,
+	pub grant_types: Vec<String>,
+	pub response_types: Vec<String>,
+	pub token_endpoint_auth_method: String,
+	pub scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ClientRegistrationResponse {
+	pub client_id: String,
+	pub client_secret: Option<String>,
+}
+
+// endregion: --- Registration
+
+// region:    --- Token
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct TokenResponse {
+	pub access_token: String,
+	pub refresh_token: Option<String>,
+	pub expires_in: Option<u64>,
+	pub scope: Option<String>,
+}
+
+impl TokenResponse {
+	/// The tokens to store (keeping the previous refresh token when the server does not rotate it).
+	pub fn into_tokens(self, previous_refresh_token: Option<String>) -> OAuthTokens {
+		OAuthTokens {
+			access_token: self.access_token,
+			refresh_token: self.refresh_token.or(previous_refresh_token),
+			expires_at: self.expires_in.map(|expires_in| now_secs() + expires_in),
+			scope: self.scope,
+		}
+	}
+}
+
+/// The error response of the token endpoint (RFC 6749 section 5.2).
+#[derive(Debug, Deserialize)]
+pub(crate) struct TokenErrorResponse {
+	pub error: String,
+	pub error_description: Option<String>,
+}
+
+// endregion: --- Token
+
+// region:    --- Support
+
+fn now_secs() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|duration| duration.as_secs())
+		.unwrap_or_default()
+}
+
+// endregion: --- Support
@@ -0,0 +1,62 @@
+use crate::mcp::client::auth::{Error, Result};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest as _, Sha256};
+
+/// The PKCE code verifier and its S256 challenge (RFC 7636), for one authorization request.
+pub(crate) struct Pkce {
+	pub verifier: String,
+	pub challenge: String,
+}
+
+impl Pkce {
+	pub const METHOD: &'static str = "S256";
+
+	pub fn new() -> Result<Self> {
+		// 32 random bytes, which gives a 43 chars verifier (the min of the RFC)
+		let verifier = random_urlsafe(32)?;
+		let challenge = s256_challenge(&verifier);
+		Ok(Self { verifier, challenge })
+	}
+}
+
+/// The S256 code challenge of a verifier: `BASE64URL(SHA256(verifier))`.
+pub(crate) fn s256_challenge(verifier: &str) -> String {
+	URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// A random base64url string of `len` random bytes (for the PKCE verifier and the `state`).
+pub(crate) fn random_urlsafe(len: usize) -> Result<String> {
+	let mut bytes = vec![0u8; len];
+	getrandom::getrandom(&mut bytes).map_err(|err| Error::custom(format!("Random generation failed. Cause: {err}")))?;
+	Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_pkce_s256_challenge() -> Result<()> {
+		// -- Setup & Fixtures
+		// From the RFC 7636 Appendix B example
+		let fx_verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+		// -- Exec
+		let challenge = s256_challenge(fx_verifier);
+		let pkce = Pkce::new()?;
+
+		// -- Check
+		assert_eq!(challenge, "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+		assert_eq!(pkce.verifier.len(), 43);
+		assert_eq!(pkce.challenge, s256_challenge(&pkce.verifier));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
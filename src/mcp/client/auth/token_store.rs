@@ -0,0 +1,59 @@
+use crate::mcp::client::auth::{OAuthCredentials, Result};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::RwLock;
+
+/// Persists the OAuth credentials (client registration and tokens) of an MCP server.
+///
+/// The default is the `InMemoryTokenStore`. Implement it to keep the credentials across runs
+/// (e.g., in a file or the OS keychain).
+///
+/// NOTE: A store is for one MCP server (each `OAuthClient` having its own store).
+pub trait TokenStore: Send + Sync + 'static {
+	fn load(&self) -> impl Future<Output = Result<Option<OAuthCredentials>>> + Send;
+
+	fn save(&self, credentials: OAuthCredentials) -> impl Future<Output = Result<()>> + Send;
+}
+
+// region:    --- InMemoryTokenStore
+
+/// Keeps the credentials in memory (for the life of the `OAuthClient`).
+#[derive(Debug, Default)]
+pub struct InMemoryTokenStore {
+	credentials: RwLock<Option<OAuthCredentials>>,
+}
+
+impl TokenStore for InMemoryTokenStore {
+	async fn load(&self) -> Result<Option<OAuthCredentials>> {
+		Ok(self.credentials.read().unwrap_or_else(|e| e.into_inner()).clone())
+	}
+
+	async fn save(&self, credentials: OAuthCredentials) -> Result<()> {
+		*self.credentials.write().unwrap_or_else(|e| e.into_inner()) = Some(credentials);
+		Ok(())
+	}
+}
+
+// endregion: --- InMemoryTokenStore
+
+// region:    --- DynTokenStore
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Object safe version of the `TokenStore` (what the `OAuthClient` stores).
+pub(crate) trait DynTokenStore: Send + Sync {
+	fn load(&self) -> BoxFuture<'_, Result<Option<OAuthCredentials>>>;
+	fn save(&self, credentials: OAuthCredentials) -> BoxFuture<'_, Result<()>>;
+}
+
+impl<T: TokenStore> DynTokenStore for T {
+	fn load(&self) -> BoxFuture<'_, Result<Option<OAuthCredentials>>> {
+		Box::pin(TokenStore::load(self))
+	}
+
+	fn save(&self, credentials: OAuthCredentials) -> BoxFuture<'_, Result<()>> {
+		Box::pin(TokenStore::save(self, credentials))
+	}
+}
+
+// endregion: --- DynTokenStore
@@ -0,0 +1,29 @@
+use crate::mcp::client::ClientState;
+use std::process::ExitStatus;
+use std::time::Duration;
+
+/// The client-level events (see `Client::subscribe_events`).
+#[derive(Debug, Clone)]
+pub enum ClientEvent {
+	/// The client lifecycle state changed.
+	StateChanged(ClientState),
+
+	/// The connection got lost (e.g., the stdio server process exited).
+	///
+	/// Without a reconnect policy, the pending requests failed with `Error::ClientDisconnected`,
+	/// and the client is `ClientState::Closed`. Otherwise, the client is `ClientState::Reconnecting`.
+	Disconnected {
+		reason: String,
+		/// The exit status of the server process (stdio only, when known).
+		exit_status: Option<ExitStatus>,
+	},
+
+	/// A reconnect attempt (starting at 1) is about to start, after this delay.
+	Reconnecting { attempt: u32, delay: Duration },
+
+	/// Reconnected and initialized (the subscriptions and logging level replayed).
+	Reconnected { attempts: u32 },
+
+	/// Gave up reconnecting. The pending requests failed, and the client is `ClientState::Closed`.
+	ReconnectFailed { attempts: u32, reason: String },
+}
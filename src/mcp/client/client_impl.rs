@@ -1,52 +1,566 @@
 use crate::RpcId;
+use crate::mcp::CallToolParams;
+use crate::mcp::CancelledNotificationParams;
+use crate::mcp::ClientRootsCapabilities;
 use crate::mcp::CreateMessageParams;
+use crate::mcp::CreateMessageResult;
 use crate::mcp::InitializeParams;
 use crate::mcp::InitializeResult;
+use crate::mcp::InitializedNotificationParams;
+use crate::mcp::IntoMcpNotification;
 use crate::mcp::IntoMcpRequest;
+use crate::mcp::ListToolsParams;
+use crate::mcp::LoggingLevel;
+use crate::mcp::McpError;
 use crate::mcp::McpMessage;
+use crate::mcp::McpNotification;
 use crate::mcp::McpRequest;
 use crate::mcp::McpResponse;
-use crate::mcp::SamplingMessage;
+use crate::mcp::ProgressNotificationParams;
+use crate::mcp::ProgressToken;
+use crate::mcp::ProtocolVersion;
+use crate::mcp::Root;
+use crate::mcp::RootsListChangedNotificationParams;
+use crate::mcp::SUPPORTED_PROTOCOL_VERSIONS;
+use crate::mcp::ServerNotification;
+use crate::mcp::SetLevelParams;
+use crate::mcp::SubscribeParams;
+use crate::mcp::Tool;
+use crate::mcp::ToolListChangedNotificationParams;
+use crate::mcp::UnsubscribeParams;
+use crate::mcp::client::ClientEvent;
+use crate::mcp::client::ClientState;
 use crate::mcp::client::IntoClientTransport;
+use crate::mcp::client::NotificationStream;
+use crate::mcp::client::RequestHandle;
 use crate::mcp::client::SamplingHandlerAsyncFn;
+use crate::mcp::client::approval::DynApprovalHandler;
+use crate::mcp::client::notification_hub::NotificationHub;
+use crate::mcp::client::roots_handler::StaticRoots;
 use crate::mcp::client::sampling_handler::IntoSamplingHandlerAsyncFn;
+use crate::mcp::client::server_request_router::ServerRequestRouter;
 use crate::mcp::client::transport::new_trx_pair;
-use crate::mcp::client::transport::{ClientTransport, ClientTrx, CommRx, CommTx};
+use crate::mcp::client::transport::{ClientTransport, ClientTrx, RequestTransportError};
+use crate::mcp::client::{Approval, ApprovalHandler, ToolApprovalPolicy};
+use crate::mcp::client::{InFlightPolicy, ReconnectPolicy};
+use crate::mcp::client::{IntoRootsHandlerAsyncFn, RootsHandlerAsyncFn};
 use crate::mcp::support::truncate;
-use crate::mcp::{Error, Result};
+use crate::mcp::transport::{CommRx, CommTx};
+use crate::mcp::{Error, Implementation, Result, ServerCapabilities};
 use dashmap::DashMap;
+use futures::FutureExt as _;
+use futures::future::{AbortHandle, Abortable, BoxFuture};
+use rpc_router::RpcError;
 use serde::Serialize;
-use std::sync::Arc;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::process::ExitStatus;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
-type OneShotRes = oneshot::Sender<McpMessage>;
-type ResQueue = Arc<DashMap<RpcId, OneShotRes>>;
+/// The default timeout of the client requests (see `Client::set_request_timeout`).
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often the orphaned pending requests are reaped.
+const REAPER_INTERVAL: Duration = Duration::from_secs(30);
+/// How long the cancelled request ids are kept to ignore their late responses.
+const CANCELLED_REQUEST_TTL: Duration = Duration::from_secs(300);
+/// How long to wait for the server process exit status on an unexpected disconnect.
+const EXIT_STATUS_TIMEOUT: Duration = Duration::from_secs(1);
+
+type OneShotRes = oneshot::Sender<Result<McpMessage>>;
+type ResQueue = Arc<DashMap<RpcId, PendingRequest>>;
+type ProgressTx = flume::Sender<ProgressNotificationParams>;
+
+/// A request sent to the server, waiting for its response.
+struct PendingRequest {
+	tx: OneShotRes,
+	method: String,
+	/// The message sent (to send it again on reconnect, see `InFlightPolicy::Retry`).
+	payload: String,
+	/// Set when the request asked for progress notifications (see `Client::send_request_with_progress`).
+	progress_token: Option<ProgressToken>,
+}
+
+// region:    --- PendingResponse
+
+/// The response of a pending request, to be received with `recv(timeout)`.
+///
+/// Cancels the request when dropped before its response arrived
+/// (i.e., the `send_request..` future or the `RequestHandle` got dropped).
+pub(crate) struct PendingResponse {
+	inner: Arc<ClientInner>,
+	c2s_tx: CommTx,
+	rpc_id: RpcId,
+	method: String,
+	/// When the request got sent (the start of its timeout).
+	sent_at: Instant,
+	rx: oneshot::Receiver<Result<McpMessage>>,
+	done: bool,
+}
+
+impl PendingResponse {
+	pub(crate) fn rpc_id(&self) -> &RpcId {
+		&self.rpc_id
+	}
+
+	pub(crate) fn method(&self) -> &str {
+		&self.method
+	}
+
+	/// The deadline of the response for this timeout (counted from when the request got sent).
+	pub(crate) fn deadline(&self, timeout: Option<Duration>) -> Option<Instant> {
+		timeout.map(|timeout| self.sent_at + timeout)
+	}
+
+	/// Waits for the response, until the `timeout` (counted from when the request got sent).
+	/// On timeout, cancels the request and returns `Error::Timeout`.
+	pub(crate) async fn recv(mut self, timeout: Option<Duration>) -> Result<McpMessage> {
+		let res = match (timeout, self.deadline(timeout)) {
+			(Some(timeout), Some(deadline)) => match tokio::time::timeout_at(deadline.into(), &mut self.rx).await {
+				Ok(res) => res,
+				Err(_) => {
+					let err = Error::Timeout {
+						rpc_id: self.rpc_id.clone(),
+						method: self.method.clone(),
+					};
+					self.cancel(Some(format!("Request timed out after {}ms", timeout.as_millis())));
+					return Err(err);
+				}
+			},
+			_ => (&mut self.rx).await,
+		};
+		self.done = true;
+
+		// Note: The one shot is dropped without a response when the request got cancelled (or reaped),
+		//       and gets an `Error::ClientDisconnected` when the connection got lost.
+		match res {
+			Ok(res) => res,
+			Err(_) => Err(Error::RequestCancelled {
+				rpc_id: self.rpc_id.clone(),
+				method: self.method.clone(),
+			}),
+		}
+	}
+
+	pub(crate) fn cancel(mut self, reason: Option<String>) {
+		self.done = true;
+		self.inner.cancel_pending_request(Some(&self.c2s_tx), &self.rpc_id, reason);
+	}
+}
+
+impl Drop for PendingResponse {
+	fn drop(&mut self) {
+		if !self.done {
+			let reason = Some("Request dropped by the client".to_string());
+			self.inner.cancel_pending_request(Some(&self.c2s_tx), &self.rpc_id, reason);
+		}
+	}
+}
+
+// endregion: --- PendingResponse
 
 #[derive(Clone)]
 pub struct Client {
 	inner: Arc<ClientInner>,
-	comm_inner: Option<Arc<CommInner>>,
+	comm: Arc<CommSlot>,
 	sampling_handler: Option<Arc<Box<dyn SamplingHandlerAsyncFn + 'static>>>,
-	s2c_mcp_requests_tx: Option<flume::Sender<McpRequest>>,
+	roots_handler: Option<Arc<Box<dyn RootsHandlerAsyncFn + 'static>>>,
+	/// Set with `set_roots` (then also the roots_handler)
+	static_roots: Option<StaticRoots>,
+	approval_handler: Option<Arc<dyn DynApprovalHandler>>,
+	/// The protocol versions accepted by this client, the first one being requested.
+	protocol_versions: Vec<ProtocolVersion>,
+	/// The default timeout of the requests (None for no timeout).
+	request_timeout: Option<Duration>,
+	/// None for no automatic reconnect.
+	reconnect_policy: Option<ReconnectPolicy>,
 }
 
 struct ClientInner {
 	name: String,
 	version: String,
-	// This is the DashMap of the rpc_id: OneShot<res>
+	// This is the DashMap of the rpc_id: PendingRequest (OneShot<res>)
 	res_queue: ResQueue,
+	// The progress notifications routing, for the pending requests with a progress token
+	progress_txs: DashMap<ProgressToken, ProgressTx>,
+	// The requests cancelled by the client (with the cancel time), so that their late responses are ignored
+	cancelled_requests: DashMap<RpcId, Instant>,
+	// The server requests being processed (e.g., sampling), abortable by a server `notifications/cancelled`
+	s2c_request_tasks: DashMap<RpcId, AbortHandle>,
+	// The tools from the `tools/list` responses, by name (for the tool call approval policies)
+	tools: RwLock<HashMap<String, Tool>>,
+	state: RwLock<ClientState>,
+	// Set when the initialize response is received
+	initialize_result: RwLock<Option<InitializeResult>>,
+	protocol_version: RwLock<Option<ProtocolVersion>>,
+	notification_hub: NotificationHub,
+	event_txs: Mutex<Vec<flume::Sender<ClientEvent>>>,
+	// The session state replayed on reconnect (the resources subscribed, and the logging level set)
+	subscribed_resources: RwLock<HashSet<String>>,
+	logging_level: RwLock<Option<LoggingLevel>>,
+	// The reaper task of the connection (see `Client::run_reaper`), aborted when disconnected
+	reaper: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ClientInner {
+	fn state(&self) -> ClientState {
+		*self.state.read().unwrap_or_else(|e| e.into_inner())
+	}
+
+	fn set_state(&self, state: ClientState) {
+		{
+			let mut current = self.state.write().unwrap_or_else(|e| e.into_inner());
+			if *current == state {
+				return;
+			}
+			debug!(from = %*current, to = %state, "Client state change");
+			*current = state;
+		}
+		self.emit_event(ClientEvent::StateChanged(state));
+	}
+
+	/// Sets the state unless closing or closed (i.e., `Client::close` called from another task).
+	///
+	/// Returns false when not set.
+	fn set_state_unless_closed(&self, state: ClientState) -> bool {
+		if matches!(self.state(), ClientState::Closing | ClientState::Closed) {
+			return false;
+		}
+		self.set_state(state);
+		true
+	}
+
+	fn protocol_version(&self) -> Option<ProtocolVersion> {
+		*self.protocol_version.read().unwrap_or_else(|e| e.into_inner())
+	}
+
+	fn emit_event(&self, event: ClientEvent) {
+		let mut event_txs = self.event_txs.lock().unwrap_or_else(|e| e.into_inner());
+		event_txs.retain(|tx| tx.send(event.clone()).is_ok());
+	}
+
+	/// Closes the client state, failing the pending requests with `Error::ClientDisconnected`.
+	fn set_disconnected(&self, reason: &str) {
+		self.set_state(ClientState::Closed);
+		self.fail_pending_requests(reason);
+		self.abort_s2c_requests();
+		self.stop_reaper();
+	}
+
+	fn stop_reaper(&self) {
+		if let Some(reaper) = self.reaper.lock().unwrap_or_else(|e| e.into_inner()).take() {
+			reaper.abort();
+		}
+	}
+
+	fn pending_rpc_ids(&self) -> Vec<RpcId> {
+		self.res_queue.iter().map(|entry| entry.key().clone()).collect()
+	}
+
+	/// Fails the pending requests with `Error::ClientDisconnected`.
+	fn fail_pending_requests(&self, reason: &str) {
+		self.fail_pending_requests_matching(reason, |_| true);
+	}
+
+	fn fail_pending_requests_matching(&self, reason: &str, filter: impl Fn(&PendingRequest) -> bool) {
+		let rpc_ids: Vec<RpcId> = self
+			.res_queue
+			.iter()
+			.filter(|entry| filter(entry.value()))
+			.map(|entry| entry.key().clone())
+			.collect();
+		for rpc_id in rpc_ids {
+			if let Some(pending) = self.take_pending_request(&rpc_id) {
+				let _ = pending.tx.send(Err(Error::ClientDisconnected {
+					reason: reason.to_string(),
+				}));
+			}
+		}
+	}
+
+	/// Aborts the server requests in process (their responses cannot be sent anymore).
+	fn abort_s2c_requests(&self) {
+		for entry in self.s2c_request_tasks.iter() {
+			entry.value().abort();
+		}
+	}
+
+	/// Tracks the session state replayed on reconnect (from the successful requests).
+	fn track_session_state(&self, method: &str, params: Option<Value>, response: &McpMessage) {
+		if !matches!(response, McpMessage::Response(_)) {
+			return;
+		}
+		let params = params.unwrap_or_default();
+		if method == SubscribeParams::METHOD || method == UnsubscribeParams::METHOD {
+			let Some(uri) = params.get("uri").and_then(Value::as_str) else {
+				return;
+			};
+			let mut subscribed_resources = self.subscribed_resources.write().unwrap_or_else(|e| e.into_inner());
+			if method == SubscribeParams::METHOD {
+				subscribed_resources.insert(uri.to_string());
+			} else {
+				subscribed_resources.remove(uri);
+			}
+		} else if method == SetLevelParams::METHOD {
+			match params.get("level").cloned().map(serde_json::from_value::<LoggingLevel>) {
+				Some(Ok(level)) => *self.logging_level.write().unwrap_or_else(|e| e.into_inner()) = Some(level),
+				_ => warn!("Cannot track the logging level of the logging/setLevel request"),
+			}
+		}
+	}
+
+	/// Clears the session state (for a new connection, not a reconnect).
+	fn reset_session_state(&self) {
+		self.subscribed_resources.write().unwrap_or_else(|e| e.into_inner()).clear();
+		*self.logging_level.write().unwrap_or_else(|e| e.into_inner()) = None;
+		self.tools.write().unwrap_or_else(|e| e.into_inner()).clear();
+	}
+
+	fn tool(&self, name: &str) -> Option<Tool> {
+		self.tools.read().unwrap_or_else(|e| e.into_inner()).get(name).cloned()
+	}
+
+	/// Caches the tools of a `tools/list` response (ignored if not a valid result).
+	fn cache_listed_tools(&self, response: &McpMessage) {
+		let McpMessage::Response(response) = response else {
+			return;
+		};
+		let Some(tools) = response.result.get("tools") else {
+			return;
+		};
+		match serde_json::from_value::<Vec<Tool>>(tools.clone()) {
+			Ok(tools) => {
+				let mut cache = self.tools.write().unwrap_or_else(|e| e.into_inner());
+				for tool in tools {
+					cache.insert(tool.name.clone(), tool);
+				}
+			}
+			Err(err) => warn!("Cannot cache the listed tools. Cause: {err}"),
+		}
+	}
+
+	/// Removes the pending request and its progress routing (which ends its progress stream).
+	fn take_pending_request(&self, rpc_id: &RpcId) -> Option<PendingRequest> {
+		let (_, pending) = self.res_queue.remove(rpc_id)?;
+		if let Some(progress_token) = pending.progress_token.as_ref() {
+			self.progress_txs.remove(progress_token);
+		}
+		Some(pending)
+	}
+
+	/// Removes the pending request (if still pending) and notifies the server with a `notifications/cancelled`
+	/// (when connected).
+	fn cancel_pending_request(&self, c2s_tx: Option<&CommTx>, rpc_id: &RpcId, reason: Option<String>) {
+		// Already answered (or cancelled), nothing to do.
+		let Some(pending) = self.take_pending_request(rpc_id) else {
+			return;
+		};
+		self.cancelled_requests.insert(rpc_id.clone(), Instant::now());
+		debug!(rpc_id = %rpc_id, method = %pending.method, "Cancelling RPC Request");
+
+		// Per spec, the `initialize` request must not be cancelled (and nothing to notify if not connected).
+		let Some(c2s_tx) = c2s_tx else {
+			return;
+		};
+		if pending.method == InitializeParams::METHOD || !self.state().can_send_message() {
+			return;
+		}
+		let mut params = CancelledNotificationParams::new(rpc_id.clone());
+		params.reason = reason;
+		let notification: McpNotification<CancelledNotificationParams> = params.into();
+		match notification.stringify() {
+			Ok(payload) => {
+				if let Err(err) = c2s_tx.try_send(payload) {
+					warn!(rpc_id = %rpc_id, "Cannot send notifications/cancelled. Cause: {err}");
+				}
+			}
+			Err(err) => error!(rpc_id = %rpc_id, "Cannot serialize notifications/cancelled. Cause: {err}"),
+		}
+	}
+
+	/// Removes the pending requests nobody waits for anymore, and forgets the old cancelled requests.
+	fn reap_pending_requests(&self) {
+		let orphan_ids: Vec<RpcId> = self
+			.res_queue
+			.iter()
+			.filter(|entry| entry.value().tx.is_closed())
+			.map(|entry| entry.key().clone())
+			.collect();
+		for rpc_id in orphan_ids {
+			if let Some(pending) = self.take_pending_request(&rpc_id) {
+				warn!(rpc_id = %rpc_id, method = %pending.method, "Reaped orphaned pending request");
+			}
+		}
+
+		self.cancelled_requests
+			.retain(|_, cancelled_at| cancelled_at.elapsed() < CANCELLED_REQUEST_TTL);
+	}
+
+	/// Aborts the server request task targeted by a server `notifications/cancelled` (no response is sent).
+	fn cancel_s2c_request(&self, notification: &McpNotification<Value>) {
+		let Some(params) = ServerNotification::parse_params::<CancelledNotificationParams>(notification) else {
+			return;
+		};
+		match self.s2c_request_tasks.remove(&params.request_id) {
+			Some((rpc_id, abort_handle)) => {
+				debug!(rpc_id = %rpc_id, reason = ?params.reason, "Server request cancelled by the server");
+				abort_handle.abort();
+			}
+			None => debug!(rpc_id = %params.request_id, "No server request in process for this cancellation"),
+		}
+	}
+
+	/// Forwards a `notifications/progress` to the `RequestHandle` of its request (if still pending).
+	fn route_progress(&self, notification: &McpNotification<Value>) {
+		let Some(params) = ServerNotification::parse_params::<ProgressNotificationParams>(notification) else {
+			return;
+		};
+		match self.progress_txs.get(&params.progress_token) {
+			// Note: The send fails only if the RequestHandle got dropped, which is fine.
+			Some(progress_tx) => {
+				let _ = progress_tx.send(params);
+			}
+			None => debug!(progress_token = ?params.progress_token, "No pending request for this progress token"),
+		}
+	}
 }
 
 struct CommInner {
-	#[allow(unused)]
 	transport: ClientTransport,
 	c2s_tx: CommTx,
 }
 
+/// The current connection, shared by the client clones (replaced on reconnect).
+type CommSlot = RwLock<Option<Arc<CommInner>>>;
+
+// region:    --- WeakClient
+
+/// The client without a strong reference to its connection, for the long running tasks
+/// (so that dropping all the clients still drops the transport).
+#[derive(Clone)]
+struct WeakClient {
+	comm: Weak<CommSlot>,
+	/// NOTE: With a detached (empty) comm slot, replaced by the upgraded one.
+	detached: Client,
+}
+
+impl WeakClient {
+	fn upgrade(&self) -> Option<Client> {
+		let comm = self.comm.upgrade()?;
+		Some(Client {
+			comm,
+			..self.detached.clone()
+		})
+	}
+}
+
+/// Disconnect & Reconnect
+impl WeakClient {
+	/// Handles the unexpected loss of the connection: reconnects with the reconnect policy, otherwise closes.
+	async fn handle_disconnect(&self, comm_inner: Arc<CommInner>) {
+		let inner = &self.detached.inner;
+		let exit_status = comm_inner.transport.exit_status(EXIT_STATUS_TIMEOUT).await;
+		let reason = match (exit_status, comm_inner.transport.closed_error()) {
+			(Some(status), _) => format!("Server process exited ({status})"),
+			(None, Some(closed_error)) => format!("Transport disconnected ({closed_error})"),
+			(None, None) => "Transport disconnected".to_string(),
+		};
+		warn!(%reason, "Client disconnected");
+
+		// -- Drop the lost connection (a new transport is started from its config to reconnect)
+		let transport = comm_inner.transport.new_from_config();
+		if let Some(comm) = self.comm.upgrade() {
+			let mut current = comm.write().unwrap_or_else(|e| e.into_inner());
+			if current.as_ref().is_some_and(|current| Arc::ptr_eq(current, &comm_inner)) {
+				*current = None;
+			}
+		}
+		drop(comm_inner);
+
+		// NOTE: A custom transport might not support the reconnect (no new transport from its config).
+		let (Some(policy), Some(transport)) = (self.detached.reconnect_policy.clone(), transport) else {
+			inner.set_disconnected(&reason);
+			inner.emit_event(ClientEvent::Disconnected { reason, exit_status });
+			return;
+		};
+
+		inner.emit_event(ClientEvent::Disconnected {
+			reason: reason.clone(),
+			exit_status,
+		});
+		if !inner.set_state_unless_closed(ClientState::Reconnecting) {
+			return;
+		}
+		inner.abort_s2c_requests();
+		let retry_ids = match policy.in_flight {
+			InFlightPolicy::Fail => {
+				inner.fail_pending_requests(&reason);
+				Vec::new()
+			}
+			InFlightPolicy::Retry => inner.pending_rpc_ids(),
+		};
+
+		self.reconnect(transport, &policy, &retry_ids).await;
+	}
+
+	/// Reconnects (with a new transport from this one config) per the policy, or closes when all attempts failed.
+	async fn reconnect(&self, transport: ClientTransport, policy: &ReconnectPolicy, retry_ids: &[RpcId]) {
+		let inner = &self.detached.inner;
+		let mut attempt = 0;
+		let mut last_error = String::new();
+		loop {
+			attempt += 1;
+			if !policy.can_attempt(attempt) {
+				break;
+			}
+			let delay = policy.delay(attempt);
+			info!(attempt, delay_ms = delay.as_millis() as u64, "Reconnecting");
+			inner.emit_event(ClientEvent::Reconnecting { attempt, delay });
+			tokio::time::sleep(delay).await;
+
+			// NOTE: Upgraded only for the attempt, so that a client drop (or close) stops the reconnect.
+			if inner.state() != ClientState::Reconnecting {
+				debug!("Reconnect stopped (client closed)");
+				return;
+			}
+			let Some(client) = self.upgrade() else {
+				debug!("Reconnect stopped (client dropped)");
+				return;
+			};
+			let Some(attempt_transport) = transport.new_from_config() else {
+				break;
+			};
+			match client.reconnect_transport(attempt_transport, retry_ids).await {
+				Ok(()) => {
+					info!(attempt, "Reconnected");
+					inner.emit_event(ClientEvent::Reconnected { attempts: attempt });
+					return;
+				}
+				Err(err) => {
+					warn!(attempt, "Reconnect attempt failed. Cause: {err}");
+					last_error = err.to_string();
+				}
+			}
+		}
+
+		let attempts = attempt - 1;
+		let reason = format!("Reconnect failed after {attempts} attempts. Cause: {last_error}");
+		warn!("{reason}");
+		inner.set_disconnected(&reason);
+		inner.emit_event(ClientEvent::ReconnectFailed { attempts, reason });
+	}
+}
+
+// endregion: --- WeakClient
+
 /// Constructors & Connect
 impl Client {
 	pub fn new(client_name: impl Into<String>, client_version: impl Into<String>) -> Client {
@@ -55,37 +569,130 @@ impl Client {
 			name: client_name.into(),
 			version: client_version.into(),
 			res_queue: Arc::new(DashMap::new()),
+			progress_txs: DashMap::new(),
+			cancelled_requests: DashMap::new(),
+			s2c_request_tasks: DashMap::new(),
+			tools: RwLock::new(HashMap::new()),
+			state: RwLock::new(ClientState::New),
+			initialize_result: RwLock::new(None),
+			protocol_version: RwLock::new(None),
+			notification_hub: NotificationHub::default(),
+			event_txs: Mutex::new(Vec::new()),
+			subscribed_resources: RwLock::new(HashSet::new()),
+			logging_level: RwLock::new(None),
+			reaper: Mutex::new(None),
 		};
 
 		Self {
 			inner: info_inner.into(),
 
-			comm_inner: None,
+			comm: Arc::default(),
 			sampling_handler: None,
-			s2c_mcp_requests_tx: None,
+			roots_handler: None,
+			static_roots: None,
+			approval_handler: None,
+			protocol_versions: SUPPORTED_PROTOCOL_VERSIONS.to_vec(),
+			request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+			reconnect_policy: None,
 		}
 	}
 
 	/// Connects the client using a transport configuration.
 	///
 	/// Accepts any type that implements `IntoClientTransport`, such as `ClientStdioTransportConfig`.
+	///
+	/// Runs the full initialization (`initialize` request, then `notifications/initialized`),
+	/// so the client is `ClientState::Ready` when this returns `Ok`.
+	/// On failure, the client is `ClientState::Closed` (transport dropped), and `connect` can be called again.
+	///
+	/// If the server answers with a protocol version not in the client's protocol versions,
+	/// the client disconnects and returns `Error::ProtocolVersionMismatch`.
+	///
+	/// NOTE: For the automatic reconnect when the connection gets lost, see `set_reconnect_policy`.
 	pub async fn connect(
 		&mut self,
 		transport_source: impl IntoClientTransport,
 	) -> Result<McpResponse<InitializeResult>> {
-		// Check not already connected
-		if self.comm_inner.is_some() {
-			return Err(
-				"Client already connected. Reconnect not supported for now.\nRecommendation: Start a new client".into(),
-			);
+		// Check not already connected (a closed client can connect again, as a new session)
+		let state = self.state();
+		if !matches!(state, ClientState::New | ClientState::Closed) || self.comm_inner().is_some() {
+			return Err(Error::ClientAlreadyConnected { state });
 		}
+		self.inner.reset_session_state();
+		self.inner.set_state(ClientState::Connecting);
+
+		// Convert the input into the internal ClientTransport using the trait method
+		let transport: ClientTransport = transport_source.into_client_transport();
+		let res = self.connect_transport(transport, ClientState::Closed).await?;
+		self.inner.set_state(ClientState::Ready);
+		self.run_reaper();
+
+		Ok(res)
+	}
+
+	/// Starts the transport, and runs the initialization (the state is `Initializing` when this returns `Ok`).
+	///
+	/// On failure, the transport is dropped and the client is in the `failure_state`.
+	async fn connect_transport(
+		&self,
+		transport: ClientTransport,
+		failure_state: ClientState,
+	) -> Result<McpResponse<InitializeResult>> {
+		match self.connect_and_initialize(transport).await {
+			Ok(res) => Ok(res),
+			Err(err) => {
+				// Dropping the transport disconnects (e.g., kills the stdio child process).
+				self.set_comm_inner(None);
+				self.inner.set_state_unless_closed(failure_state);
+				Err(err)
+			}
+		}
+	}
 
+	/// Connects the new transport of a reconnect, then replays the session state
+	/// (resource subscriptions and logging level), and sends the in-flight requests again.
+	async fn reconnect_transport(&self, transport: ClientTransport, retry_ids: &[RpcId]) -> Result<()> {
+		self.connect_transport(transport, ClientState::Reconnecting).await?;
+		if !self.inner.set_state_unless_closed(ClientState::Ready) {
+			return Err(Error::ClientDisconnected {
+				reason: "Client closed".to_string(),
+			});
+		}
+
+		// -- Replay the session state
+		let uris: Vec<String> = {
+			let subscribed_resources = self.inner.subscribed_resources.read().unwrap_or_else(|e| e.into_inner());
+			subscribed_resources.iter().cloned().collect()
+		};
+		for uri in uris {
+			if let Err(err) = self.send_request(SubscribeParams::new(uri.clone())).await {
+				warn!(%uri, "Cannot re-subscribe to the resource. Cause: {err}");
+			}
+		}
+		let logging_level = self.inner.logging_level.read().unwrap_or_else(|e| e.into_inner()).clone();
+		if let Some(logging_level) = logging_level {
+			if let Err(err) = self.send_request(SetLevelParams::new(logging_level)).await {
+				warn!("Cannot re-set the logging level. Cause: {err}");
+			}
+		}
+
+		// -- Send the in-flight requests again (see `InFlightPolicy::Retry`)
+		let c2s_tx = self.try_c2s_tx()?;
+		for rpc_id in retry_ids {
+			let Some(payload) = self.inner.res_queue.get(rpc_id).map(|pending| pending.payload.clone()) else {
+				continue;
+			};
+			debug!(rpc_id = %rpc_id, "Retrying in-flight request");
+			c2s_tx.send(payload).await?;
+		}
+
+		Ok(())
+	}
+
+	async fn connect_and_initialize(&self, mut transport: ClientTransport) -> Result<McpResponse<InitializeResult>> {
 		// -- Create the Trx Pair
 		let (client_trx, transport_trx) = new_trx_pair();
 
-		// Convert the input into the internal ClientTransport using the trait method
-		let mut transport: ClientTransport = transport_source.into_client_transport();
-
 		// Start the transport
 		transport.start(transport_trx).await?;
 		let transport = transport; // no need to mut anymore
@@ -94,54 +701,127 @@ impl Client {
 			c2s_tx,
 			s2c_rx,
 			s2c_aux_rx,
+			req_err_rx,
 		} = client_trx;
-		self.comm_inner = Some(CommInner { transport, c2s_tx }.into());
+		let comm_inner = Arc::new(CommInner { transport, c2s_tx });
+		self.set_comm_inner(Some(comm_inner.clone()));
 
 		// -- Run Typed MCP Requests
 		let (s2c_mcp_requests_tx, s2c_mcp_requests_rx) = flume::unbounded::<McpRequest>();
-		self.s2c_mcp_requests_tx = Some(s2c_mcp_requests_tx);
-		self.run_server_requests(s2c_mcp_requests_rx)?;
+		self.run_server_requests(s2c_mcp_requests_rx, Arc::downgrade(&comm_inner));
 
 		// -- Run Transport messages
-		self.run_s2c_rx(s2c_rx)?;
+		self.run_s2c_rx(s2c_rx, s2c_mcp_requests_tx, Arc::downgrade(&comm_inner));
 		self.run_s2c_aux_rx(s2c_aux_rx)?;
+		self.run_req_err_rx(req_err_rx);
 
-		// send the initialize
-		let init_params = InitializeParams::from_client_info(self.name(), self.version());
+		// -- Initialize
+		self.inner.set_state(ClientState::Initializing);
+		let mut init_params = InitializeParams::from_client_info(self.name(), self.version());
+		if let Some(requested_version) = self.protocol_versions.first() {
+			init_params.protocol_version = requested_version.to_string();
+		}
+		init_params.capabilities.sampling = self.sampling_handler.is_some();
+		if self.roots_handler.is_some() {
+			init_params.capabilities.roots = Some(ClientRootsCapabilities {
+				list_changed: Some(true),
+			});
+		}
 		let res = self.send_request(init_params).await?;
 
+		// -- Validate the protocol version
+		let protocol_version = self.negotiate_protocol_version(&res.result.protocol_version)?;
+		*self.inner.protocol_version.write().unwrap_or_else(|e| e.into_inner()) = Some(protocol_version);
+		comm_inner.transport.set_protocol_version(protocol_version);
+		*self.inner.initialize_result.write().unwrap_or_else(|e| e.into_inner()) = Some(res.result.clone());
+
+		// -- Notify the server that the client is initialized
+		self.send_notification(InitializedNotificationParams::new()).await?;
+
 		Ok(res)
 	}
+
+	/// Closes the connection gracefully, and returns the exit status of the server process (stdio only).
+	///
+	/// For stdio, closes the server stdin, waits for the grace period, sends a SIGTERM (unix), waits again,
+	/// then kills it (see `ClientStdioTransportConfig::with_shutdown_grace_period`).
+	/// The pending requests fail with `Error::ClientDisconnected`, and the client is then `ClientState::Closed`.
+	///
+	/// NOTE: Dropping the client (all its clones) still kills the server process, without the grace period.
+	pub async fn close(&mut self) -> Result<Option<ExitStatus>> {
+		// NOTE: Closing first, so that a reconnect in progress stops.
+		self.inner.set_state(ClientState::Closing);
+		let Some(comm_inner) = self.take_comm_inner() else {
+			self.inner.set_disconnected("Client closed");
+			return Ok(None);
+		};
+
+		let res = comm_inner.transport.close().await;
+		self.inner.set_disconnected("Client closed");
+		match res {
+			Ok(Some(status)) => info!(%status, "Client closed"),
+			Ok(None) => info!("Client closed"),
+			Err(ref err) => warn!("Client closed with error. Cause: {err}"),
+		}
+
+		Ok(res?)
+	}
+
+	fn negotiate_protocol_version(&self, server_version: &str) -> Result<ProtocolVersion> {
+		match server_version.parse::<ProtocolVersion>() {
+			Ok(version) if self.protocol_versions.contains(&version) => Ok(version),
+			_ => Err(Error::ProtocolVersionMismatch {
+				server_version: server_version.to_string(),
+				supported_versions: self.protocol_versions.iter().map(|v| v.to_string()).collect(),
+			}),
+		}
+	}
 }
 
 /// Communications
 impl Client {
+	/// Sends a request and waits for its response, with the client request timeout (see `set_request_timeout`).
 	pub async fn send_request_raw<P>(&self, req: impl Into<McpRequest<P>>) -> Result<McpMessage>
 	where
 		P: Serialize,
 	{
-		let req = req.into();
-
-		// -- Build and bind the one shot for the response
-		let (tx, rx) = oneshot::channel::<McpMessage>();
-		let rpc_id = req.id.clone();
-		self.inner.res_queue.insert(rpc_id, tx);
+		self.exec_request_raw(req.into(), self.request_timeout).await
+	}
 
-		// -- Send the message
-		let rpc_id = &req.id;
-		let method = &req.method;
-		debug!(rpc_id = %rpc_id, method = %method, "Sending RPC Request");
-		let msg = serde_json::to_string(&req).map_err(Error::custom_from_err)?;
-		self.try_c2s_tx()?.send(msg).await?;
-
-		// -- Wait for response
-		match rx.await {
-			Ok(res) => Ok(res),
-			Err(err) => Err(Error::custom_from_err(err)),
-		}
+	/// Cancels a pending request (e.g., sent from another task), notifying the server with a `notifications/cancelled`.
+	///
+	/// The pending `send_request..` returns `Error::RequestCancelled`, and the late response (if any) is ignored.
+	pub fn cancel_request(&self, rpc_id: &RpcId, reason: impl Into<String>) -> Result<()> {
+		// NOTE: Not connected while reconnecting, the request is then just removed.
+		let c2s_tx = self.try_c2s_tx().ok();
+		self.inner.cancel_pending_request(c2s_tx.as_ref(), rpc_id, Some(reason.into()));
+		Ok(())
 	}
 
 	pub async fn send_request<REQ, P>(&self, req: REQ) -> Result<McpResponse<REQ::McpResult>>
+	where
+		REQ: Into<McpRequest<P>>,
+		REQ: IntoMcpRequest<P>,
+		P: Serialize,
+	{
+		self.exec_request(req, self.request_timeout).await
+	}
+
+	/// Same as `send_request`, with a timeout overriding the client request timeout (for this request).
+	pub async fn send_request_with_timeout<REQ, P>(
+		&self,
+		req: REQ,
+		timeout: Duration,
+	) -> Result<McpResponse<REQ::McpResult>>
+	where
+		REQ: Into<McpRequest<P>>,
+		REQ: IntoMcpRequest<P>,
+		P: Serialize,
+	{
+		self.exec_request(req, Some(timeout)).await
+	}
+
+	async fn exec_request<REQ, P>(&self, req: REQ, timeout: Option<Duration>) -> Result<McpResponse<REQ::McpResult>>
 	where
 		REQ: Into<McpRequest<P>>,
 		REQ: IntoMcpRequest<P>,
@@ -149,7 +829,7 @@ impl Client {
 	{
 		let req = req.into();
 		// Get the generic/raw McpMessage
-		let response = self.send_request_raw(req).await?;
+		let response = self.exec_request_raw(req, timeout).await?;
 		// Get McpResponse
 		let response = response.try_into_response()?;
 
@@ -161,6 +841,74 @@ impl Client {
 		Ok(McpResponse { id, result })
 	}
 
+	/// Sends a request asking for progress notifications, and returns without waiting for the response.
+	///
+	/// A `ProgressToken` is generated (from the request id) and set in the request `_meta.progressToken`.
+	/// The returned `RequestHandle` yields the progress notifications of this request and the typed result
+	/// (with the client request timeout, see `RequestHandle::with_timeout`).
+	pub async fn send_request_with_progress<REQ, P>(&self, req: REQ) -> Result<RequestHandle<REQ::McpResult>>
+	where
+		REQ: Into<McpRequest<P>>,
+		REQ: IntoMcpRequest<P>,
+		P: Serialize,
+	{
+		let McpRequest { id, method, params } = req.into();
+
+		// -- Generate the progress token
+		let progress_token = match &id {
+			RpcId::Number(num) => ProgressToken::Number(*num),
+			other => ProgressToken::String(other.to_string()),
+		};
+
+		// -- Set the `_meta.progressToken` in the params
+		let params = match params {
+			Some(params) => serde_json::to_value(params).map_err(Error::custom_from_err)?,
+			None => Value::Null,
+		};
+		let params = set_progress_token(params, &progress_token)
+			.map_err(|err| Error::custom(format!("Cannot set progress token in '{method}' request. Cause: {err}")))?;
+
+		// -- Send
+		let req = McpRequest {
+			id,
+			method,
+			params: Some(params),
+		};
+		let (progress_tx, progress_rx) = flume::unbounded::<ProgressNotificationParams>();
+		let pending_response = self
+			.send_request_pending(&req, Some((progress_token.clone(), progress_tx)))
+			.await?;
+
+		Ok(RequestHandle::new(
+			progress_token,
+			NotificationStream::new(progress_rx),
+			pending_response,
+			self.request_timeout,
+		))
+	}
+
+	pub async fn send_notification<P>(&self, notification: impl Into<McpNotification<P>>) -> Result<()>
+	where
+		P: Serialize,
+	{
+		let notification = notification.into();
+
+		// -- Check the lifecycle state
+		let state = self.state();
+		if !state.can_send_message() {
+			return Err(Error::ClientNotReady {
+				method: notification.method,
+				state,
+			});
+		}
+
+		debug!(method = %notification.method, "Sending RPC Notification");
+		let payload = serde_json::to_string(&notification).map_err(Error::custom_from_err)?;
+		self.try_c2s_tx()?.send(payload).await?;
+
+		Ok(())
+	}
+
 	pub async fn send_response<R>(&self, mcp_response: McpResponse<R>) -> Result<()>
 	where
 		R: Serialize,
@@ -185,140 +933,567 @@ impl Client {
 	pub fn version(&self) -> &str {
 		&self.inner.version
 	}
+
+	/// The default timeout of the requests (None for no timeout).
+	pub fn request_timeout(&self) -> Option<Duration> {
+		self.request_timeout
+	}
+
+	/// The current lifecycle state of the connection.
+	pub fn state(&self) -> ClientState {
+		self.inner.state()
+	}
+
+	/// The protocol version negotiated with the server (None if not initialized yet).
+	pub fn protocol_version(&self) -> Option<ProtocolVersion> {
+		self.inner.protocol_version()
+	}
+
+	/// The `InitializeResult` returned by the server (None if not initialized yet).
+	pub fn initialize_result(&self) -> Option<InitializeResult> {
+		self.inner.initialize_result.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	pub fn server_info(&self) -> Option<Implementation> {
+		self.initialize_result().map(|r| r.server_info)
+	}
+
+	pub fn server_capabilities(&self) -> Option<ServerCapabilities> {
+		self.initialize_result().map(|r| r.capabilities)
+	}
+
+	/// The server instructions (usually to be added to the system prompt).
+	pub fn instructions(&self) -> Option<String> {
+		self.initialize_result().and_then(|r| r.instructions)
+	}
+}
+
+/// Setters
+impl Client {
+	/// Sets the protocol versions this client accepts (must be called before `connect`).
+	///
+	/// The first one is requested in the `initialize`. Defaults to `SUPPORTED_PROTOCOL_VERSIONS`.
+	pub fn set_protocol_versions(&mut self, protocol_versions: impl Into<Vec<ProtocolVersion>>) {
+		let protocol_versions = protocol_versions.into();
+		if protocol_versions.is_empty() {
+			warn!("Empty protocol versions ignored");
+			return;
+		}
+		self.protocol_versions = protocol_versions;
+	}
+
+	/// Sets the default timeout of the requests (None for no timeout). Defaults to `DEFAULT_REQUEST_TIMEOUT`.
+	///
+	/// On timeout, the request is cancelled (`notifications/cancelled`) and `Error::Timeout` is returned.
+	pub fn set_request_timeout(&mut self, request_timeout: Option<Duration>) {
+		self.request_timeout = request_timeout;
+	}
+
+	/// Sets the automatic reconnect when the connection gets lost (None, the default, for no reconnect).
+	///
+	/// Must be called before `connect`. See `ReconnectPolicy`, and `subscribe_events` for the connection events.
+	pub fn set_reconnect_policy(&mut self, reconnect_policy: Option<ReconnectPolicy>) {
+		self.reconnect_policy = reconnect_policy;
+	}
+}
+
+/// Notifications
+impl Client {
+	/// Subscribes to the client-level events (e.g., `ClientEvent::Disconnected`).
+	pub fn subscribe_events(&self) -> NotificationStream<ClientEvent> {
+		let (tx, rx) = flume::unbounded::<ClientEvent>();
+		self.inner.event_txs.lock().unwrap_or_else(|e| e.into_inner()).push(tx);
+		NotificationStream::new(rx)
+	}
+
+	/// Subscribes to all the notifications from the server, typed as `ServerNotification`.
+	///
+	/// Can be called before `connect` (to not miss any notification).
+	pub fn subscribe_notifications(&self) -> NotificationStream<ServerNotification> {
+		self.inner
+			.notification_hub
+			.subscribe(|notification| Some(ServerNotification::from(notification.clone())))
+	}
+
+	/// Subscribes to the notifications of one type (by its method).
+	///
+	/// e.g., `client.subscribe_notification::<LoggingMessageNotificationParams>()`
+	pub fn subscribe_notification<P>(&self) -> NotificationStream<P>
+	where
+		P: IntoMcpNotification + DeserializeOwned + Send + 'static,
+	{
+		self.inner.notification_hub.subscribe(|notification| {
+			if notification.method != P::METHOD {
+				return None;
+			}
+			ServerNotification::parse_params(notification)
+		})
+	}
+
+	/// Subscribes to the notifications with a method not typed by `ServerNotification` (raw fallback).
+	pub fn subscribe_unknown_notifications(&self) -> NotificationStream<McpNotification<Value>> {
+		self.inner.notification_hub.subscribe(|notification| {
+			(!ServerNotification::is_known_method(&notification.method)).then(|| notification.clone())
+		})
+	}
+}
+
+/// Roots
+impl Client {
+	/// Sets a static list of roots answering the server `roots/list` requests (must be called before `connect`).
+	///
+	/// The `roots` capability is then advertised. Use `update_roots` to change them once connected.
+	pub fn set_roots(&mut self, roots: impl Into<Vec<Root>>) {
+		let static_roots = StaticRoots::new(roots.into());
+		self.roots_handler = Some(Arc::new(Box::new(static_roots.clone())));
+		self.static_roots = Some(static_roots);
+	}
+
+	/// The static roots (None if not set with `set_roots`).
+	pub fn roots(&self) -> Option<Vec<Root>> {
+		self.static_roots.as_ref().map(|static_roots| static_roots.roots())
+	}
+
+	/// Replaces the static roots (see `set_roots`), and notifies the server with `notifications/roots/list_changed`.
+	pub async fn update_roots(&self, roots: impl Into<Vec<Root>>) -> Result<()> {
+		let static_roots = self
+			.static_roots
+			.as_ref()
+			.ok_or("Cannot update roots. No static roots (set_roots must be called before connect)")?;
+		static_roots.set_roots(roots.into());
+		self.notify_roots_list_changed().await
+	}
+
+	/// Notifies the server that the roots changed (e.g., for a roots handler with dynamic roots).
+	///
+	/// Does nothing if not connected yet (the server will list the roots once initialized).
+	pub async fn notify_roots_list_changed(&self) -> Result<()> {
+		if matches!(self.state(), ClientState::New | ClientState::Connecting) {
+			return Ok(());
+		}
+		self.send_notification(RootsListChangedNotificationParams::new()).await
+	}
 }
 
 /// Handlers
 impl Client {
+	/// Registers the handler answering the server `sampling/createMessage` requests (must be called before `connect`).
+	///
+	/// The `sampling` capability is then advertised. The handler can return `Error::user_rejected(..)`
+	/// when the user rejects the request; any error is sent back to the server as a JSON-RPC error.
 	pub fn register_sampling_handler(&mut self, sampling_handler: impl IntoSamplingHandlerAsyncFn) {
 		let sampling_handler = sampling_handler.into_sampling_handler();
 		self.sampling_handler = Some(sampling_handler);
 	}
 
+	/// Registers the handler answering the server `roots/list` requests (must be called before `connect`).
+	///
+	/// The `roots` capability is then advertised. Call `notify_roots_list_changed` when the roots change.
+	pub fn register_roots_handler(&mut self, roots_handler: impl IntoRootsHandlerAsyncFn) {
+		let roots_handler = roots_handler.into_roots_handler();
+		self.roots_handler = Some(roots_handler);
+		self.static_roots = None;
+	}
+
+	/// Sets the human-in-the-loop hooks reviewing the sampling requests and the tool calls (before `connect`).
+	///
+	/// See `ApprovalHandler`. A denied request returns (or is answered with) `Error::UserRejected`.
+	pub fn set_approval_handler(&mut self, approval_handler: impl ApprovalHandler) {
+		self.approval_handler = Some(Arc::new(approval_handler));
+	}
+
+	/// Executes the registered sampling handler for a `sampling/createMessage` request,
+	/// reviewed by the approval handler first (as the server requests are).
+	///
+	/// Errors are the ones sent back to the server (see `Error::into_rpc_error`), e.g.,
+	/// method-not-found without a sampling handler, or `Error::UserRejected` from the approval or the handler.
 	pub async fn exec_sampling_handler(
 		&self,
 		create_message_req: McpRequest<CreateMessageParams>,
-	) -> Result<McpResponse<SamplingMessage>> {
-		// FIXME: do the work
-		Err("client::exec_sampling_handler not implemented".into())
+	) -> Result<McpResponse<CreateMessageResult>> {
+		let McpRequest { id, params, .. } = create_message_req;
+
+		let router = self.server_request_router();
+		if router.sampling_handler.is_none() {
+			return Err(RpcError::from_method_not_found(Some(CreateMessageParams::METHOD.into())).into());
+		}
+		let Some(params) = params else {
+			return Err(RpcError::from_invalid_params(Some("Missing CreateMessageParams".into())).into());
+		};
+
+		let result = router.exec_sampling(params).await?;
+
+		Ok(McpResponse { id, result })
+	}
+
+	/// The router of the server requests, with the handlers of this client.
+	fn server_request_router(&self) -> ServerRequestRouter {
+		ServerRequestRouter {
+			sampling_handler: self.sampling_handler.clone(),
+			roots_handler: self.roots_handler.clone(),
+			approval_handler: self.approval_handler.clone(),
+		}
+	}
+}
+
+/// Private Communications
+impl Client {
+	/// Checks the lifecycle state, binds the response one shot (and the progress routing), and sends the request.
+	///
+	/// Returns the `PendingResponse`, which cancels the request when dropped before the response.
+	async fn send_request_pending<P>(
+		&self,
+		req: &McpRequest<P>,
+		progress: Option<(ProgressToken, ProgressTx)>,
+	) -> Result<PendingResponse>
+	where
+		P: Serialize,
+	{
+		// -- Check the lifecycle state
+		let state = self.state();
+		if !state.can_send_request(&req.method) {
+			return Err(Error::ClientNotReady {
+				method: req.method.clone(),
+				state,
+			});
+		}
+		let c2s_tx = self.try_c2s_tx()?;
+		let msg = match self.approval_handler.as_ref() {
+			Some(approval_handler) if req.method == CallToolParams::METHOD => {
+				self.approve_tool_call(req, approval_handler.as_ref()).await?
+			}
+			_ => serde_json::to_string(req).map_err(Error::custom_from_err)?,
+		};
+
+		// -- Build and bind the one shot for the response
+		let (tx, rx) = oneshot::channel::<Result<McpMessage>>();
+		let progress_token = match progress {
+			Some((progress_token, progress_tx)) => {
+				self.inner.progress_txs.insert(progress_token.clone(), progress_tx);
+				Some(progress_token)
+			}
+			None => None,
+		};
+		self.inner.res_queue.insert(
+			req.id.clone(),
+			PendingRequest {
+				tx,
+				method: req.method.clone(),
+				payload: msg.clone(),
+				progress_token,
+			},
+		);
+		let mut pending_response = PendingResponse {
+			inner: self.inner.clone(),
+			c2s_tx: c2s_tx.clone(),
+			rpc_id: req.id.clone(),
+			method: req.method.clone(),
+			sent_at: Instant::now(),
+			rx,
+			done: false,
+		};
+
+		// -- Send the message
+		debug!(rpc_id = %req.id, method = %req.method, "Sending RPC Request");
+		if let Err(err) = c2s_tx.send(msg).await {
+			self.inner.take_pending_request(&req.id);
+			pending_response.done = true;
+			return Err(err.into());
+		}
+
+		Ok(pending_response)
+	}
+
+	async fn exec_request_raw<P>(&self, req: McpRequest<P>, timeout: Option<Duration>) -> Result<McpMessage>
+	where
+		P: Serialize,
+	{
+		// Note: If this future is dropped, the pending response cancels the request.
+		let pending_response = self.send_request_pending(&req, None).await?;
+		let response = pending_response.recv(timeout).await?;
+
+		if req.method == ListToolsParams::METHOD {
+			self.inner.cache_listed_tools(&response);
+		} else if matches!(
+			req.method.as_str(),
+			SubscribeParams::METHOD | UnsubscribeParams::METHOD | SetLevelParams::METHOD
+		) {
+			let params = req.params.as_ref().and_then(|params| serde_json::to_value(params).ok());
+			self.inner.track_session_state(&req.method, params, &response);
+		}
+
+		Ok(response)
+	}
+
+	/// Applies the approval policy (and review) of a `tools/call` request, and returns the message to send.
+	async fn approve_tool_call<P>(
+		&self,
+		req: &McpRequest<P>,
+		approval_handler: &dyn DynApprovalHandler,
+	) -> Result<String>
+	where
+		P: Serialize,
+	{
+		let params = serde_json::to_value(&req.params).map_err(Error::custom_from_err)?;
+		let params: CallToolParams = serde_json::from_value(params).map_err(Error::custom_from_err)?;
+		let tool = match self.inner.tool(&params.name) {
+			Some(tool) => Some(tool),
+			// Not listed yet (or the list changed), so, list the tools (which caches them).
+			None => {
+				self.list_all_tools().await;
+				self.inner.tool(&params.name)
+			}
+		};
+
+		let params = match approval_handler.tool_call_policy(tool.as_ref()) {
+			ToolApprovalPolicy::Approve => params,
+			ToolApprovalPolicy::Deny => {
+				return Err(Error::UserRejected {
+					reason: Some(format!("Tool '{}' denied by the approval policy", params.name)),
+				});
+			}
+			ToolApprovalPolicy::Review => match approval_handler.review_tool_call(&params, tool.as_ref()).await {
+				Approval::Approve => params,
+				Approval::Edit(edited_params) => edited_params,
+				Approval::Deny { reason } => return Err(Error::UserRejected { reason }),
+			},
+		};
+
+		let req = McpRequest {
+			id: req.id.clone(),
+			method: req.method.clone(),
+			params: Some(params),
+		};
+		req.stringify()
+	}
+
+	/// Lists all the tools (all pages), which caches them for the approval policies.
+	///
+	/// NOTE: Boxed, as the `tools/list` requests go through `send_request_pending` (which calls this).
+	fn list_all_tools(&self) -> BoxFuture<'_, ()> {
+		async move {
+			let mut cursor = None;
+			loop {
+				let params = match cursor {
+					Some(cursor) => ListToolsParams::new().with_cursor(cursor),
+					None => ListToolsParams::new(),
+				};
+				match self.send_request(params).await {
+					Ok(res) => match res.result.next_cursor {
+						Some(next_cursor) => cursor = Some(next_cursor),
+						None => break,
+					},
+					Err(err) => {
+						warn!("Cannot list the tools for the tool call approval. Cause: {err}");
+						break;
+					}
+				}
+			}
+		}
+		.boxed()
 	}
 }
 
 /// Private Accessors
 impl Client {
-	fn try_c2s_tx(&self) -> Result<&CommTx> {
-		let trans_inner = self.comm_inner.as_ref().ok_or("Client not connected (no transport inner")?;
-		let in_tx = &trans_inner.c2s_tx;
-		Ok(in_tx)
+	fn try_c2s_tx(&self) -> Result<CommTx> {
+		let comm_inner = self.comm_inner().ok_or("Client not connected (no transport inner")?;
+		Ok(comm_inner.c2s_tx.clone())
 	}
 
-	fn try_s2c_mcp_requests_tx(&self) -> Result<&flume::Sender<McpRequest>> {
-		let tx = self
-			.s2c_mcp_requests_tx
-			.as_ref()
-			.ok_or("Client not connected (no s2c_mcp_requests_tx)")?;
-		Ok(tx)
+	fn comm_inner(&self) -> Option<Arc<CommInner>> {
+		self.comm.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	fn set_comm_inner(&self, comm_inner: Option<Arc<CommInner>>) {
+		*self.comm.write().unwrap_or_else(|e| e.into_inner()) = comm_inner;
+	}
+
+	fn take_comm_inner(&self) -> Option<Arc<CommInner>> {
+		self.comm.write().unwrap_or_else(|e| e.into_inner()).take()
+	}
+
+	fn downgrade(&self) -> WeakClient {
+		WeakClient {
+			comm: Arc::downgrade(&self.comm),
+			detached: Client {
+				comm: Arc::default(),
+				..self.clone()
+			},
+		}
 	}
 }
 
 /// Runners
 impl Client {
-	fn run_s2c_rx(&self, s2c_rx: CommRx) -> Result<()> {
-		let res_queue = self.inner.res_queue.clone();
-		let try_s2c_mcp_requests_tx = self.try_s2c_mcp_requests_tx()?.clone();
+	/// Processes the messages of this connection (`comm_inner`), then handles its loss (see `WeakClient::handle_disconnect`).
+	fn run_s2c_rx(
+		&self,
+		s2c_rx: CommRx,
+		try_s2c_mcp_requests_tx: flume::Sender<McpRequest>,
+		comm_inner: Weak<CommInner>,
+	) {
+		let inner = self.inner.clone();
+		// NOTE: Weak, so that the client drop still drops the transport.
+		let weak_client = self.downgrade();
 		tokio::spawn(async move {
-			loop {
-				match s2c_rx.recv().await {
-					Ok(msg) => {
-						let Ok(mcp_message) = serde_json::from_str::<McpMessage>(&msg) else {
-							error!(message = %msg, "Parsing received McpMessage");
-							continue;
-						};
-						match mcp_message {
-							McpMessage::Response(mcp_response) => process_mcp_response(mcp_response, &res_queue),
-							McpMessage::Request(mcp_request) => {
-								match try_s2c_mcp_requests_tx.send_async(mcp_request).await {
-									Ok(_) => (),
-									Err(err) => {
-										error!("error sending to s2c_mcp_requests_tx. Cause: {err} ")
-									}
+			// NOTE: Ends when the transport is gone (all the s2c_tx dropped).
+			while let Ok(msg) = s2c_rx.recv().await {
+				for mcp_message in parse_s2c_messages(&msg, inner.protocol_version()) {
+					match mcp_message {
+						McpMessage::Response(mcp_response) => process_mcp_response(mcp_response, &inner),
+						McpMessage::Request(mcp_request) => {
+							match try_s2c_mcp_requests_tx.send_async(mcp_request).await {
+								Ok(_) => (),
+								Err(err) => {
+									error!("error sending to s2c_mcp_requests_tx. Cause: {err} ")
 								}
 							}
-							McpMessage::Notification(mcp_notification) => {
-								warn!("MCP Notification in out_rx not supported yet")
+						}
+						McpMessage::Notification(mcp_notification) => {
+							debug!(method = %mcp_notification.method, "Received RPC Notification");
+							if mcp_notification.method == ProgressNotificationParams::METHOD {
+								inner.route_progress(&mcp_notification);
+							} else if mcp_notification.method == CancelledNotificationParams::METHOD {
+								inner.cancel_s2c_request(&mcp_notification);
+							} else if mcp_notification.method == ToolListChangedNotificationParams::METHOD {
+								inner.tools.write().unwrap_or_else(|e| e.into_inner()).clear();
 							}
-
-							McpMessage::Error(mcp_error) => warn!("MCP Error in out_rx not supported yet"),
+							inner.notification_hub.dispatch(&mcp_notification);
 						}
-					}
-					Err(e) => {
-						error!(%e, "Receiving out_rx message");
-						break;
+
+						McpMessage::Error(mcp_error) => process_mcp_error(mcp_error, &inner),
 					}
 				}
 			}
-		});
 
-		Ok(())
+			// -- The transport is gone, so is the connection.
+			if weak_client.comm.strong_count() == 0 {
+				debug!("Client dropped, transport closed");
+				inner.set_state(ClientState::Closed);
+				return;
+			}
+			// NOTE: Gone when this connection got closed or replaced
+			//       (e.g., a failed reconnect attempt, or a new connect after a close).
+			let Some(comm_inner) = comm_inner.upgrade() else {
+				debug!("Previous connection closed");
+				return;
+			};
+			match inner.state() {
+				ClientState::Closing | ClientState::Closed => {
+					debug!("Transport closed");
+					return;
+				}
+				// The connect (or reconnect attempt) fails with its initialize.
+				ClientState::Connecting | ClientState::Initializing => {
+					debug!("Transport closed while initializing");
+					inner.fail_pending_requests_matching("Transport closed while initializing", |pending| {
+						pending.method == InitializeParams::METHOD
+					});
+					return;
+				}
+				_ => (),
+			}
+			// Unexpected (e.g., the stdio server process exited or crashed)
+			weak_client.handle_disconnect(comm_inner).await;
+		});
 	}
 
-	/// Handle the server requests
-	/// TODO: For now, only support the sampling_handler (no routing)
-	fn run_server_requests(&self, s2c_mcp_request_rx: flume::Receiver<McpRequest>) -> Result<()> {
-		let sampling_handler = self.sampling_handler.clone();
-		let c2s_tx = self.try_c2s_tx()?.clone();
+	/// Handle the server requests (routed by method, see `ServerRequestRouter`)
+	///
+	/// Each request is processed in its own task, which a server `notifications/cancelled` aborts.
+	fn run_server_requests(&self, s2c_mcp_request_rx: flume::Receiver<McpRequest>, comm_inner: Weak<CommInner>) {
+		let inner = self.inner.clone();
+		// TODO: Today assuming the handlers are registered before connect.
+		//       Otherwise, need to use mutex to get the latest handlers
+		let router = self.server_request_router();
+		// NOTE: Weak, so that the transport (which holds the s2c side) stops when the client is dropped.
+		//       Also, the responses are only sent to the connection of their requests.
 
 		tokio::spawn(async move {
 			loop {
 				match s2c_mcp_request_rx.recv_async().await {
 					Ok(mcp_request) => {
-						// TODO: Today assuming register sampling handler before connect.
-						//       Otherwise, need to use mutex to get the latest sampling_handler
-						let Some(sampling_handler) = sampling_handler.as_ref() else {
-							error!("This client does not have any sampling. Cannot process event");
-							continue;
-						};
-
-						// NOTE - Today no routing, assuming server request can only be Sampling Request
-
-						let Some(sampling_request_params) = mcp_request.params else {
-							error!("McpRequest is not Sampling request. No params");
-							continue;
-						};
+						// -- Register the abort handle before spawning, so the task can always remove it
+						let rpc_id = mcp_request.id.clone();
+						let (abort_handle, abort_registration) = AbortHandle::new_pair();
+						inner.s2c_request_tasks.insert(rpc_id.clone(), abort_handle);
 
-						let Ok(sampling_request_params) =
-							serde_json::from_value::<CreateMessageParams>(sampling_request_params)
-						else {
-							error!("McpRequest is not a Sampling request. Params fail parsing as CreateMessageParams");
-							continue;
-						};
-
-						match sampling_handler.exec_fn(sampling_request_params).await {
-							Ok(res) => {
-								let res = McpResponse {
-									id: mcp_request.id,
-									result: res,
-								};
-								let payload = match serde_json::to_string(&res) {
-									Ok(res) => res,
-									Err(err) => {
-										error!("While serializing McpResponse for c2s. {res:?}");
-										continue;
+						let inner = inner.clone();
+						let router = router.clone();
+						let comm_inner = comm_inner.clone();
+						let task = async move {
+							let message = router.process(mcp_request).await;
+							let Some(comm_inner) = comm_inner.upgrade() else {
+								debug!("Client dropped (or reconnected), server request response not sent");
+								return;
+							};
+							match serde_json::to_string(&message) {
+								Ok(payload) => {
+									if let Err(err) = comm_inner.c2s_tx.send(payload).await {
+										error!("Fail to send the server request response. Cause: {err}");
 									}
-								};
-								c2s_tx.send(payload).await;
-							}
-							Err(err) => {
-								//
-								error!("Error processing sampling. Cause: {err}")
+								}
+								Err(err) => error!("While serializing the server request response. Cause: {err}"),
 							}
 						};
+						tokio::spawn(async move {
+							if Abortable::new(task, abort_registration).await.is_err() {
+								debug!(rpc_id = %rpc_id, "Server request task aborted");
+							}
+							inner.s2c_request_tasks.remove(&rpc_id);
+						});
+					}
+					Err(err) => {
+						debug!("s2c_mcp_request_rx closed. Cause: {err}");
+						break;
 					}
-					Err(err) => error!("Cannot rx from s2c_mcp_request_rx. Cause: {err}"),
 				}
 			}
 		});
+	}
 
-		Ok(())
+	/// Periodically reaps the orphaned pending requests, until the client is dropped or disconnected
+	/// (the task is aborted by `set_disconnected`).
+	fn run_reaper(&self) {
+		let inner: Weak<ClientInner> = Arc::downgrade(&self.inner);
+		let reaper = tokio::spawn(async move {
+			let mut interval = tokio::time::interval(REAPER_INTERVAL);
+			interval.tick().await; // the first tick is immediate
+			loop {
+				interval.tick().await;
+				let Some(inner) = inner.upgrade() else {
+					break;
+				};
+				inner.reap_pending_requests();
+			}
+		});
+		let previous = self.inner.reaper.lock().unwrap_or_else(|e| e.into_inner()).replace(reaper);
+		if let Some(previous) = previous {
+			previous.abort();
+		}
+	}
+
+	/// Fails the pending requests which failed at the transport level with `Error::TransportRequest`.
+	fn run_req_err_rx(&self, req_err_rx: flume::Receiver<RequestTransportError>) {
+		let inner = self.inner.clone();
+		tokio::spawn(async move {
+			// NOTE: Ends when the transport is gone (all the req_err_tx dropped).
+			while let Ok(RequestTransportError { rpc_id, error }) = req_err_rx.recv_async().await {
+				let Some(pending) = inner.take_pending_request(&rpc_id) else {
+					debug!(rpc_id = %rpc_id, "No pending request for this transport error. Ignored");
+					continue;
+				};
+				let err = Error::TransportRequest {
+					rpc_id: rpc_id.clone(),
+					method: pending.method,
+					cause: error,
+				};
+				if pending.tx.send(Err(err)).is_err() {
+					error!(rpc_id = %rpc_id, "Cannot send one_shot")
+				}
+			}
+		});
 	}
 
 	fn run_s2c_aux_rx(&self, err_rx: CommRx) -> Result<()> {
@@ -327,7 +1502,7 @@ impl Client {
 				match err_rx.recv().await {
 					Ok(msg) => warn!(io_err = %msg,"io_err"),
 					Err(e) => {
-						info!(%e, "aux_rx dropped not needed");
+						debug!(%e, "aux_rx dropped not needed");
 						break;
 					}
 				}
@@ -340,17 +1515,59 @@ impl Client {
 
 // region:    --- Support
 
-fn process_mcp_response(mcp_res: McpResponse, res_queue: &ResQueue) {
+/// Parses a message received from the server.
+///
+/// A JSON-RPC batch (array) is only accepted when the negotiated protocol version supports batching.
+fn parse_s2c_messages(msg: &str, protocol_version: Option<ProtocolVersion>) -> Vec<McpMessage> {
+	if !msg.trim_start().starts_with('[') {
+		return match msg.parse::<McpMessage>() {
+			Ok(mcp_message) => vec![mcp_message],
+			Err(err) => {
+				error!(message = %truncate(msg, 256), "Parsing received McpMessage. Cause: {err}");
+				Vec::new()
+			}
+		};
+	}
+
+	// -- JSON-RPC batch
+	if !protocol_version.is_some_and(|v| v.supports_batching()) {
+		let version = protocol_version.map(|v| v.as_str()).unwrap_or("none");
+		error!(protocol_version = %version, "JSON-RPC batch not supported by the protocol version. Ignored");
+		return Vec::new();
+	}
+	let values = match serde_json::from_str::<Vec<Value>>(msg) {
+		Ok(values) => values,
+		Err(err) => {
+			error!(message = %truncate(msg, 256), "Parsing received JSON-RPC batch. Cause: {err}");
+			return Vec::new();
+		}
+	};
+	values
+		.into_iter()
+		.filter_map(|value| match McpMessage::from_value(value) {
+			Ok(mcp_message) => Some(mcp_message),
+			Err(err) => {
+				error!("Parsing McpMessage in JSON-RPC batch. Cause: {err}");
+				None
+			}
+		})
+		.collect()
+}
+
+fn process_mcp_response(mcp_res: McpResponse, inner: &ClientInner) {
 	let rpc_id = mcp_res.id.clone();
 
 	// FIXME: Need to fix when it's a McpRequest from mcp server (e.g., sampling)
 	debug!(rpc_id = %rpc_id, "Received RPC Response");
 
-	match res_queue.remove(&rpc_id) {
-		Some((rpc_id, one_shot)) => match one_shot.send(mcp_res.into()) {
+	match inner.take_pending_request(&rpc_id) {
+		Some(pending) => match pending.tx.send(Ok(mcp_res.into())) {
 			Ok(_) => (),
 			Err(_) => error!(rpc_id = %rpc_id, "Cannot send one_shot"),
 		},
+		None if inner.cancelled_requests.remove(&rpc_id).is_some() => {
+			debug!(rpc_id = %rpc_id, "Late response of a cancelled request. Ignored")
+		}
 		None => {
 			let payload = always_to_string(&mcp_res);
 			error!(rpc_id = %rpc_id, payload_excerpt = %truncate(&payload, 256), "No matching request that id")
@@ -358,6 +1575,42 @@ fn process_mcp_response(mcp_res: McpResponse, res_queue: &ResQueue) {
 	}
 }
 
+fn process_mcp_error(mcp_error: McpError, inner: &ClientInner) {
+	let rpc_id = mcp_error.id.clone();
+	debug!(rpc_id = %rpc_id, "Received RPC Error");
+
+	match inner.take_pending_request(&rpc_id) {
+		Some(pending) => {
+			if pending.tx.send(Ok(mcp_error.into())).is_err() {
+				error!(rpc_id = %rpc_id, "Cannot send one_shot")
+			}
+		}
+		None if inner.cancelled_requests.remove(&rpc_id).is_some() => {
+			debug!(rpc_id = %rpc_id, "Late error of a cancelled request. Ignored")
+		}
+		None => error!(rpc_id = %rpc_id, error = ?mcp_error.error, "No matching request for this error id"),
+	}
+}
+
+/// Sets the `_meta.progressToken` in the request params (null params become an object).
+fn set_progress_token(params: Value, progress_token: &ProgressToken) -> Result<Value> {
+	let mut params = match params {
+		Value::Null => Value::Object(Map::new()),
+		params => params,
+	};
+	let meta = params
+		.as_object_mut()
+		.ok_or("params must be an object")?
+		.entry("_meta")
+		.or_insert_with(|| Value::Object(Map::new()));
+	let meta = meta.as_object_mut().ok_or("_meta must be an object")?;
+	meta.insert(
+		"progressToken".to_string(),
+		serde_json::to_value(progress_token).map_err(Error::custom_from_err)?,
+	);
+	Ok(params)
+}
+
 fn always_to_string<T: Serialize + std::fmt::Debug>(val: &T) -> String {
 	// Try to serialize using a reference to val, so we don't move it
 	match serde_json::to_string(val) {
@@ -367,3 +1620,255 @@ fn always_to_string<T: Serialize + std::fmt::Debug>(val: &T) -> String {
 }
 
 // endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mcp::PingParams;
+	use crate::mcp::testing::{MockAction, MockExpectation, MockServer};
+	use serde_json::json;
+	use std::sync::atomic::{AtomicBool, Ordering};
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_client_set_progress_token() -> Result<()> {
+		// -- Setup & Fixtures
+		let token = ProgressToken::from("tok-1");
+
+		// -- Exec
+		let from_null = set_progress_token(Value::Null, &token)?;
+		let with_meta = set_progress_token(json!({"name": "slow", "_meta": {"other": 1}}), &token)?;
+		let not_object = set_progress_token(json!(["a"]), &token);
+
+		// -- Check
+		assert_eq!(from_null, json!({"_meta": {"progressToken": "tok-1"}}));
+		assert_eq!(
+			with_meta,
+			json!({"name": "slow", "_meta": {"other": 1, "progressToken": "tok-1"}})
+		);
+		assert!(not_object.is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_client_reap_pending_requests() -> Result<()> {
+		// -- Setup & Fixtures
+		let client = Client::new("test-client", "0.1.0");
+		let inner = &client.inner;
+		let (alive_tx, _alive_rx) = oneshot::channel::<crate::mcp::Result<McpMessage>>();
+		let (orphan_tx, orphan_rx) = oneshot::channel::<crate::mcp::Result<McpMessage>>();
+		drop(orphan_rx);
+		let orphan_token = ProgressToken::from("orphan");
+		let (progress_tx, _progress_rx) = flume::unbounded::<ProgressNotificationParams>();
+		inner.progress_txs.insert(orphan_token.clone(), progress_tx);
+		for (id, tx, progress_token) in [("alive", alive_tx, None), ("orphan", orphan_tx, Some(orphan_token.clone()))] {
+			let pending = PendingRequest {
+				tx,
+				method: "tools/call".to_string(),
+				payload: String::new(),
+				progress_token,
+			};
+			inner.res_queue.insert(RpcId::from(id), pending);
+		}
+		let expired_at = Instant::now() - CANCELLED_REQUEST_TTL - Duration::from_secs(1);
+		inner.cancelled_requests.insert(RpcId::from("expired"), expired_at);
+		inner.cancelled_requests.insert(RpcId::from("recent"), Instant::now());
+
+		// -- Exec
+		inner.reap_pending_requests();
+
+		// -- Check
+		assert!(inner.res_queue.contains_key(&RpcId::from("alive")));
+		assert!(!inner.res_queue.contains_key(&RpcId::from("orphan")));
+		assert!(!inner.progress_txs.contains_key(&orphan_token));
+		assert!(!inner.cancelled_requests.contains_key(&RpcId::from("expired")));
+		assert!(inner.cancelled_requests.contains_key(&RpcId::from("recent")));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_client_track_session_state() -> Result<()> {
+		// -- Setup & Fixtures
+		let client = Client::new("test-client", "0.1.0");
+		let inner = &client.inner;
+		let ok: McpMessage = McpResponse {
+			id: RpcId::from(1),
+			result: json!({}),
+		}
+		.into();
+
+		// -- Exec
+		for uri in ["file:///a.txt", "file:///b.txt"] {
+			inner.track_session_state(SubscribeParams::METHOD, Some(json!({"uri": uri})), &ok);
+		}
+		inner.track_session_state(UnsubscribeParams::METHOD, Some(json!({"uri": "file:///a.txt"})), &ok);
+		inner.track_session_state(SetLevelParams::METHOD, Some(json!({"level": "warning"})), &ok);
+
+		// -- Check
+		let subscribed_resources = inner.subscribed_resources.read().map_err(|err| err.to_string())?.clone();
+		assert_eq!(subscribed_resources, HashSet::from(["file:///b.txt".to_string()]));
+		assert_eq!(
+			*inner.logging_level.read().map_err(|err| err.to_string())?,
+			Some(LoggingLevel::Warning)
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_client_cancel_on_drop() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockServer::new("mock", "0.0.1").on_request(fx_slow_tool_call(5_000));
+		let mut client = Client::new("test-client", "0.1.0");
+		client.connect(mock.serve_in_memory()).await?;
+		let req = McpRequest::new("drop-1", CallToolParams::new("slow"));
+
+		// -- Exec
+		// The send_request future gets dropped on this timeout.
+		let res = tokio::time::timeout(Duration::from_millis(100), client.send_request(req)).await;
+		let cancelled = wait_until(|| mock.received_requests("notifications/cancelled").pop()).await?;
+
+		// -- Check
+		assert!(res.is_err(), "the send_request future should be dropped");
+		assert_eq!(cancelled.pointer("/params/requestId"), Some(&json!("drop-1")));
+		assert!(client.inner.res_queue.is_empty());
+		assert!(client.inner.cancelled_requests.contains_key(&RpcId::from("drop-1")));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_client_cancel_request_and_late_response() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockServer::new("mock", "0.0.1").on_request(fx_slow_tool_call(300));
+		let mut client = Client::new("test-client", "0.1.0");
+		client.connect(mock.serve_in_memory()).await?;
+		let rpc_id = RpcId::from("cancel-1");
+		let req = McpRequest::new(rpc_id.clone(), CallToolParams::new("slow"));
+		let sender = client.clone();
+		let task = tokio::spawn(async move { sender.send_request(req).await });
+
+		// -- Exec
+		wait_until(|| mock.received_requests("tools/call").pop()).await?;
+		client.cancel_request(&rpc_id, "User aborted")?;
+		let res = task.await?;
+		let cancelled = wait_until(|| mock.received_requests("notifications/cancelled").pop()).await?;
+		// The late response (after the delay) is ignored, and clears the cancelled request.
+		wait_until(|| (!client.inner.cancelled_requests.contains_key(&rpc_id)).then_some(())).await?;
+		client.send_request(PingParams::default()).await?;
+
+		// -- Check
+		let Err(Error::RequestCancelled { rpc_id: res_id, method }) = res else {
+			return Err(format!("Should be RequestCancelled, was: {res:?}").into());
+		};
+		assert_eq!(res_id, rpc_id);
+		assert_eq!(method, "tools/call");
+		assert_eq!(cancelled.pointer("/params/requestId"), Some(&json!("cancel-1")));
+		assert_eq!(cancelled.pointer("/params/reason"), Some(&json!("User aborted")));
+		assert!(client.inner.res_queue.is_empty());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_client_server_cancel_aborts_sampling() -> Result<()> {
+		// -- Setup & Fixtures
+		let sampling_request = json!({
+			"jsonrpc": "2.0",
+			"id": "sampling-1",
+			"method": "sampling/createMessage",
+			"params": {"messages": [], "maxTokens": 10}
+		});
+		let mock = MockServer::new("mock", "0.0.1")
+			.on_initialized(MockAction::send(sampling_request))
+			.on_initialized(MockAction::delay(100))
+			.on_initialized(MockAction::notify(
+				"notifications/cancelled",
+				json!({"requestId": "sampling-1", "reason": "No longer needed"}),
+			));
+		let started = Arc::new(AtomicBool::new(false));
+		let finished = Arc::new(AtomicBool::new(false));
+		let mut client = Client::new("test-client", "0.1.0");
+		let (handler_started, handler_finished) = (started.clone(), finished.clone());
+		client.register_sampling_handler(move |_params: CreateMessageParams| {
+			let (started, finished) = (handler_started.clone(), handler_finished.clone());
+			async move {
+				started.store(true, Ordering::SeqCst);
+				tokio::time::sleep(Duration::from_secs(5)).await;
+				finished.store(true, Ordering::SeqCst);
+				Ok(CreateMessageResult::new_assistant("Too late", "mock-model"))
+			}
+		});
+
+		// -- Exec
+		client.connect(mock.serve_in_memory()).await?;
+		wait_until(|| started.load(Ordering::SeqCst).then_some(())).await?;
+		wait_until(|| client.inner.s2c_request_tasks.is_empty().then_some(())).await?;
+		tokio::time::sleep(Duration::from_millis(100)).await;
+
+		// -- Check
+		assert!(
+			!finished.load(Ordering::SeqCst),
+			"the sampling handler should be aborted"
+		);
+		assert!(
+			mock.received().iter().all(|msg| msg.get("id") != Some(&json!("sampling-1"))),
+			"no response should be sent to a cancelled server request"
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_client_reaper_stopped_on_close() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut client = Client::new("test-client", "0.1.0");
+		let reaper_running = |client: &Client| {
+			let reaper = client.inner.reaper.lock().unwrap_or_else(|e| e.into_inner());
+			reaper.as_ref().is_some_and(|reaper| !reaper.is_finished())
+		};
+
+		// -- Exec & Check
+		// NOTE: Connected twice, as each connect starts its own reaper.
+		for _ in 0..2 {
+			client.connect(MockServer::new("mock", "0.0.1").serve_in_memory()).await?;
+			assert!(reaper_running(&client), "the reaper should run once connected");
+			client.close().await?;
+			assert!(client.inner.reaper.lock().map_err(|err| err.to_string())?.is_none());
+		}
+
+		Ok(())
+	}
+
+	// region:    --- Support
+
+	/// A `tools/call` expectation replying after this delay.
+	fn fx_slow_tool_call(delay_ms: u64) -> MockExpectation {
+		MockExpectation::new(CallToolParams::METHOD)
+			.with_action(MockAction::delay(delay_ms))
+			.respond(json!({"content": [{"type": "text", "text": "done"}]}))
+	}
+
+	/// Polls `check` until it returns Some (2s max).
+	async fn wait_until<T>(mut check: impl FnMut() -> Option<T>) -> Result<T> {
+		let deadline = Instant::now() + Duration::from_secs(2);
+		loop {
+			if let Some(value) = check() {
+				return Ok(value);
+			}
+			if Instant::now() > deadline {
+				return Err("wait_until timed out".into());
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	}
+
+	// endregion: --- Support
+}
+
+// endregion: --- Tests
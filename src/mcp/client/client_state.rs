@@ -0,0 +1,77 @@
+use crate::mcp::{InitializeParams, IntoMcpRequest, PingParams};
+use derive_more::Display;
+
+/// The lifecycle state of a `Client` connection.
+///
+/// `New` → `Connecting` → `Initializing` → `Ready` → `Closing` → `Closed`
+///
+/// With a reconnect policy, a lost connection goes `Ready` → `Reconnecting` → `Initializing` → `Ready`
+/// (or `Closed` when the reconnect fails).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ClientState {
+	/// Not connected yet (`Client::connect` not called).
+	New,
+	/// Starting the transport.
+	Connecting,
+	/// `initialize` sent, waiting for the `InitializeResult`.
+	Initializing,
+	/// Initialized (`notifications/initialized` sent). Requests can be sent.
+	Ready,
+	/// The connection got lost, reconnecting (see `Client::set_reconnect_policy`).
+	Reconnecting,
+	/// Shutting down the connection.
+	Closing,
+	/// Connection closed (or failed). `Client::connect` can be called again (as a new session).
+	Closed,
+}
+
+impl ClientState {
+	/// Returns true if a client request with this method can be sent in this state.
+	///
+	/// Per spec, only `ping` (and the `initialize` itself) can be sent before the initialization completes.
+	pub(crate) fn can_send_request(&self, method: &str) -> bool {
+		match self {
+			ClientState::Initializing => method == InitializeParams::METHOD || method == PingParams::METHOD,
+			ClientState::Ready => method != InitializeParams::METHOD,
+			_ => false,
+		}
+	}
+
+	/// Returns true if notifications and responses (to server requests) can be sent in this state.
+	pub(crate) fn can_send_message(&self) -> bool {
+		matches!(self, ClientState::Initializing | ClientState::Ready)
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_client_state_can_send_request() {
+		// -- Check Initializing
+		assert!(ClientState::Initializing.can_send_request("initialize"));
+		assert!(ClientState::Initializing.can_send_request("ping"));
+		assert!(!ClientState::Initializing.can_send_request("tools/list"));
+
+		// -- Check Ready
+		assert!(ClientState::Ready.can_send_request("tools/list"));
+		assert!(!ClientState::Ready.can_send_request("initialize"));
+
+		// -- Check others
+		for state in [
+			ClientState::New,
+			ClientState::Connecting,
+			ClientState::Reconnecting,
+			ClientState::Closing,
+			ClientState::Closed,
+		] {
+			assert!(!state.can_send_request("ping"), "{state} should not send requests");
+			assert!(!state.can_send_message(), "{state} should not send messages");
+		}
+	}
+}
+
+// endregion: --- Tests
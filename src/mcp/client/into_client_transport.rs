@@ -1,13 +1,11 @@
-use super::transport::ClientTransport; // internal type
+use super::transport::ClientTransport;
 
-pub trait Sealed {}
-
-/// A trait for types that can be converted into an internal `ClientTransport`.
+/// A trait for types that can be converted into a `ClientTransport`.
 ///
 /// This allows the `Client::connect` method to accept various configurations
-/// or pre-configured transport types (like `ClientStdioTransportConfig`)
-/// without exposing the internal `ClientTransport` enum directly in the signature.
-pub trait IntoClientTransport: Sealed {
+/// or pre-configured transport types (like `ClientStdioTransportConfig`),
+/// as well as any `CustomClientTransport` (e.g., a third-party WebSocket transport).
+pub trait IntoClientTransport {
 	/// Performs the conversion into a `ClientTransport`.
 	fn into_client_transport(self) -> ClientTransport;
 }
@@ -1,18 +1,48 @@
 // region:    --- Modules
 
+pub mod auth;
+
+mod approval;
+mod client_event;
+mod client_state;
 mod into_client_transport;
+mod notification_hub;
+mod reconnect_policy;
+mod request_handle;
+mod roots_handler;
 mod sampling_handler;
-mod transport;
+mod server_request_router;
+pub(crate) mod transport;
 
 mod client_impl;
 
+pub use approval::*;
+pub use client_event::*;
 pub use client_impl::*;
+pub use client_state::*;
 
 // --- Re-exports (hand picks)
 pub use into_client_transport::IntoClientTransport;
 pub use transport::ClientHttpTransportConfig;
+pub use transport::ClientMemoryTransportConfig;
+pub use transport::ClientSseTransportConfig;
 pub use transport::ClientStdioTransportConfig;
+#[cfg(feature = "websocket")]
+pub use transport::ClientWebSocketTransportConfig;
+pub use transport::Error as TransportError;
+pub use transport::{BearerTokenProviderAsyncFn, IntoBearerTokenProviderAsyncFn};
+pub use transport::{ClientTransport, CustomClientTransport, CustomTransportBox, RequestTransportError, TransportTrx};
+pub use transport::{DEFAULT_ENV_ALLOWLIST, StderrRingBuffer, StdioStderr};
+
+// NOTE: Shared with the server (see `crate::mcp::transport`), re-exported for the custom transports.
+pub use crate::mcp::transport::{CommRx, CommTx};
+#[cfg(feature = "websocket")]
+pub use crate::mcp::transport::{DEFAULT_WEBSOCKET_PING_INTERVAL, DEFAULT_WEBSOCKET_PONG_TIMEOUT};
 
+pub use notification_hub::NotificationStream;
+pub use reconnect_policy::*;
+pub use request_handle::RequestHandle;
+pub use roots_handler::*;
 pub use sampling_handler::*;
 
 // endregion: --- Modules
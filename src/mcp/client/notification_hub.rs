@@ -0,0 +1,93 @@
+use crate::mcp::McpNotification;
+use futures::{FutureExt as _, Stream, StreamExt as _};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// A subscriber returns false when it should be removed (i.e., its stream got dropped).
+type Subscriber = Box<dyn Fn(&McpNotification<Value>) -> bool + Send + Sync>;
+
+/// Dispatches the notifications received from the server to the `NotificationStream`s.
+#[derive(Default)]
+pub(crate) struct NotificationHub {
+	subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl NotificationHub {
+	/// Adds a subscriber receiving the notifications for which `map` returns a value.
+	pub(crate) fn subscribe<T, F>(&self, map: F) -> NotificationStream<T>
+	where
+		T: Send + 'static,
+		F: Fn(&McpNotification<Value>) -> Option<T> + Send + Sync + 'static,
+	{
+		let (tx, rx) = flume::unbounded::<T>();
+		let subscriber: Subscriber = Box::new(move |notification| {
+			if tx.is_disconnected() {
+				return false;
+			}
+			match map(notification) {
+				Some(value) => tx.send(value).is_ok(),
+				None => true,
+			}
+		});
+		self.subscribers.lock().unwrap_or_else(|e| e.into_inner()).push(subscriber);
+
+		NotificationStream::new(rx)
+	}
+
+	pub(crate) fn dispatch(&self, notification: &McpNotification<Value>) {
+		let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+		subscribers.retain(|subscriber| subscriber(notification));
+	}
+}
+
+// region:    --- NotificationStream
+
+/// The notifications received from the server for one subscription (see `Client::subscribe_notification`).
+///
+/// The subscription ends when this stream is dropped. It is not bounded, so it should be consumed.
+pub struct NotificationStream<T> {
+	rx: flume::Receiver<T>,
+	/// When set, the stream ends at this deadline (e.g., the timeout of the request of a progress stream).
+	deadline: Option<Instant>,
+}
+
+impl<T> NotificationStream<T> {
+	pub(crate) fn new(rx: flume::Receiver<T>) -> Self {
+		Self { rx, deadline: None }
+	}
+
+	pub(crate) fn set_deadline(&mut self, deadline: Option<Instant>) {
+		self.deadline = deadline;
+	}
+
+	/// Waits for the next notification. Returns None when the client is dropped (or the stream deadline passed).
+	pub async fn recv(&self) -> Option<T> {
+		match self.deadline {
+			Some(deadline) => tokio::time::timeout_at(deadline.into(), self.rx.recv_async()).await.ok()?.ok(),
+			None => self.rx.recv_async().await.ok(),
+		}
+	}
+
+	/// Returns the next notification if one is already received.
+	pub fn try_recv(&self) -> Option<T> {
+		if self.deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+			return None;
+		}
+		self.rx.try_recv().ok()
+	}
+
+	/// Converts into a `futures::Stream`.
+	pub fn into_stream(self) -> impl Stream<Item = T> + Unpin + Send + 'static
+	where
+		T: Send + 'static,
+	{
+		let deadline = match self.deadline {
+			Some(deadline) => tokio::time::sleep_until(deadline.into()).boxed(),
+			None => futures::future::pending().boxed(),
+		};
+		self.rx.into_stream().take_until(deadline)
+	}
+}
+
+// endregion: --- NotificationStream
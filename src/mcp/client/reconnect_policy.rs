@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+/// The automatic reconnect of the client when the connection gets lost (see `Client::set_reconnect_policy`).
+///
+/// On reconnect, the transport is restarted (same config), `initialize` is re-run,
+/// the resources subscribed with `SubscribeParams` are re-subscribed, and the logging level is re-set.
+///
+/// The delay before each attempt starts at `initial_delay`, multiplied by `multiplier` after each failed attempt
+/// (up to `max_delay`).
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+	/// None for no limit.
+	pub max_attempts: Option<u32>,
+	pub initial_delay: Duration,
+	pub max_delay: Duration,
+	pub multiplier: f64,
+	pub in_flight: InFlightPolicy,
+}
+
+/// What to do with the requests waiting for their response when the connection gets lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InFlightPolicy {
+	/// Fail them with `Error::ClientDisconnected` (default).
+	#[default]
+	Fail,
+	/// Send them again once reconnected (they still fail if the reconnect fails, or on their timeout).
+	///
+	/// NOTE: A request might then be processed twice by the server (e.g., a non-idempotent `tools/call`).
+	Retry,
+}
+
+impl Default for ReconnectPolicy {
+	fn default() -> Self {
+		Self {
+			max_attempts: Some(5),
+			initial_delay: Duration::from_millis(500),
+			max_delay: Duration::from_secs(30),
+			multiplier: 2.0,
+			in_flight: InFlightPolicy::default(),
+		}
+	}
+}
+
+/// Builders
+impl ReconnectPolicy {
+	pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+		self.max_attempts = max_attempts;
+		self
+	}
+
+	pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+		self.initial_delay = initial_delay;
+		self
+	}
+
+	pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+		self.max_delay = max_delay;
+		self
+	}
+
+	pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+		self.multiplier = multiplier;
+		self
+	}
+
+	pub fn with_in_flight(mut self, in_flight: InFlightPolicy) -> Self {
+		self.in_flight = in_flight;
+		self
+	}
+}
+
+/// Support
+impl ReconnectPolicy {
+	/// The delay before this attempt (starting at 1).
+	pub(crate) fn delay(&self, attempt: u32) -> Duration {
+		let factor = self.multiplier.max(1.0).powi(attempt.saturating_sub(1).min(64) as i32);
+		let delay = self.initial_delay.as_secs_f64() * factor;
+		Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()))
+	}
+
+	pub(crate) fn can_attempt(&self, attempt: u32) -> bool {
+		self.max_attempts.is_none_or(|max_attempts| attempt <= max_attempts)
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_reconnect_policy_delay() -> Result<()> {
+		// -- Setup & Fixtures
+		let policy = ReconnectPolicy::default()
+			.with_initial_delay(Duration::from_millis(100))
+			.with_max_delay(Duration::from_millis(500))
+			.with_max_attempts(Some(3));
+
+		// -- Exec
+		let delays: Vec<u128> = (1..=5).map(|attempt| policy.delay(attempt).as_millis()).collect();
+
+		// -- Check
+		assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+		assert!(policy.can_attempt(3));
+		assert!(!policy.can_attempt(4));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,96 @@
+use crate::RpcId;
+use crate::mcp::client::NotificationStream;
+use crate::mcp::client::client_impl::PendingResponse;
+use crate::mcp::{Error, McpResponse, ProgressNotificationParams, ProgressToken, Result};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// A request in flight, returned by `Client::send_request_with_progress`.
+///
+/// - `progress()` yields the `ProgressNotificationParams` sent by the server for this request,
+///   and ends when the response arrives (or the request times out).
+/// - `result()` waits for the typed response.
+///
+/// NOTE: The timeout is counted from when the request got sent (not from the `result()` call).
+/// - `cancel(reason)` cancels the request (as does dropping the handle before the response).
+pub struct RequestHandle<R> {
+	progress_token: ProgressToken,
+	progress: NotificationStream<ProgressNotificationParams>,
+	pending_response: PendingResponse,
+	timeout: Option<Duration>,
+	_phantom: PhantomData<fn() -> R>,
+}
+
+/// Constructor
+impl<R> RequestHandle<R> {
+	pub(crate) fn new(
+		progress_token: ProgressToken,
+		progress: NotificationStream<ProgressNotificationParams>,
+		pending_response: PendingResponse,
+		timeout: Option<Duration>,
+	) -> Self {
+		let mut progress = progress;
+		progress.set_deadline(pending_response.deadline(timeout));
+		Self {
+			progress_token,
+			progress,
+			pending_response,
+			timeout,
+			_phantom: PhantomData,
+		}
+	}
+}
+
+/// Setters
+impl<R> RequestHandle<R> {
+	/// Overrides the client request timeout (None for no timeout), still counted from when the request got sent.
+	pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+		self.timeout = timeout;
+		self.progress.set_deadline(self.pending_response.deadline(timeout));
+		self
+	}
+}
+
+/// Accessors
+impl<R> RequestHandle<R> {
+	pub fn rpc_id(&self) -> &RpcId {
+		self.pending_response.rpc_id()
+	}
+
+	pub fn method(&self) -> &str {
+		self.pending_response.method()
+	}
+
+	pub fn progress_token(&self) -> &ProgressToken {
+		&self.progress_token
+	}
+
+	/// The progress notifications of this request.
+	/// Ends (`recv()` returns None) when the response arrives, or when the request times out.
+	pub fn progress(&self) -> &NotificationStream<ProgressNotificationParams> {
+		&self.progress
+	}
+}
+
+/// Cancel
+impl<R> RequestHandle<R> {
+	/// Cancels the request, notifying the server with a `notifications/cancelled` (the late response is ignored).
+	pub fn cancel(self, reason: impl Into<String>) {
+		self.pending_response.cancel(Some(reason.into()));
+	}
+}
+
+/// Result
+impl<R: DeserializeOwned> RequestHandle<R> {
+	/// Waits for the response of the request (returns `Error::Timeout` once the timeout since the send passed).
+	///
+	/// NOTE: The progress notifications not consumed yet are dropped.
+	pub async fn result(self) -> Result<McpResponse<R>> {
+		// Note: If this future is dropped, the pending response cancels the request.
+		let response = self.pending_response.recv(self.timeout).await?;
+		let McpResponse { id, result } = response.try_into_response()?;
+		let result = serde_json::from_value::<R>(result).map_err(Error::custom_from_err)?;
+		Ok(McpResponse { id, result })
+	}
+}
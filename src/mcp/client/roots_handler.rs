@@ -0,0 +1,138 @@
+use crate::mcp::{ListRootsParams, ListRootsResult, Result, Root};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+pub trait RootsHandlerAsyncFn: Send + Sync {
+	fn exec_fn(
+		&self,
+		list_roots_params: ListRootsParams,
+	) -> Pin<Box<dyn Future<Output = Result<ListRootsResult>> + Send>>;
+}
+
+impl std::fmt::Debug for dyn RootsHandlerAsyncFn {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "RootsHandlerAsyncFn")
+	}
+}
+
+// region:    --- Adapter for generic async functions
+
+struct GenericFnAdapter<FN, FUT>
+where
+	FN: FnOnce(ListRootsParams) -> FUT + Send + Sync + Clone + 'static,
+	FUT: Future<Output = Result<ListRootsResult>> + Send + 'static,
+{
+	f: FN,
+	_phantom: std::marker::PhantomData<fn() -> FUT>,
+}
+
+impl<FN, FUT> RootsHandlerAsyncFn for GenericFnAdapter<FN, FUT>
+where
+	FN: FnOnce(ListRootsParams) -> FUT + Send + Sync + Clone + 'static,
+	FUT: Future<Output = Result<ListRootsResult>> + Send + 'static,
+{
+	fn exec_fn(
+		&self,
+		list_roots_params: ListRootsParams,
+	) -> Pin<Box<dyn Future<Output = Result<ListRootsResult>> + Send>> {
+		Box::pin((self.f.clone())(list_roots_params))
+	}
+}
+
+// endregion: --- Adapter for generic async functions
+
+// region:    --- IntoRootsHandlerAsyncFn Trait ---
+
+pub trait IntoRootsHandlerAsyncFn {
+	fn into_roots_handler(self) -> Arc<Box<dyn RootsHandlerAsyncFn>>;
+}
+
+impl IntoRootsHandlerAsyncFn for Arc<Box<dyn RootsHandlerAsyncFn>> {
+	fn into_roots_handler(self) -> Arc<Box<dyn RootsHandlerAsyncFn>> {
+		self
+	}
+}
+
+impl<F, Fut> IntoRootsHandlerAsyncFn for F
+where
+	F: FnOnce(ListRootsParams) -> Fut + Send + Sync + Clone + 'static,
+	Fut: Future<Output = Result<ListRootsResult>> + Send + 'static,
+{
+	fn into_roots_handler(self) -> Arc<Box<dyn RootsHandlerAsyncFn>> {
+		let adapter = GenericFnAdapter {
+			f: self,
+			_phantom: std::marker::PhantomData,
+		};
+		Arc::new(Box::new(adapter))
+	}
+}
+
+// endregion: --- IntoRootsHandlerAsyncFn Trait ---
+
+// region:    --- StaticRoots
+
+/// A static list of roots answering `roots/list` (see `Client::set_roots`), updatable at runtime.
+#[derive(Clone, Default)]
+pub(crate) struct StaticRoots {
+	roots: Arc<RwLock<Vec<Root>>>,
+}
+
+impl StaticRoots {
+	pub(crate) fn new(roots: Vec<Root>) -> Self {
+		Self {
+			roots: Arc::new(RwLock::new(roots)),
+		}
+	}
+
+	pub(crate) fn roots(&self) -> Vec<Root> {
+		self.roots.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	pub(crate) fn set_roots(&self, roots: Vec<Root>) {
+		*self.roots.write().unwrap_or_else(|e| e.into_inner()) = roots;
+	}
+}
+
+impl RootsHandlerAsyncFn for StaticRoots {
+	fn exec_fn(
+		&self,
+		_list_roots_params: ListRootsParams,
+	) -> Pin<Box<dyn Future<Output = Result<ListRootsResult>> + Send>> {
+		let roots = self.roots();
+		Box::pin(async move { Ok(ListRootsResult { meta: None, roots }) })
+	}
+}
+
+// endregion: --- StaticRoots
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_roots_handler_static_roots_update() -> Result<()> {
+		// -- Setup & Fixtures
+		let static_roots = StaticRoots::new(vec![Root::new("file:///project-a")]);
+		let roots_handler: Arc<Box<dyn RootsHandlerAsyncFn>> = Arc::new(Box::new(static_roots.clone()));
+
+		// -- Exec
+		let before = roots_handler.exec_fn(ListRootsParams::default()).await?;
+		static_roots.set_roots(vec![Root::new("file:///project-b").with_name("Project B")]);
+		let after = roots_handler.exec_fn(ListRootsParams::default()).await?;
+
+		// -- Check
+		assert_eq!(before.roots[0].uri, "file:///project-a");
+		assert_eq!(after.roots.len(), 1);
+		assert_eq!(after.roots[0].uri, "file:///project-b");
+		assert_eq!(after.roots[0].name.as_deref(), Some("Project B"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
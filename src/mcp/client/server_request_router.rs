@@ -0,0 +1,191 @@
+use crate::mcp::client::approval::DynApprovalHandler;
+use crate::mcp::client::{Approval, RootsHandlerAsyncFn, SamplingHandlerAsyncFn};
+use crate::mcp::support::{parse_params, to_result_value};
+use crate::mcp::{
+	CreateMessageParams, CreateMessageResult, EmptyResult, Error, IntoMcpRequest, ListRootsParams, McpError,
+	McpMessage, McpRequest, McpResponse, PingParams, Result,
+};
+use rpc_router::RpcError;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Routes the requests sent by the server to the client (by method), and builds the message to send back.
+///
+/// - `ping` is answered automatically.
+/// - `roots/list` and `sampling/createMessage` go to their registered handler
+///   (the sampling request being reviewed by the approval handler first, if any).
+/// - Any other method (or a method without handler) gets a JSON-RPC method-not-found error.
+#[derive(Clone, Default)]
+pub(crate) struct ServerRequestRouter {
+	pub(crate) sampling_handler: Option<Arc<Box<dyn SamplingHandlerAsyncFn + 'static>>>,
+	pub(crate) roots_handler: Option<Arc<Box<dyn RootsHandlerAsyncFn + 'static>>>,
+	pub(crate) approval_handler: Option<Arc<dyn DynApprovalHandler>>,
+}
+
+impl ServerRequestRouter {
+	/// Processes the request and returns the `McpResponse` or `McpError` to send back to the server.
+	pub(crate) async fn process(&self, mcp_request: McpRequest) -> McpMessage {
+		let rpc_id = mcp_request.id.clone();
+		debug!(rpc_id = %rpc_id, method = %mcp_request.method, "Received RPC Request from server");
+
+		match self.route(mcp_request).await {
+			Ok(result) => McpResponse { id: rpc_id, result }.into(),
+			Err(err) => McpError::new(rpc_id, err.into_rpc_error()).into(),
+		}
+	}
+
+	async fn route(&self, mcp_request: McpRequest) -> Result<Value> {
+		let McpRequest { method, params, .. } = mcp_request;
+		let method = method.as_str();
+
+		match method {
+			PingParams::METHOD => to_result_value(EmptyResult::new()),
+
+			ListRootsParams::METHOD => {
+				let Some(roots_handler) = self.roots_handler.as_ref() else {
+					return Err(method_not_found(method));
+				};
+				let params: ListRootsParams = parse_params(params)?;
+				let result = roots_handler.exec_fn(params).await?;
+				to_result_value(result)
+			}
+
+			CreateMessageParams::METHOD => {
+				if self.sampling_handler.is_none() {
+					return Err(method_not_found(method));
+				}
+				let params: CreateMessageParams = parse_params(params)?;
+				let result = self.exec_sampling(params).await?;
+				to_result_value(result)
+			}
+
+			_ => Err(method_not_found(method)),
+		}
+	}
+
+	/// Reviews the sampling request with the approval handler (if any), then executes the sampling handler.
+	pub(crate) async fn exec_sampling(&self, params: CreateMessageParams) -> Result<CreateMessageResult> {
+		let Some(sampling_handler) = self.sampling_handler.as_ref() else {
+			return Err(method_not_found(CreateMessageParams::METHOD));
+		};
+		let mut params = params;
+		if let Some(approval_handler) = self.approval_handler.as_ref() {
+			match approval_handler.review_sampling(&params).await {
+				Approval::Approve => (),
+				Approval::Edit(edited_params) => params = edited_params,
+				Approval::Deny { reason } => return Err(Error::UserRejected { reason }),
+			}
+		}
+		sampling_handler.exec_fn(params).await
+	}
+}
+
+// region:    --- Support
+
+fn method_not_found(method: &str) -> Error {
+	RpcError::from_method_not_found(Some(method.into())).into()
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mcp::client::{IntoRootsHandlerAsyncFn, IntoSamplingHandlerAsyncFn};
+	use crate::mcp::{ListRootsResult, Root};
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	fn fx_request(id: i64, method: &str) -> Result<McpRequest> {
+		let request = serde_json::from_value(json!({"jsonrpc": "2.0", "id": id, "method": method}))?;
+		Ok(request)
+	}
+
+	#[tokio::test]
+	async fn test_server_request_router_methods() -> Result<()> {
+		// -- Setup & Fixtures
+		let roots_handler = |_params: ListRootsParams| async move {
+			Ok(ListRootsResult {
+				meta: None,
+				roots: vec![Root::new("file:///workspace")],
+			})
+		};
+		let router = ServerRequestRouter {
+			sampling_handler: None,
+			roots_handler: Some(roots_handler.into_roots_handler()),
+			approval_handler: None,
+		};
+
+		// -- Exec
+		let ping = router.process(fx_request(1, "ping")?).await;
+		let roots = router.process(fx_request(2, "roots/list")?).await;
+		let sampling = router.process(fx_request(3, "sampling/createMessage")?).await;
+		let unknown = router.process(fx_request(4, "elicitation/create")?).await;
+
+		// -- Check
+		assert_eq!(ping.try_into_response()?.result, json!({}));
+		let roots = roots.try_into_response()?.result;
+		assert_eq!(roots.pointer("/roots/0/uri"), Some(&json!("file:///workspace")));
+		for (message, method) in [(sampling, "sampling/createMessage"), (unknown, "elicitation/create")] {
+			let McpMessage::Error(mcp_error) = message else {
+				return Err(format!("Should be an McpError for '{method}'").into());
+			};
+			assert_eq!(mcp_error.error.code, RpcError::CODE_METHOD_NOT_FOUND);
+		}
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_request_router_sampling_errors() -> Result<()> {
+		// -- Setup & Fixtures
+		let sampling_handler = |params: CreateMessageParams| async move {
+			match params.system_prompt.as_deref() {
+				Some("reject") => Err(Error::user_rejected("Not now")),
+				_ => Err(Error::custom("Model unavailable")),
+			}
+		};
+		let router = ServerRequestRouter {
+			sampling_handler: Some(sampling_handler.into_sampling_handler()),
+			roots_handler: None,
+			approval_handler: None,
+		};
+		let fx_sampling = |id: i64, system_prompt: &str| -> Result<McpRequest> {
+			let request = serde_json::from_value(json!({
+				"jsonrpc": "2.0", "id": id, "method": "sampling/createMessage",
+				"params": {"messages": [], "maxTokens": 10, "systemPrompt": system_prompt}
+			}))?;
+			Ok(request)
+		};
+
+		// -- Exec
+		let rejected = router.process(fx_sampling(1, "reject")?).await;
+		let failed = router.process(fx_sampling(2, "fail")?).await;
+		let invalid = router.process(fx_request(3, "sampling/createMessage")?).await;
+
+		// -- Check
+		let codes = [rejected, failed, invalid]
+			.into_iter()
+			.map(|message| match message {
+				McpMessage::Error(mcp_error) => Ok(mcp_error.error.code),
+				other => Err(format!("Should be an McpError, but was {other:?}")),
+			})
+			.collect::<core::result::Result<Vec<_>, _>>()?;
+		assert_eq!(
+			codes,
+			[
+				Error::CODE_USER_REJECTED,
+				RpcError::CODE_INTERNAL_ERROR,
+				RpcError::CODE_INVALID_PARAMS
+			]
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
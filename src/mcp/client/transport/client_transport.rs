@@ -1,26 +1,136 @@
 use super::Result;
 use super::comm_trx::TransportTrx;
-use crate::mcp::client::transport::ClientHttpTransport;
+use super::custom_transport::{CustomClientTransport, DynCustomClientTransport};
+use crate::mcp::ProtocolVersion;
+use crate::mcp::client::ClientStdioTransportConfig;
 use crate::mcp::client::transport::stdio::ClientStdioTransport;
-use crate::mcp::client::{self, ClientStdioTransportConfig};
-use crate::mcp::client::{ClientHttpTransportConfig, IntoClientTransport};
+use crate::mcp::client::transport::{ClientHttpTransport, ClientMemoryTransport, ClientSseTransport};
+#[cfg(feature = "websocket")]
+use crate::mcp::client::transport::{ClientWebSocketTransport, ClientWebSocketTransportConfig};
+use crate::mcp::client::{
+	ClientHttpTransportConfig, ClientMemoryTransportConfig, ClientSseTransportConfig, IntoClientTransport,
+};
 use derive_more::From;
+use std::process::ExitStatus;
+use std::time::Duration;
 
+/// The transport of a client connection (see `IntoClientTransport`).
+///
+/// Built from the transport configs (e.g., `ClientStdioTransportConfig`) or with `ClientTransport::custom(..)`,
+/// the built-in variants being internal.
+///
+/// NOTE: Non exhaustive, as the variants depend on the features (e.g., `websocket`).
+#[non_exhaustive]
 #[derive(From)]
+#[allow(clippy::enum_variant_names)]
 pub enum ClientTransport {
 	StdioTransport(ClientStdioTransport),
 	HttpTransport(ClientHttpTransport),
+	SseTransport(ClientSseTransport),
+	MemoryTransport(ClientMemoryTransport),
+	#[cfg(feature = "websocket")]
+	WebSocketTransport(ClientWebSocketTransport),
+	#[from(skip)]
+	Custom(CustomTransportBox),
 }
 
+/// A boxed `CustomClientTransport` (see `ClientTransport::custom(..)`).
+pub struct CustomTransportBox(Box<dyn DynCustomClientTransport>);
+
+/// Constructors
+impl ClientTransport {
+	pub fn custom(transport: impl CustomClientTransport) -> Self {
+		ClientTransport::Custom(CustomTransportBox(Box::new(transport)))
+	}
+}
+
+/// Lifecycle
 impl ClientTransport {
 	// Note: Changed pub(crate) as it's internal detail now used via trait.
 	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
 		match self {
 			ClientTransport::StdioTransport(transport) => transport.start(transport_trx).await?,
 			ClientTransport::HttpTransport(transport) => transport.start(transport_trx).await?,
+			ClientTransport::SseTransport(transport) => transport.start(transport_trx).await?,
+			ClientTransport::MemoryTransport(transport) => transport.start(transport_trx).await?,
+			#[cfg(feature = "websocket")]
+			ClientTransport::WebSocketTransport(transport) => transport.start(transport_trx).await?,
+			ClientTransport::Custom(transport) => transport.0.start(transport_trx).await?,
 		};
 		Ok(())
 	}
+
+	/// A new transport (not started) with the same config, to reconnect (None if the custom transport cannot).
+	pub(crate) fn new_from_config(&self) -> Option<ClientTransport> {
+		let transport = match self {
+			ClientTransport::StdioTransport(transport) => transport.new_from_config().into(),
+			ClientTransport::HttpTransport(transport) => transport.new_from_config().into(),
+			ClientTransport::SseTransport(transport) => transport.new_from_config().into(),
+			ClientTransport::MemoryTransport(transport) => transport.new_from_config()?.into(),
+			#[cfg(feature = "websocket")]
+			ClientTransport::WebSocketTransport(transport) => transport.new_from_config().into(),
+			ClientTransport::Custom(transport) => {
+				ClientTransport::Custom(CustomTransportBox(transport.0.new_from_config()?))
+			}
+		};
+		Some(transport)
+	}
+
+	/// Closes the transport gracefully, returning the exit status of the server process (stdio only).
+	///
+	/// NOTE: The other transports close when their channel closes (i.e., the client drops them).
+	pub(crate) async fn close(&self) -> Result<Option<ExitStatus>> {
+		match self {
+			ClientTransport::StdioTransport(transport) => transport.close().await,
+			ClientTransport::HttpTransport(transport) => {
+				transport.close().await;
+				Ok(None)
+			}
+			ClientTransport::SseTransport(_) | ClientTransport::MemoryTransport(_) => Ok(None),
+			#[cfg(feature = "websocket")]
+			ClientTransport::WebSocketTransport(_) => Ok(None),
+			ClientTransport::Custom(transport) => transport.0.close().await.map(|_| None),
+		}
+	}
+
+	/// The exit status of the server process (stdio only), waiting up to the timeout for it.
+	pub(crate) async fn exit_status(&self, timeout: Duration) -> Option<ExitStatus> {
+		match self {
+			ClientTransport::StdioTransport(transport) => transport.exit_status(timeout).await,
+			ClientTransport::HttpTransport(_)
+			| ClientTransport::SseTransport(_)
+			| ClientTransport::MemoryTransport(_)
+			| ClientTransport::Custom(_) => None,
+			#[cfg(feature = "websocket")]
+			ClientTransport::WebSocketTransport(_) => None,
+		}
+	}
+
+	/// The error the connection ended with, when the transport knows it (e.g., the WebSocket close code).
+	pub(crate) fn closed_error(&self) -> Option<String> {
+		match self {
+			#[cfg(feature = "websocket")]
+			ClientTransport::WebSocketTransport(transport) => transport.closed_error(),
+			ClientTransport::StdioTransport(_)
+			| ClientTransport::HttpTransport(_)
+			| ClientTransport::SseTransport(_)
+			| ClientTransport::MemoryTransport(_)
+			| ClientTransport::Custom(_) => None,
+		}
+	}
+
+	/// Gives the negotiated protocol version to the transport (for the version-specific behaviors).
+	pub(crate) fn set_protocol_version(&self, protocol_version: ProtocolVersion) {
+		match self {
+			ClientTransport::StdioTransport(_)
+			| ClientTransport::SseTransport(_)
+			| ClientTransport::MemoryTransport(_) => (),
+			#[cfg(feature = "websocket")]
+			ClientTransport::WebSocketTransport(_) => (),
+			ClientTransport::HttpTransport(transport) => transport.set_protocol_version(protocol_version),
+			ClientTransport::Custom(transport) => transport.0.set_protocol_version(protocol_version),
+		}
+	}
 }
 
 // region:    --- Froms
@@ -39,6 +149,28 @@ impl From<ClientHttpTransportConfig> for ClientTransport {
 	}
 }
 
+impl From<ClientSseTransportConfig> for ClientTransport {
+	fn from(config: ClientSseTransportConfig) -> Self {
+		let transport = ClientSseTransport::from(config);
+		transport.into()
+	}
+}
+
+impl From<ClientMemoryTransportConfig> for ClientTransport {
+	fn from(config: ClientMemoryTransportConfig) -> Self {
+		let transport = ClientMemoryTransport::from(config);
+		transport.into()
+	}
+}
+
+#[cfg(feature = "websocket")]
+impl From<ClientWebSocketTransportConfig> for ClientTransport {
+	fn from(config: ClientWebSocketTransportConfig) -> Self {
+		let transport = ClientWebSocketTransport::from(config);
+		transport.into()
+	}
+}
+
 // endregion: --- Froms
 
 // region:    --- IntoClientTransport Impls
@@ -50,7 +182,6 @@ impl IntoClientTransport for ClientStdioTransportConfig {
 		ClientTransport::from(self)
 	}
 }
-impl client::into_client_transport::Sealed for ClientStdioTransportConfig {}
 
 /// Implement the public trait for the public config type.
 impl IntoClientTransport for ClientHttpTransportConfig {
@@ -59,7 +190,29 @@ impl IntoClientTransport for ClientHttpTransportConfig {
 		ClientTransport::from(self)
 	}
 }
-impl client::into_client_transport::Sealed for ClientHttpTransportConfig {}
+
+/// Implement the public trait for the public config type.
+impl IntoClientTransport for ClientSseTransportConfig {
+	fn into_client_transport(self) -> ClientTransport {
+		// Reuse the From implementation defined above
+		ClientTransport::from(self)
+	}
+}
+
+/// Implement the public trait for the public config type.
+impl IntoClientTransport for ClientMemoryTransportConfig {
+	fn into_client_transport(self) -> ClientTransport {
+		ClientTransport::from(self)
+	}
+}
+
+/// Implement the public trait for the public config type.
+#[cfg(feature = "websocket")]
+impl IntoClientTransport for ClientWebSocketTransportConfig {
+	fn into_client_transport(self) -> ClientTransport {
+		ClientTransport::from(self)
+	}
+}
 
 /// Identity implementation for internal consistency/use.
 impl IntoClientTransport for ClientTransport {
@@ -68,6 +221,11 @@ impl IntoClientTransport for ClientTransport {
 	}
 }
 
-impl client::into_client_transport::Sealed for ClientTransport {}
+/// The custom transports plug in as is.
+impl<T: CustomClientTransport> IntoClientTransport for T {
+	fn into_client_transport(self) -> ClientTransport {
+		ClientTransport::custom(self)
+	}
+}
 
 // endregion: --- IntoClientTransport Impls
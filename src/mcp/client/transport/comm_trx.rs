@@ -1,81 +1,52 @@
-use super::Result;
-use flume::{Receiver, Sender};
-use tracing::error;
+use crate::RpcId;
+use crate::mcp::client::transport::Error;
+use crate::mcp::transport::{CommRx, CommTx};
 
 pub struct ClientTrx {
 	pub c2s_tx: CommTx,
 	pub s2c_rx: CommRx,
 	pub s2c_aux_rx: CommRx,
+	pub req_err_rx: flume::Receiver<RequestTransportError>,
 }
 
+/// The transport side of the client channels (see `CustomClientTransport`).
 pub struct TransportTrx {
+	/// The client messages to send to the server.
 	pub c2s_rx: CommRx,
+	/// The server messages for the client.
 	pub s2c_tx: CommTx,
+	/// The non fatal transport errors (logged by the client).
 	pub s2c_aux_tx: CommTx,
+	/// The client requests which failed at the transport level (failing their pending request).
+	pub req_err_tx: flume::Sender<RequestTransportError>,
+}
+
+/// A client request which failed at the transport level (e.g., an HTTP 5xx, or a connection refused).
+///
+/// The pending request fails with `mcp::Error::TransportRequest` (with this `error` as `cause`).
+#[derive(Debug)]
+pub struct RequestTransportError {
+	pub rpc_id: RpcId,
+	pub error: Error,
 }
 
 pub fn new_trx_pair() -> (ClientTrx, TransportTrx) {
 	let (c2s_tx, c2s_rx) = flume::unbounded::<String>();
 	let (s2c_tx, s2c_rx) = flume::unbounded::<String>();
 	let (s2c_aux_tx, s2c_aux_rx) = flume::unbounded::<String>();
+	let (req_err_tx, req_err_rx) = flume::unbounded::<RequestTransportError>();
 
 	let client_trx = ClientTrx {
 		c2s_tx: c2s_tx.into(),
 		s2c_rx: s2c_rx.into(),
 		s2c_aux_rx: s2c_aux_rx.into(),
+		req_err_rx,
 	};
 	let transport_trx = TransportTrx {
 		c2s_rx: c2s_rx.into(),
 		s2c_tx: s2c_tx.into(),
 		s2c_aux_tx: s2c_aux_tx.into(),
+		req_err_tx,
 	};
 	(client_trx, transport_trx)
 }
-
-// region:    --- CommTx
-
-#[derive(Clone)]
-pub struct CommTx {
-	tx: Sender<String>,
-}
-
-impl CommTx {
-	pub async fn send(&self, item: impl Into<String>) -> Result<()> {
-		match self.tx.send_async(item.into()).await {
-			Ok(_) => Ok(()),
-			Err(err) => {
-				error!("Cannot send to CommTx");
-				Err(err.into())
-			}
-		}
-	}
-}
-
-impl From<Sender<String>> for CommTx {
-	fn from(tx: Sender<String>) -> Self {
-		Self { tx }
-	}
-}
-
-// endregion: --- CommTx
-
-// region:    --- CommRx
-
-pub struct CommRx {
-	rx: Receiver<String>,
-}
-
-impl CommRx {
-	pub async fn recv(&self) -> Result<String> {
-		let res = self.rx.recv_async().await?;
-		Ok(res)
-	}
-}
-
-impl From<Receiver<String>> for CommRx {
-	fn from(rx: Receiver<String>) -> Self {
-		Self { rx }
-	}
-}
-
-// endregion: --- CommRx
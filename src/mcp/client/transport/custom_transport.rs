@@ -0,0 +1,146 @@
+use super::Result;
+use super::comm_trx::TransportTrx;
+use crate::mcp::ProtocolVersion;
+use std::future::Future;
+use std::pin::Pin;
+
+/// A client transport implemented outside of the built-in ones (e.g., WebSocket, Unix socket, in-memory),
+/// to be given to `Client::connect` as is.
+///
+/// The client gives the transport its side of the channels on `start`:
+/// - `c2s_rx` - the client messages (JSON-RPC message strings) to send to the server.
+///   When it closes (client closed or dropped), the transport should shut down.
+/// - `s2c_tx` - the server messages (JSON-RPC message strings, or batches) for the client.
+///   Dropping it (with `s2c_aux_tx`) tells the client that the connection got lost.
+/// - `s2c_aux_tx` - the non fatal transport errors (logged by the client).
+/// - `req_err_tx` - the requests which failed at the transport level (see `RequestTransportError`).
+///
+/// ```ignore
+/// struct MyTransport { url: String }
+///
+/// impl CustomClientTransport for MyTransport {
+///     async fn start(&mut self, transport_trx: TransportTrx) -> Result<(), TransportError> {
+///         let conn = my_connect(&self.url).await.map_err(TransportError::custom_from_err)?;
+///         tokio::spawn(run_conn(conn, transport_trx));
+///         Ok(())
+///     }
+/// }
+///
+/// client.connect(MyTransport { url }).await?;
+/// ```
+pub trait CustomClientTransport: Send + Sync + 'static {
+	/// Connects to the server, and starts relaying the messages of the channels (returns once ready to send).
+	fn start(&mut self, transport_trx: TransportTrx) -> impl Future<Output = Result<()>> + Send;
+
+	/// Closes the connection gracefully (see `Client::close`).
+	///
+	/// By default, nothing beyond the closed `c2s_rx`.
+	fn close(&self) -> impl Future<Output = Result<()>> + Send {
+		async { Ok(()) }
+	}
+
+	/// A new transport (not started) with the same config, for the reconnect (see `Client::set_reconnect_policy`).
+	///
+	/// By default None (the client then closes on a lost connection).
+	fn new_from_config(&self) -> Option<Self>
+	where
+		Self: Sized,
+	{
+		None
+	}
+
+	/// The negotiated protocol version (for the version-specific behaviors), once initialized.
+	fn set_protocol_version(&self, _protocol_version: ProtocolVersion) {}
+}
+
+// region:    --- DynCustomClientTransport
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Object safe version of the `CustomClientTransport` (what the `ClientTransport::Custom` holds).
+pub(crate) trait DynCustomClientTransport: Send + Sync {
+	fn start(&mut self, transport_trx: TransportTrx) -> BoxFuture<'_, Result<()>>;
+	fn close(&self) -> BoxFuture<'_, Result<()>>;
+	fn new_from_config(&self) -> Option<Box<dyn DynCustomClientTransport>>;
+	fn set_protocol_version(&self, protocol_version: ProtocolVersion);
+}
+
+impl<T: CustomClientTransport> DynCustomClientTransport for T {
+	fn start(&mut self, transport_trx: TransportTrx) -> BoxFuture<'_, Result<()>> {
+		Box::pin(CustomClientTransport::start(self, transport_trx))
+	}
+
+	fn close(&self) -> BoxFuture<'_, Result<()>> {
+		Box::pin(CustomClientTransport::close(self))
+	}
+
+	fn new_from_config(&self) -> Option<Box<dyn DynCustomClientTransport>> {
+		let transport = CustomClientTransport::new_from_config(self)?;
+		Some(Box::new(transport))
+	}
+
+	fn set_protocol_version(&self, protocol_version: ProtocolVersion) {
+		CustomClientTransport::set_protocol_version(self, protocol_version)
+	}
+}
+
+// endregion: --- DynCustomClientTransport
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mcp::PingParams;
+	use crate::mcp::client::Client;
+	use crate::mcp::testing::MockServer;
+	use std::sync::Arc;
+	use std::sync::atomic::{AtomicBool, Ordering};
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	/// Relays the channels to a mock server.
+	struct MockTransport {
+		mock: MockServer,
+		closed: Arc<AtomicBool>,
+	}
+
+	impl CustomClientTransport for MockTransport {
+		async fn start(&mut self, transport_trx: TransportTrx) -> super::Result<()> {
+			let TransportTrx { c2s_rx, s2c_tx, .. } = transport_trx;
+			let mock = self.mock.clone();
+			tokio::spawn(async move { mock.serve_channels(c2s_rx, s2c_tx).await });
+			Ok(())
+		}
+
+		async fn close(&self) -> super::Result<()> {
+			self.closed.store(true, Ordering::SeqCst);
+			Ok(())
+		}
+	}
+
+	#[tokio::test]
+	async fn test_custom_transport_connect() -> Result<()> {
+		// -- Setup & Fixtures
+		let closed = Arc::new(AtomicBool::new(false));
+		let transport = MockTransport {
+			mock: MockServer::new("mock", "0.0.1"),
+			closed: closed.clone(),
+		};
+		let mut client = Client::new("test-client", "0.0.1");
+
+		// -- Exec
+		client.connect(transport).await?;
+		client.send_request(PingParams::default()).await?;
+		let exit_status = client.close().await?;
+
+		// -- Check
+		assert_eq!(client.server_info().map(|info| info.name).as_deref(), Some("mock"));
+		assert!(exit_status.is_none());
+		assert!(closed.load(Ordering::SeqCst), "custom close should be called");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
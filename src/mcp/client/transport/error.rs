@@ -1,5 +1,5 @@
 use derive_more::{Display, From};
-use flume::{RecvError, SendError};
+use flume::{RecvError, SendError, TrySendError};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -12,9 +12,39 @@ pub enum Error {
 	CommSend(String),
 	CommRecv(RecvError),
 
+	/// The stdio command was not found in the child PATH (see `with_path_diagnostics(true)`).
+	CommandNotFound {
+		cmd: String,
+		searched: Vec<String>,
+	},
+
+	/// A non successful (non 2xx) HTTP response.
+	HttpStatus {
+		status: u16,
+		body: String,
+	},
+	/// A 401 HTTP response (with the `WWW-Authenticate` header, for the OAuth discovery).
+	Unauthorized {
+		www_authenticate: Option<String>,
+		body: String,
+	},
+	#[from]
+	Auth(crate::mcp::client::auth::Error),
+
+	/// The WebSocket got closed by the peer with a non normal close code (1006 when lost without close frame).
+	WebSocketClosed {
+		code: u16,
+		reason: String,
+	},
+	/// No frame (e.g., pong) received within the pong timeout after a ping.
+	WebSocketKeepaliveTimeout,
+
 	// -- Externals
 	#[from]
 	Reqwest(reqwest::Error),
+	#[cfg(feature = "websocket")]
+	#[from]
+	WebSocket(tokio_tungstenite::tungstenite::Error),
 }
 
 // region:    --- Froms
@@ -25,12 +55,33 @@ impl From<SendError<String>> for Error {
 	}
 }
 
+impl From<TrySendError<String>> for Error {
+	fn from(value: TrySendError<String>) -> Self {
+		Self::CommSend(value.to_string())
+	}
+}
+
 impl From<RecvError> for Error {
 	fn from(err: RecvError) -> Self {
 		Self::CommRecv(err)
 	}
 }
 
+/// Keeps the same variants (e.g., `WebSocketClosed`), for the disconnect reasons.
+impl From<crate::mcp::transport::Error> for Error {
+	fn from(err: crate::mcp::transport::Error) -> Self {
+		use crate::mcp::transport::Error as CommError;
+		match err {
+			CommError::CommSend(msg) => Self::CommSend(msg),
+			CommError::CommRecv(err) => Self::CommRecv(err),
+			CommError::WebSocketClosed { code, reason } => Self::WebSocketClosed { code, reason },
+			CommError::WebSocketKeepaliveTimeout => Self::WebSocketKeepaliveTimeout,
+			#[cfg(feature = "websocket")]
+			CommError::WebSocket(err) => Self::WebSocket(err),
+		}
+	}
+}
+
 // endregion: --- Froms
 
 // region:    --- Intos
@@ -0,0 +1,66 @@
+use crate::mcp::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Gives the bearer token of the HTTP requests (called before each request, so it can refresh the token).
+pub trait BearerTokenProviderAsyncFn: Send + Sync {
+	fn exec_fn(&self) -> Pin<Box<dyn Future<Output = Result<String>> + Send>>;
+}
+
+impl std::fmt::Debug for dyn BearerTokenProviderAsyncFn {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "BearerTokenProviderAsyncFn")
+	}
+}
+
+// region:    --- Adapter for generic async functions
+
+struct GenericFnAdapter<FN, FUT>
+where
+	FN: FnOnce() -> FUT + Send + Sync + Clone + 'static,
+	FUT: Future<Output = Result<String>> + Send + 'static,
+{
+	f: FN,
+	_phantom: std::marker::PhantomData<fn() -> FUT>,
+}
+
+impl<FN, FUT> BearerTokenProviderAsyncFn for GenericFnAdapter<FN, FUT>
+where
+	FN: FnOnce() -> FUT + Send + Sync + Clone + 'static,
+	FUT: Future<Output = Result<String>> + Send + 'static,
+{
+	fn exec_fn(&self) -> Pin<Box<dyn Future<Output = Result<String>> + Send>> {
+		Box::pin((self.f.clone())())
+	}
+}
+
+// endregion: --- Adapter for generic async functions
+
+// region:    --- IntoBearerTokenProviderAsyncFn Trait ---
+
+pub trait IntoBearerTokenProviderAsyncFn {
+	fn into_bearer_token_provider(self) -> Arc<Box<dyn BearerTokenProviderAsyncFn>>;
+}
+
+impl IntoBearerTokenProviderAsyncFn for Arc<Box<dyn BearerTokenProviderAsyncFn>> {
+	fn into_bearer_token_provider(self) -> Arc<Box<dyn BearerTokenProviderAsyncFn>> {
+		self
+	}
+}
+
+impl<F, Fut> IntoBearerTokenProviderAsyncFn for F
+where
+	F: FnOnce() -> Fut + Send + Sync + Clone + 'static,
+	Fut: Future<Output = Result<String>> + Send + 'static,
+{
+	fn into_bearer_token_provider(self) -> Arc<Box<dyn BearerTokenProviderAsyncFn>> {
+		let adapter = GenericFnAdapter {
+			f: self,
+			_phantom: std::marker::PhantomData,
+		};
+		Arc::new(Box::new(adapter))
+	}
+}
+
+// endregion: --- IntoBearerTokenProviderAsyncFn Trait ---
@@ -1,11 +1,374 @@
-/// NOTE: HttpTransport is not fully implemented yet.
-///       Not to be used (use StdioTransport for now)
+use crate::mcp::client::auth::OAuthClient;
+use crate::mcp::client::transport::{BearerTokenProviderAsyncFn, Error, IntoBearerTokenProviderAsyncFn, Result};
+use reqwest::RequestBuilder;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The config of the Streamable HTTP transport (the MCP endpoint url, e.g., `http://localhost:3001/mcp`).
+///
+/// ```ignore
+/// let config = ClientHttpTransportConfig::new("https://mcp.example.com/mcp")
+///     .with_header("x-api-version", "2")
+///     .with_bearer_token_provider(|| async { Ok(fetch_token().await?) })
+///     .with_connect_timeout(Duration::from_secs(5));
+/// ```
 pub struct ClientHttpTransportConfig {
-	pub url: String,
+	pub(crate) url: String,
+	/// When true, falls back to the legacy HTTP+SSE transport (2024-11-05) when the initialize POST fails
+	/// with a 4xx (see the backwards compatibility section of the spec).
+	pub(crate) legacy_sse_fallback: bool,
+
+	/// Static headers, sent with every request.
+	pub(crate) headers: Vec<(String, String)>,
+	/// Called before each request for the `Authorization: Bearer <token>` header.
+	pub(crate) bearer_token_provider: Option<Arc<Box<dyn BearerTokenProviderAsyncFn>>>,
+	/// The OAuth authorization (takes precedence over the bearer token provider).
+	pub(crate) oauth: Option<Arc<OAuthClient>>,
+
+	// -- reqwest::Client settings (ignored when a `reqwest_client` is given)
+	pub(crate) connect_timeout: Option<Duration>,
+	pub(crate) read_timeout: Option<Duration>,
+	pub(crate) proxy: Option<String>,
+	/// PEM encoded root certificates (added to the built-in ones).
+	pub(crate) root_certificates: Vec<Vec<u8>>,
+	pub(crate) reqwest_client: Option<reqwest::Client>,
 }
 
 impl ClientHttpTransportConfig {
 	pub fn new(url: impl Into<String>) -> Self {
-		Self { url: url.into() }
+		Self {
+			url: url.into(),
+			legacy_sse_fallback: false,
+			headers: Vec::new(),
+			bearer_token_provider: None,
+			oauth: None,
+			connect_timeout: None,
+			read_timeout: None,
+			proxy: None,
+			root_certificates: Vec::new(),
+			reqwest_client: None,
+		}
+	}
+}
+
+/// Builders
+impl ClientHttpTransportConfig {
+	pub fn with_legacy_sse_fallback(mut self, legacy_sse_fallback: bool) -> Self {
+		self.legacy_sse_fallback = legacy_sse_fallback;
+		self
+	}
+
+	/// Adds a static header, sent with every request (validated on connect).
+	pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.headers.push((name.into(), value.into()));
+		self
+	}
+
+	/// Sets the bearer token provider, called before each request (so it can cache and refresh the token).
+	pub fn with_bearer_token_provider(mut self, provider: impl IntoBearerTokenProviderAsyncFn) -> Self {
+		self.bearer_token_provider = Some(provider.into_bearer_token_provider());
+		self
+	}
+
+	/// Sets a static bearer token (for a token which does not expire, e.g., an API key).
+	pub fn with_bearer_token(self, token: impl Into<String>) -> Self {
+		let token = token.into();
+		self.with_bearer_token_provider(move || async move { Ok(token) })
+	}
+
+	/// Authorizes the requests with OAuth 2.1 (see `OAuthClient`), the 401 responses triggering the authorization.
+	pub fn with_oauth(mut self, oauth: OAuthClient) -> Self {
+		self.oauth = Some(Arc::new(oauth));
+		self
+	}
+
+	pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+		self.connect_timeout = Some(connect_timeout);
+		self
+	}
+
+	/// The timeout of each read of the response body.
+	///
+	/// NOTE: Applies to the SSE streams as well, so an idle stream is dropped (and reconnected) after this timeout.
+	pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+		self.read_timeout = Some(read_timeout);
+		self
+	}
+
+	/// Proxies all the requests through this proxy url (e.g., `http://proxy.local:8080`).
+	pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Self {
+		self.proxy = Some(proxy_url.into());
+		self
+	}
+
+	/// Adds a PEM encoded root certificate (e.g., of an internal CA), trusted in addition to the built-in ones.
+	pub fn with_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+		self.root_certificates.push(pem.into());
+		self
+	}
+
+	/// Uses this pre-built `reqwest::Client`.
+	///
+	/// NOTE: The timeouts, proxy, and root certificates of this config are then ignored
+	///       (the headers and bearer token provider still apply).
+	pub fn with_reqwest_client(mut self, reqwest_client: reqwest::Client) -> Self {
+		self.reqwest_client = Some(reqwest_client);
+		self
+	}
+}
+
+/// Accessors
+impl ClientHttpTransportConfig {
+	pub fn url(&self) -> &str {
+		&self.url
+	}
+
+	pub fn legacy_sse_fallback(&self) -> bool {
+		self.legacy_sse_fallback
+	}
+}
+
+/// Builds
+impl ClientHttpTransportConfig {
+	/// Returns the given `reqwest::Client`, or builds one with the settings of this config.
+	pub(crate) fn build_reqwest_client(&self) -> Result<reqwest::Client> {
+		if let Some(reqwest_client) = self.reqwest_client.as_ref() {
+			return Ok(reqwest_client.clone());
+		}
+
+		// TODO: probably need add cookies support
+		let mut builder = reqwest::ClientBuilder::new();
+		if let Some(connect_timeout) = self.connect_timeout {
+			builder = builder.connect_timeout(connect_timeout);
+		}
+		if let Some(read_timeout) = self.read_timeout {
+			builder = builder.read_timeout(read_timeout);
+		}
+		if let Some(proxy) = self.proxy.as_deref() {
+			builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+		}
+		for pem in self.root_certificates.iter() {
+			for certificate in reqwest::Certificate::from_pem_bundle(pem)? {
+				builder = builder.add_root_certificate(certificate);
+			}
+		}
+
+		Ok(builder.build()?)
+	}
+
+	/// Builds the headers added to every request (validating the static headers).
+	pub(crate) fn build_request_headers(&self) -> Result<HttpRequestHeaders> {
+		let mut headers = HeaderMap::new();
+		for (name, value) in self.headers.iter() {
+			let name = HeaderName::try_from(name.as_str())
+				.map_err(|err| Error::custom(format!("Invalid header name '{name}'. Cause: {err}")))?;
+			let value = HeaderValue::try_from(value.as_str())
+				.map_err(|err| Error::custom(format!("Invalid header value for '{name}'. Cause: {err}")))?;
+			headers.append(name, value);
+		}
+
+		Ok(HttpRequestHeaders {
+			headers,
+			bearer_token_provider: self.bearer_token_provider.clone(),
+			oauth: self.oauth.clone(),
+		})
 	}
 }
+
+// region:    --- HttpRequestHeaders
+
+/// The static headers and the bearer token (from OAuth or the provider), added to every request of the HTTP transports.
+pub(crate) struct HttpRequestHeaders {
+	headers: HeaderMap,
+	bearer_token_provider: Option<Arc<Box<dyn BearerTokenProviderAsyncFn>>>,
+	oauth: Option<Arc<OAuthClient>>,
+}
+
+impl HttpRequestHeaders {
+	pub(crate) async fn apply(&self, req: RequestBuilder) -> Result<RequestBuilder> {
+		let mut req = req.headers(self.headers.clone());
+
+		if let Some(oauth) = self.oauth.as_ref() {
+			// NOTE: No token before the first authorization (the 401 triggers it).
+			if let Some(access_token) = oauth.access_token().await? {
+				req = req.header(AUTHORIZATION, format!("Bearer {access_token}"));
+			}
+		} else if let Some(bearer_token_provider) = self.bearer_token_provider.as_ref() {
+			let token = bearer_token_provider
+				.exec_fn()
+				.await
+				.map_err(|err| Error::custom(format!("Bearer token provider failed. Cause: {err}")))?;
+			req = req.header(AUTHORIZATION, format!("Bearer {token}"));
+		}
+
+		Ok(req)
+	}
+
+	/// With OAuth, authorizes the client after a 401 on `url` (refreshing the token, or running the authorization flow),
+	/// and returns the rejected request with the new access token (to send again).
+	///
+	/// Returns None without OAuth (the 401 is then final).
+	pub(crate) async fn reauthorize(
+		&self,
+		url: &str,
+		www_authenticate: Option<&str>,
+		mut rejected_req: reqwest::Request,
+	) -> Result<Option<reqwest::Request>> {
+		let Some(oauth) = self.oauth.as_ref() else {
+			return Ok(None);
+		};
+
+		let rejected_token = bearer_token(rejected_req.headers());
+		oauth
+			.handle_unauthorized(url, www_authenticate, rejected_token.as_deref())
+			.await?;
+		if let Some(access_token) = oauth.access_token().await? {
+			let authorization = HeaderValue::try_from(format!("Bearer {access_token}"))
+				.map_err(|err| Error::custom(format!("Invalid access token. Cause: {err}")))?;
+			rejected_req.headers_mut().insert(AUTHORIZATION, authorization);
+		}
+
+		Ok(Some(rejected_req))
+	}
+}
+
+/// The bearer token of the request `Authorization` header.
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+	let authorization = headers.get(AUTHORIZATION)?.to_str().ok()?;
+	authorization.strip_prefix("Bearer ").map(String::from)
+}
+
+// endregion: --- HttpRequestHeaders
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use axum::Router;
+	use axum::extract::Request;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use tokio::net::TcpListener;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_http_config_request_headers() -> Result<()> {
+		// -- Setup & Fixtures
+		let calls = Arc::new(AtomicUsize::new(0));
+		let provider_calls = calls.clone();
+		let config = ClientHttpTransportConfig::new("http://localhost:3001/mcp")
+			.with_header("x-tenant", "acme")
+			.with_bearer_token_provider(move || {
+				let calls = provider_calls.clone();
+				async move {
+					let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+					Ok(format!("token-{call}"))
+				}
+			});
+		let invalid_config = ClientHttpTransportConfig::new("http://localhost:3001/mcp").with_header("bad header", "x");
+		let req_client = config.build_reqwest_client()?;
+
+		// -- Exec
+		let request_headers = config.build_request_headers()?;
+		let first = request_headers.apply(req_client.get(&config.url)).await?.build()?;
+		let second = request_headers.apply(req_client.get(&config.url)).await?.build()?;
+
+		// -- Check
+		assert_eq!(
+			first.headers().get("x-tenant").map(|v| v.as_bytes()),
+			Some(&b"acme"[..])
+		);
+		assert_eq!(
+			first.headers().get(AUTHORIZATION).map(|v| v.as_bytes()),
+			Some(&b"Bearer token-1"[..])
+		);
+		assert_eq!(
+			second.headers().get(AUTHORIZATION).map(|v| v.as_bytes()),
+			Some(&b"Bearer token-2"[..])
+		);
+		assert!(invalid_config.build_request_headers().is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_http_config_build_reqwest_client() -> Result<()> {
+		// -- Setup & Fixtures
+		let silent_url = fx_silent_server().await?;
+		let proxy_url = fx_proxy_server().await?;
+		let read_timeout_client = ClientHttpTransportConfig::new(&silent_url)
+			.with_read_timeout(Duration::from_millis(100))
+			.build_reqwest_client()?;
+		let proxy_client = ClientHttpTransportConfig::new("http://mcp.example.invalid/mcp")
+			.with_proxy(&proxy_url)
+			.build_reqwest_client()?;
+
+		// -- Exec
+		let read_err = tokio::time::timeout(Duration::from_secs(2), read_timeout_client.get(&silent_url).send())
+			.await?
+			.err()
+			.ok_or("Should fail with the read timeout")?;
+		let proxied = proxy_client.get("http://mcp.example.invalid/mcp").send().await?.text().await?;
+
+		// -- Check
+		assert!(read_err.is_timeout(), "{read_err:?}");
+		// NOTE: The connect timeout is only built (a non routable address hangs, or fails fast, depending on the network).
+		let connect_timeout_config =
+			ClientHttpTransportConfig::new(&silent_url).with_connect_timeout(Duration::from_millis(100));
+		assert!(connect_timeout_config.build_reqwest_client().is_ok());
+		// The proxy received the absolute url of the request.
+		assert_eq!(proxied, "GET http://mcp.example.invalid/mcp HTTP/1.1");
+
+		// -- Check root certificates
+		let config = ClientHttpTransportConfig::new("https://mcp.example.com/mcp").with_root_certificate_pem(FX_CA_PEM);
+		assert!(config.build_reqwest_client().is_ok());
+		let invalid_pem = "-----BEGIN CERTIFICATE-----\nbm90IGEgY2VydA==\n-----END CERTIFICATE-----\n";
+		let config =
+			ClientHttpTransportConfig::new("https://mcp.example.com/mcp").with_root_certificate_pem(invalid_pem);
+		assert!(config.build_reqwest_client().is_err());
+
+		Ok(())
+	}
+
+	// -- Support
+
+	/// A self-signed CA certificate (test only).
+	const FX_CA_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBfDCCASGgAwIBAgIUSyHe/BciObpRndwi6e9Ud4zGQoQwCgYIKoZIzj0EAwIw
+EjEQMA4GA1UEAwwHdGVzdC1jYTAgFw0yNjEwMTcyMTAxNTNaGA8yMTI2MDkyMzIx
+MDE1M1owEjEQMA4GA1UEAwwHdGVzdC1jYTBZMBMGByqGSM49AgEGCCqGSM49AwEH
+A0IABL9yf1Cu3U19YwwKEM6EWI+JlpxlffcCMkHpMPEhpLhjPmSifb+7c2lOdAaa
+IoewirgdpfQQQs5oehOVemqKuFGjUzBRMB0GA1UdDgQWBBSd9i2uRpCdi9Bbzu0E
+KHDgn9kVUDAfBgNVHSMEGDAWgBSd9i2uRpCdi9Bbzu0EKHDgn9kVUDAPBgNVHRMB
+Af8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQDc5eBu2NIy/ZwNRc4NXp4dNOjE
+N8ovZ31PBt/guVySEQIhAIBfkNpjzi/irAl9zr8mIVe3iVWniuhETTcv4MVPmDxX
+-----END CERTIFICATE-----
+";
+
+	/// A server accepting the requests, but never responding.
+	async fn fx_silent_server() -> Result<String> {
+		let app = Router::new().fallback(std::future::pending::<()>);
+		let addr = fx_serve(app).await?;
+		Ok(format!("http://{addr}/mcp"))
+	}
+
+	/// An HTTP proxy answering every request with its request line.
+	async fn fx_proxy_server() -> Result<String> {
+		let app =
+			Router::new().fallback(async |req: Request| format!("{} {} {:?}", req.method(), req.uri(), req.version()));
+		let addr = fx_serve(app).await?;
+		Ok(format!("http://{addr}"))
+	}
+
+	/// Serves the app on a local port, returning its address.
+	async fn fx_serve(app: Router) -> Result<std::net::SocketAddr> {
+		let listener = TcpListener::bind("127.0.0.1:0").await?;
+		let addr = listener.local_addr()?;
+		tokio::spawn(async move { axum::serve(listener, app).await });
+		Ok(addr)
+	}
+}
+
+// endregion: --- Tests
@@ -1,143 +1,707 @@
-use crate::mcp::client::transport::{ClientHttpTransportConfig, TransportTrx};
-use crate::mcp::client::transport::{CommTx, Result};
+use crate::mcp::ProtocolVersion;
+use crate::mcp::client::transport::support::{
+	MessageInfo, SharedS2cTx, check_status, content_type, report_transport_error,
+};
+use crate::mcp::client::transport::{ClientHttpTransportConfig, HttpRequestHeaders, TransportTrx};
+use crate::mcp::client::transport::{Error, RequestTransportError, Result, SseConn};
+use crate::mcp::transport::CommTx;
 use eventsource_stream::Eventsource;
 use futures::stream::StreamExt;
-use reqwest::header::HeaderValue;
-use reqwest::{Response, ResponseBuilderExt};
-use std::ops::Index;
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use tracing::{debug, error};
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::{AbortHandle, JoinSet};
+use tracing::{debug, error, info, warn};
 
+const HEADER_SESSION_ID: &str = "mcp-session-id";
+const HEADER_PROTOCOL_VERSION: &str = "mcp-protocol-version";
+const HEADER_LAST_EVENT_ID: &str = "last-event-id";
+
+/// The delay before reconnecting a GET SSE stream (unless the server sent a `retry`).
+const SSE_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// The consecutive failed GET SSE (re)connections before giving up.
+const SSE_MAX_RECONNECT_ATTEMPTS: usize = 5;
+
+/// The Streamable HTTP transport (client side).
+///
+/// - Each client message is POSTed, the server answering with a JSON body or an SSE stream.
+/// - Once initialized, a standalone GET SSE stream listens to the server requests and notifications
+///   (when the server offers it).
+/// - SSE streams disconnected are resumed with a GET and the `Last-Event-ID` of the last event received.
+/// - When the session expires (404), the session is re-initialized (with the initialize request of the client),
+///   and the message is sent again.
+/// - The session is terminated with a DELETE when the client is closed (or dropped).
+/// - With `legacy_sse_fallback`, falls back to the legacy HTTP+SSE transport when the initialize fails with a 4xx.
 pub struct ClientHttpTransport {
 	config: Arc<ClientHttpTransportConfig>,
+	/// The negotiated protocol version (set by the client after the initialize).
+	protocol_version: Arc<RwLock<Option<ProtocolVersion>>>,
+	/// The connection and its send loop task (set on start).
+	conn: Option<(Arc<HttpConn>, AbortHandle)>,
+}
+
+/// Setters
+impl ClientHttpTransport {
+	pub(crate) fn set_protocol_version(&self, protocol_version: ProtocolVersion) {
+		*self.protocol_version.write().unwrap_or_else(|e| e.into_inner()) = Some(protocol_version);
+	}
 }
 
 /// Lifecyle - start
 impl ClientHttpTransport {
 	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
 		let TransportTrx {
-			c2s_rx: in_rx,
-			s2c_tx: out_tx,
-			s2c_aux_tx: err_tx,
+			c2s_rx,
+			s2c_tx,
+			s2c_aux_tx,
+			req_err_tx,
 		} = transport_trx;
 
-		// TODO: probably need add cookies support
-		let req_client = reqwest::ClientBuilder::new().build()?;
+		let req_client = self.config.build_reqwest_client()?;
+		let request_headers = Arc::new(self.config.build_request_headers()?);
 
-		// -- Sending Request to Server (equivalent of std_in/stdout_out)
-		let config = self.config.clone();
+		let conn = Arc::new(HttpConn {
+			config: self.config.clone(),
+			req_client,
+			request_headers,
+			protocol_version: self.protocol_version.clone(),
+			session_id: RwLock::default(),
+			initialize_msg: RwLock::default(),
+			listener: Mutex::default(),
+			s2c_tx: SharedS2cTx::new(s2c_tx),
+			s2c_aux_tx,
+			req_err_tx,
+			reinitializing: tokio::sync::Mutex::default(),
+			legacy_conn: RwLock::default(),
+			closed: watch::Sender::new(false),
+		});
 
-		let session_id_holder: Arc<Mutex<Option<String>>> = Arc::default();
+		// -- Sending the client messages to the server (equivalent of std_in/stdout_out)
+		// NOTE: The initialize is sent inline (it starts the session, or falls back to the legacy transport),
+		//       and the other messages in their own tasks, so that a slow response does not hold back
+		//       the next messages (e.g., the `notifications/cancelled` of this request).
+		let sender_conn = conn.clone();
+		let sender = tokio::spawn(async move {
+			let conn = sender_conn;
+			// NOTE: Dropped with the sender task (on close), which aborts the POSTs in flight.
+			let mut send_tasks = JoinSet::new();
+			while let Ok(msg) = c2s_rx.recv().await {
+				if let Some(legacy_conn) = conn.legacy_conn() {
+					legacy_conn.send_message(msg).await;
+					continue;
+				}
+				if MessageInfo::from_msg(&msg).method.as_deref() == Some("initialize") {
+					if let SendOutcome::LegacySseFallback(msg) = conn.send_message(msg).await {
+						conn.connect_legacy_sse(msg).await;
+					}
+				} else {
+					let conn = conn.clone();
+					send_tasks.spawn(async move { conn.send_message(msg).await });
+				}
 
-		tokio::spawn(async move {
-			while let Ok(txt) = in_rx.recv().await {
-				// TODO: remove the txt.clone
-				let req = req_client
-					.post(&config.url)
-					.header(reqwest::header::CONTENT_TYPE, "application/json")
-					.header(reqwest::header::ACCEPT, "text/event-stream, application/json")
-					.body(txt);
+				// Clean up the completed send tasks
+				while send_tasks.try_join_next().is_some() {}
+			}
+			// The client is gone, so, terminate the session.
+			conn.close().await;
+		});
+		self.conn = Some((conn, sender.abort_handle()));
 
-				let mut session_holder_guard = session_id_holder.lock().await;
-				let (req, holder_sid) = if let Some(session_id) = session_holder_guard.as_ref() {
-					let req = req.header("mcp-session-id", session_id);
-					(req, Some(session_id))
-				} else {
-					// TODO: Make sure to do a warn if not initialize event (string loose match)
-					(req, None)
-				};
-				// drop(session_holder_guard);
+		Ok(())
+	}
+}
 
-				// -- Send Request
-				let Ok(mut res) = req.send().await else {
-					error!("Cannot build MCP SEND REQUEST");
-					continue;
+/// Lifecyle - close
+impl ClientHttpTransport {
+	/// Stops sending, stops the SSE streams, and terminates the session (waiting for the DELETE response).
+	pub(crate) async fn close(&self) {
+		let Some((conn, sender)) = self.conn.as_ref() else {
+			return;
+		};
+		sender.abort();
+		conn.close().await;
+	}
+}
+
+// region:    --- HttpConn
+
+/// The state of the HTTP connection, shared by the send loop and the SSE stream tasks.
+struct HttpConn {
+	config: Arc<ClientHttpTransportConfig>,
+	req_client: reqwest::Client,
+	/// The static headers and bearer token (added to every request).
+	request_headers: Arc<HttpRequestHeaders>,
+	protocol_version: Arc<RwLock<Option<ProtocolVersion>>>,
+	/// The `mcp-session-id` given by the server on initialize.
+	session_id: RwLock<Option<String>>,
+	/// The initialize request of the client, replayed to re-initialize the session when it expires.
+	initialize_msg: RwLock<Option<String>>,
+	/// The standalone GET SSE stream task.
+	listener: Mutex<Option<AbortHandle>>,
+	/// Shared with the legacy SSE connection (which drops it when its stream ends).
+	s2c_tx: SharedS2cTx,
+	s2c_aux_tx: CommTx,
+	req_err_tx: flume::Sender<RequestTransportError>,
+	/// Held while re-initializing an expired session (the concurrent messages may all get a 404).
+	reinitializing: tokio::sync::Mutex<()>,
+	/// Set when the server only supports the legacy HTTP+SSE transport (see `legacy_sse_fallback`).
+	legacy_conn: RwLock<Option<Arc<SseConn>>>,
+	/// Set to true on close (ends the SSE streams).
+	closed: watch::Sender<bool>,
+}
+
+/// Send
+impl HttpConn {
+	async fn send_message(self: &Arc<Self>, msg: String) -> SendOutcome {
+		let info = MessageInfo::from_msg(&msg);
+
+		// -- A new initialize starts a new session
+		let is_initialize = info.method.as_deref() == Some("initialize");
+		if is_initialize {
+			self.stop_listener();
+			*self.session_id.write().unwrap_or_else(|e| e.into_inner()) = None;
+			*self.initialize_msg.write().unwrap_or_else(|e| e.into_inner()) = Some(msg.clone());
+		}
+
+		// -- Send (re-initializing the session if expired)
+		let session_id = self.session_id();
+		let res = match (self.post(&msg).await, session_id) {
+			(Err(Error::HttpStatus { status: 404, .. }), Some(session_id)) if !is_initialize => {
+				match self.reinitialize(&session_id).await {
+					Ok(()) => self.post(&msg).await,
+					Err(err) => Err(err),
+				}
+			}
+			// The server may only support the legacy HTTP+SSE transport (e.g., 404 or 405).
+			// NOTE: 401 is not a transport mismatch, but an authorization error.
+			(Err(Error::HttpStatus { status, .. }), _)
+				if is_initialize
+					&& self.config.legacy_sse_fallback
+					&& (400..500).contains(&status)
+					&& status != 401 =>
+			{
+				return SendOutcome::LegacySseFallback(msg);
+			}
+			(res, _) => res,
+		};
+
+		let res = match res {
+			Ok(res) => res,
+			Err(err) => {
+				self.report_error(&info, err).await;
+				return SendOutcome::Sent;
+			}
+		};
+
+		// -- Once initialized, listen to the server messages
+		if info.method.as_deref() == Some("notifications/initialized") {
+			self.start_listener();
+		}
+
+		// -- Forward the response
+		if let Err(err) = self.process_response(res, info.request_id()).await {
+			self.report_error(&info, err).await;
+		}
+
+		SendOutcome::Sent
+	}
+
+	fn legacy_conn(&self) -> Option<Arc<SseConn>> {
+		self.legacy_conn.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// Connects with the legacy HTTP+SSE transport (on the same url), and sends the initialize request.
+	async fn connect_legacy_sse(&self, initialize_msg: String) {
+		info!(url = %self.config.url, "Streamable HTTP initialize failed. Falling back to the legacy HTTP+SSE transport.");
+		let res = SseConn::connect(
+			self.req_client.clone(),
+			self.request_headers.clone(),
+			&self.config.url,
+			self.s2c_tx.clone(),
+			self.s2c_aux_tx.clone(),
+			self.req_err_tx.clone(),
+		)
+		.await;
+		match res {
+			Ok(legacy_conn) => {
+				let legacy_conn = Arc::new(legacy_conn);
+				*self.legacy_conn.write().unwrap_or_else(|e| e.into_inner()) = Some(legacy_conn.clone());
+				legacy_conn.send_message(initialize_msg).await;
+			}
+			Err(err) => {
+				self.report_error(&MessageInfo::from_msg(&initialize_msg), err).await;
+			}
+		}
+	}
+
+	/// POSTs a message, returning the response when successful (2xx).
+	async fn post(&self, msg: &str) -> Result<Response> {
+		let req = self
+			.request(Method::POST)
+			.await?
+			.header(CONTENT_TYPE, "application/json")
+			.header(ACCEPT, "application/json, text/event-stream")
+			.body(msg.to_string());
+		self.send(req).await
+	}
+
+	/// Forwards the response of a POST (JSON body, or SSE stream read in its own task).
+	async fn process_response(self: &Arc<Self>, res: Response, request_id: Option<Value>) -> Result<()> {
+		if res.status() == StatusCode::ACCEPTED {
+			return Ok(());
+		}
+
+		match content_type(&res).as_deref() {
+			// -- When sse, we treat it as such
+			Some("text/event-stream") => {
+				let conn = self.clone();
+				tokio::spawn(async move {
+					conn.run_post_stream(res, request_id).await;
+				});
+			}
+			// -- When application/json or no contentype treat it as the json-rpc response
+			// NOTE: None because sometime servers (like the everything) seems
+			//       to be sending json-rpc error with not content type (but json)
+			Some("application/json") | None => {
+				let txt = res.text().await?;
+				if !txt.trim().is_empty() {
+					self.s2c_tx.send(txt).await?;
+				}
+			}
+			Some(other) => {
+				return Err(Error::custom(format!(
+					"MCP Server responded with non supported content type '{other}'"
+				)));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Replays the initialize request and the initialized notification, to start a new session
+	/// (unless the `expired_session_id` was already replaced, by a concurrent message).
+	///
+	/// NOTE: The initialize response is not forwarded (the client already has the initialize result).
+	async fn reinitialize(self: &Arc<Self>, expired_session_id: &str) -> Result<()> {
+		let _reinitializing = self.reinitializing.lock().await;
+		if self.session_id().as_deref() != Some(expired_session_id) {
+			return Ok(());
+		}
+		info!("MCP session expired (404). Re-initializing the session.");
+
+		let initialize_msg = self.initialize_msg.read().unwrap_or_else(|e| e.into_inner()).clone();
+		let Some(initialize_msg) = initialize_msg else {
+			return Err(Error::custom(
+				"Cannot re-initialize the MCP session, no initialize request sent",
+			));
+		};
+
+		self.stop_listener();
+		*self.session_id.write().unwrap_or_else(|e| e.into_inner()) = None;
+
+		let res = self.post(&initialize_msg).await?;
+		res.bytes().await?;
+
+		let initialized_msg = json!({"jsonrpc": "2.0", "method": "notifications/initialized"}).to_string();
+		let res = self.post(&initialized_msg).await?;
+		res.bytes().await?;
+
+		self.start_listener();
+
+		Ok(())
+	}
+
+	/// Reports a failed message to the client (see `report_transport_error`).
+	async fn report_error(&self, info: &MessageInfo, err: Error) {
+		report_transport_error(&self.req_err_tx, &self.s2c_aux_tx, info, err).await;
+	}
+}
+
+/// SSE streams
+impl HttpConn {
+	/// Reads the SSE stream of a POST request.
+	///
+	/// When disconnected before the response, the stream is resumed with a GET and the `Last-Event-ID`.
+	async fn run_post_stream(self: Arc<Self>, res: Response, request_id: Option<Value>) {
+		let end = self.forward_sse(res, request_id.as_ref()).await;
+		let Some(request_id) = request_id else {
+			return;
+		};
+		if end.response_received || self.is_closed() {
+			return;
+		}
+
+		match end.last_event_id {
+			Some(last_event_id) => {
+				debug!(%request_id, %last_event_id, "MCP SSE stream disconnected. Resuming.");
+				self.run_get_stream(Some(last_event_id), Some(request_id)).await;
+			}
+			None => {
+				let info = MessageInfo {
+					id: Some(request_id),
+					method: None,
 				};
+				let err =
+					Error::custom("SSE stream ended before the response (and cannot be resumed without event id)");
+				self.report_error(&info, err).await;
+			}
+		}
+	}
+
+	/// Runs a GET SSE stream, reconnecting with the `Last-Event-ID` when disconnected.
+	///
+	/// - With a `request_id`, resumes the stream of a POST request (until its response).
+	/// - Without, the standalone stream for the server requests and notifications (until the session ends).
+	async fn run_get_stream(self: Arc<Self>, mut last_event_id: Option<String>, request_id: Option<Value>) {
+		let mut delay = SSE_RECONNECT_DELAY;
+		let mut failed_attempts = 0;
 
-				// -- Set and Check mcp-session-id
-				let res_session_id = res.headers().get("mcp-session-id").and_then(|v| v.to_str().ok());
-				match (holder_sid, res_session_id) {
-					(None, Some(session_id)) => {
-						*session_holder_guard = Some(session_id.to_string());
+		loop {
+			if self.is_closed() {
+				return;
+			}
+			match self.get(last_event_id.as_deref()).await {
+				Ok(res) => {
+					let end = self.forward_sse(res, request_id.as_ref()).await;
+					if end.response_received {
+						return;
+					}
+					if end.last_event_id.is_some() {
+						last_event_id = end.last_event_id;
+					}
+					if let Some(retry) = end.retry {
+						delay = retry;
 					}
-					(Some(holder_sid), Some(session_id)) => {
-						if holder_sid != session_id {
-							error!("MCP Server did not send matching session id. Abort");
-							continue;
-						}
+					if end.event_count > 0 {
+						failed_attempts = 0;
 					}
-					_ => (),
 				}
+				// The server does not offer a GET stream.
+				Err(Error::HttpStatus { status: 405, .. }) if request_id.is_none() => {
+					debug!("MCP Server does not offer a GET SSE stream (405)");
+					return;
+				}
+				// The session expired (it is re-initialized on the next message sent).
+				Err(Error::HttpStatus { status: 404, .. }) => {
+					info!("MCP session expired (404). GET SSE stream ended.");
+					return;
+				}
+				Err(err) => {
+					warn!("MCP GET SSE stream failed. Cause: {err}");
+				}
+			}
 
-				// FIXME: Need handle cases when no content-type or content-type is application/json
-				let res_content_type = res.headers().get("content-type").and_then(|v| v.to_str().ok());
-				match res_content_type {
-					// -- When sse, we treat it as such
-					Some("text/event-stream") => {
-						process_sse_event(res, &out_tx).await;
-					}
-					// -- When application/json or no contentype treat it as the json-rpc response
-					// NOTE: None because sometime servers (like the everything) seems
-					//       to be sending json-rpc error with not content type (but json)
-					Some("application/json") | None => {
-						let txt = match res.text().await {
-							Ok(txt) => txt,
-							Err(err) => {
-								error!("MCP Response fail to read body - {err}");
-								continue;
-							}
+			failed_attempts += 1;
+			if failed_attempts >= SSE_MAX_RECONNECT_ATTEMPTS {
+				let err = Error::custom(format!(
+					"GET SSE stream failed after {SSE_MAX_RECONNECT_ATTEMPTS} reconnection attempts"
+				));
+				match request_id {
+					Some(request_id) => {
+						let info = MessageInfo {
+							id: Some(request_id),
+							method: None,
 						};
-						if let Err(err) = out_tx.send(txt).await {
-							error!(%err, "while sending txt to out_txt.");
-							continue;
-						}
+						self.report_error(&info, err).await;
 					}
-					Some(other) => {
-						error!("MCP Server responded with non supporter content type {other}.");
+					None => {
+						let _ = self.s2c_aux_tx.send(format!("HTTP transport error: {err}")).await;
 					}
 				}
+				return;
+			}
+
+			tokio::time::sleep(delay).await;
+		}
+	}
+
+	/// Opens a GET SSE stream (resuming after the `last_event_id` if any).
+	async fn get(&self, last_event_id: Option<&str>) -> Result<Response> {
+		let mut req = self.request(Method::GET).await?.header(ACCEPT, "text/event-stream");
+		if let Some(last_event_id) = last_event_id {
+			req = req.header(HEADER_LAST_EVENT_ID, last_event_id);
+		}
+		let res = self.send(req).await?;
+
+		match content_type(&res).as_deref() {
+			Some("text/event-stream") => Ok(res),
+			other => Err(Error::custom(format!(
+				"MCP Server GET stream responded with content type '{}' (expected 'text/event-stream')",
+				other.unwrap_or_default()
+			))),
+		}
+	}
+
+	/// Forwards the SSE events data to the client, until the stream ends (or the response of `request_id`).
+	async fn forward_sse(&self, res: Response, request_id: Option<&Value>) -> SseStreamEnd {
+		let mut end = SseStreamEnd::default();
+		let mut stream = res.bytes_stream().eventsource();
+		let mut closed = self.closed.subscribe();
+
+		loop {
+			let event = tokio::select! {
+				event = stream.next() => event,
+				_ = closed.wait_for(|closed| *closed) => None,
+			};
+			let Some(event) = event else {
+				break;
+			};
+			let event = match event {
+				Ok(event) => event,
+				Err(err) => {
+					warn!("MCP SSE stream error. Cause: {err}");
+					break;
+				}
+			};
+			debug!(
+				"mcp sse event received: id={},type={},data_len={}",
+				event.id,
+				event.event,
+				event.data.len()
+			);
+
+			end.event_count += 1;
+			if !event.id.is_empty() {
+				end.last_event_id = Some(event.id);
+			}
+			if event.retry.is_some() {
+				end.retry = event.retry;
 			}
+
+			// NOTE: Events without data (e.g., priming the event id) or not `message` do not carry JSON-RPC messages.
+			if event.data.trim().is_empty() || !(event.event.is_empty() || event.event == "message") {
+				continue;
+			}
+
+			let is_response = request_id.is_some_and(|request_id| is_response_to(&event.data, request_id));
+			if let Err(err) = self.s2c_tx.send(event.data).await {
+				error!(%err, "while sending sse event data to s2c_tx.");
+				break;
+			}
+			if is_response {
+				end.response_received = true;
+				break;
+			}
+		}
+
+		end
+	}
+
+	fn start_listener(self: &Arc<Self>) {
+		let conn = self.clone();
+		let handle = tokio::spawn(async move {
+			conn.run_get_stream(None, None).await;
 		});
+		let previous = self
+			.listener
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.replace(handle.abort_handle());
+		if let Some(previous) = previous {
+			previous.abort();
+		}
+	}
 
-		Ok(())
+	fn stop_listener(&self) {
+		if let Some(listener) = self.listener.lock().unwrap_or_else(|e| e.into_inner()).take() {
+			listener.abort();
+		}
 	}
 }
 
-// region:    --- Support
+/// Lifecycle - close
+impl HttpConn {
+	/// Stops the SSE streams and terminates the session (DELETE, or closes the legacy SSE connection).
+	async fn close(&self) {
+		self.closed.send_replace(true);
+		self.stop_listener();
 
-async fn process_sse_event(res: Response, out_tx: &CommTx) -> Result<()> {
-	let mut stream = res.bytes_stream().eventsource();
-
-	while let Some(event) = stream.next().await {
-		match event {
-			Ok(event) => {
-				//
-				debug!(
-					"mcp sse event received: id={},type={},data_len={}",
-					event.id,
-					event.event,
-					event.data.len()
-				);
-				if let Err(err) = out_tx.send(event.data).await {
-					error!(%err, "while sending txt to out_txt.");
-				}
+		if let Some(legacy_conn) = self.legacy_conn.write().unwrap_or_else(|e| e.into_inner()).take() {
+			legacy_conn.close();
+			return;
+		}
+
+		let Some(session_id) = self.session_id() else {
+			return;
+		};
+
+		let res = match self.request(Method::DELETE).await {
+			Ok(req) => self.send(req).await,
+			Err(err) => Err(err),
+		};
+		*self.session_id.write().unwrap_or_else(|e| e.into_inner()) = None;
+		match res {
+			Ok(_) => debug!(%session_id, "MCP session terminated"),
+			// The server does not allow the client to terminate the session.
+			Err(Error::HttpStatus { status: 405, .. }) => (),
+			Err(err) => warn!(%session_id, "MCP session DELETE failed. Cause: {err}"),
+		}
+	}
+}
+
+/// Support
+impl HttpConn {
+	/// Sends the request, mapping the non successful responses to errors (see `check_status`).
+	///
+	/// With OAuth, a 401 authorizes the client (see `HttpRequestHeaders::reauthorize`), and sends the request again.
+	async fn send(&self, req: RequestBuilder) -> Result<Response> {
+		let req = req.build()?;
+		let retry_req = req.try_clone();
+
+		let res = self.req_client.execute(req).await?;
+		self.update_session_id(&res)?;
+
+		let (www_authenticate, body, retry_req) = match (check_status(res).await, retry_req) {
+			(Err(Error::Unauthorized { www_authenticate, body }), Some(retry_req)) => {
+				(www_authenticate, body, retry_req)
 			}
-			Err(e) => error!("stream event error occured: {}", e),
+			(res, _) => return res,
+		};
+		let Some(retry_req) = self
+			.request_headers
+			.reauthorize(&self.config.url, www_authenticate.as_deref(), retry_req)
+			.await?
+		else {
+			return Err(Error::Unauthorized { www_authenticate, body });
+		};
+
+		let res = self.req_client.execute(retry_req).await?;
+		self.update_session_id(&res)?;
+		check_status(res).await
+	}
+
+	/// Sets the session id given by the server (on initialize), and checks it matches afterward.
+	fn update_session_id(&self, res: &Response) -> Result<()> {
+		let res_session_id = res.headers().get(HEADER_SESSION_ID).and_then(|v| v.to_str().ok());
+		match (self.session_id(), res_session_id) {
+			(None, Some(session_id)) => {
+				*self.session_id.write().unwrap_or_else(|e| e.into_inner()) = Some(session_id.to_string());
+			}
+			(Some(holder_sid), Some(session_id)) if holder_sid != session_id => {
+				return Err(Error::custom(format!(
+					"MCP Server did not send matching session id (expected '{holder_sid}', got '{session_id}')"
+				)));
+			}
+			_ => (),
+		}
+		Ok(())
+	}
+
+	fn is_closed(&self) -> bool {
+		*self.closed.borrow()
+	}
+
+	fn session_id(&self) -> Option<String> {
+		self.session_id.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// Builds a request with the config headers (static and bearer token), and the session and protocol version headers.
+	async fn request(&self, method: Method) -> Result<RequestBuilder> {
+		let req = self.req_client.request(method, &self.config.url);
+		let mut req = self.request_headers.apply(req).await?;
+
+		// -- Add the mcp-protocol-version (when the negotiated version requires it)
+		let version = *self.protocol_version.read().unwrap_or_else(|e| e.into_inner());
+		if let Some(version) = version.filter(|v| v.has_protocol_version_header()) {
+			req = req.header(HEADER_PROTOCOL_VERSION, version.as_str());
 		}
+
+		if let Some(session_id) = self.session_id() {
+			req = req.header(HEADER_SESSION_ID, session_id);
+		}
+
+		Ok(req)
 	}
+}
+
+// endregion: --- HttpConn
 
-	Ok(())
+// region:    --- Support
+
+/// The outcome of sending a client message.
+enum SendOutcome {
+	Sent,
+	/// The initialize request (to send with the legacy HTTP+SSE transport).
+	LegacySseFallback(String),
 }
+
+/// How an SSE stream ended.
+#[derive(Default)]
+struct SseStreamEnd {
+	event_count: usize,
+	last_event_id: Option<String>,
+	retry: Option<Duration>,
+	response_received: bool,
+}
+
+/// Returns true if the JSON-RPC message is the response (result or error) of the request.
+fn is_response_to(data: &str, request_id: &Value) -> bool {
+	let Ok(value) = serde_json::from_str::<Value>(data) else {
+		return false;
+	};
+	value.get("id") == Some(request_id) && (value.get("result").is_some() || value.get("error").is_some())
+}
+
 // endregion: --- Support
 
 // region:    --- Froms
 
+impl ClientHttpTransport {
+	/// A new transport (not started) with the same config (for the reconnect).
+	pub(crate) fn new_from_config(&self) -> Self {
+		Self {
+			config: self.config.clone(),
+			protocol_version: Arc::default(),
+			conn: None,
+		}
+	}
+}
+
 impl From<ClientHttpTransportConfig> for ClientHttpTransport {
 	fn from(config: ClientHttpTransportConfig) -> Self {
 		let config = Arc::new(config);
-		Self { config }
+		Self {
+			config,
+			protocol_version: Arc::default(),
+			conn: None,
+		}
 	}
 }
 
 // endregion: --- Froms
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_http_transport_message_info_and_is_response_to() -> Result<()> {
+		// -- Setup & Fixtures
+		let request = MessageInfo::from_msg(r#"{"jsonrpc":"2.0","id":7,"method":"tools/list"}"#);
+		let response = MessageInfo::from_msg(r#"{"jsonrpc":"2.0","id":3,"result":{}}"#);
+		let error_msg = r#"{"jsonrpc":"2.0","id":7,"error":{"code":-32603,"message":"Internal failure"}}"#;
+
+		// -- Exec
+		let request_id = request.request_id().ok_or("Should have a request id")?;
+
+		// -- Check
+		assert!(response.request_id().is_none());
+		assert!(is_response_to(error_msg, &request_id));
+		assert!(!is_response_to(error_msg, &json!(8)));
+		assert!(!is_response_to(
+			r#"{"jsonrpc":"2.0","method":"notifications/progress"}"#,
+			&request_id
+		));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
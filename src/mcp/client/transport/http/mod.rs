@@ -1,8 +1,10 @@
 // region:    --- Modules
 
+mod bearer_token_provider;
 mod http_config;
 mod http_transport;
 
+pub use bearer_token_provider::*;
 pub use http_config::*;
 pub use http_transport::*;
 
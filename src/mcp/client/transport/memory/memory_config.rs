@@ -0,0 +1,52 @@
+use crate::mcp::server::{Server, ServerMemoryTransportConfig};
+use crate::mcp::transport::{CommRx, CommTx};
+use std::sync::Mutex;
+
+/// The config of the in-memory transport (no process, no network), connecting the client to
+/// an in-crate `Server`, or to a scripted fake server, through channels.
+///
+/// ```ignore
+/// // -- To an in-crate server (each connect, or reconnect, runs a new server session)
+/// client.connect(ClientMemoryTransportConfig::from_server(server)).await?;
+///
+/// // -- To a scripted fake server (the raw messages of the server end)
+/// let (transport_config, server_end) = ClientMemoryTransportConfig::new_duplex();
+/// let (c2s_rx, s2c_tx) = server_end.into_channels();
+/// ```
+pub struct ClientMemoryTransportConfig {
+	pub(crate) peer: MemoryPeer,
+}
+
+pub(crate) enum MemoryPeer {
+	Server(Server),
+	/// The client end of a duplex (single use, so, no reconnect).
+	Duplex(Mutex<Option<(CommTx, CommRx)>>),
+}
+
+/// Constructors
+impl ClientMemoryTransportConfig {
+	/// Connects to this server (a new session for each connect).
+	pub fn from_server(server: Server) -> Self {
+		Self {
+			peer: MemoryPeer::Server(server),
+		}
+	}
+
+	/// A connected pair of the client config and of the server end.
+	///
+	/// The server end can be served with `Server::serve(..)`, or be turned into its raw channels
+	/// to script a fake server (see `ServerMemoryTransportConfig::into_channels()`).
+	pub fn new_duplex() -> (Self, ServerMemoryTransportConfig) {
+		let (c2s_tx, c2s_rx) = flume::unbounded::<String>();
+		let (s2c_tx, s2c_rx) = flume::unbounded::<String>();
+
+		let client_config = Self {
+			peer: MemoryPeer::Duplex(Mutex::new(Some((c2s_tx.into(), s2c_rx.into())))),
+		};
+		let server_config = ServerMemoryTransportConfig {
+			c2s_rx: c2s_rx.into(),
+			s2c_tx: s2c_tx.into(),
+		};
+		(client_config, server_config)
+	}
+}
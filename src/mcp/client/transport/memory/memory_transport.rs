@@ -0,0 +1,82 @@
+use crate::mcp::client::transport::memory::memory_config::MemoryPeer;
+use crate::mcp::client::transport::{ClientMemoryTransportConfig, Error, Result, TransportTrx};
+use crate::mcp::transport::{CommRx, CommTx};
+use std::sync::Arc;
+use tracing::{debug, error};
+
+/// The in-memory transport (client side), relaying the messages to the server end channels.
+///
+/// - When the client closes (or is dropped), the server end `c2s_rx` closes (ending the server session).
+/// - When the server end `s2c_tx` is dropped (e.g., the session ended), the client gets disconnected.
+pub struct ClientMemoryTransport {
+	config: Arc<ClientMemoryTransportConfig>,
+}
+
+/// Lifecyle - start
+impl ClientMemoryTransport {
+	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
+		let TransportTrx { c2s_rx, s2c_tx, .. } = transport_trx;
+
+		let (peer_c2s_tx, peer_s2c_rx) = match &self.config.peer {
+			MemoryPeer::Server(server) => {
+				let (client_config, server_config) = ClientMemoryTransportConfig::new_duplex();
+				let server = server.clone();
+				tokio::spawn(async move {
+					if let Err(err) = server.serve(server_config).await {
+						error!("In-memory server session failed. Cause: {err}");
+					}
+				});
+				take_duplex_channels(&client_config.peer)?
+			}
+			peer @ MemoryPeer::Duplex(_) => take_duplex_channels(peer)?,
+		};
+
+		// -- Client to server
+		tokio::spawn(async move {
+			c2s_rx.forward_to(&peer_c2s_tx).await;
+			debug!("In-memory c2s ended");
+		});
+
+		// -- Server to client
+		// NOTE: Ends (dropping the s2c_tx) when the server end is gone.
+		tokio::spawn(async move {
+			peer_s2c_rx.forward_to(&s2c_tx).await;
+			debug!("In-memory s2c ended");
+		});
+
+		Ok(())
+	}
+
+	/// A new transport (not started) with the same config, to reconnect (None for a duplex, as single use).
+	pub(crate) fn new_from_config(&self) -> Option<Self> {
+		match self.config.peer {
+			MemoryPeer::Server(_) => Some(Self {
+				config: self.config.clone(),
+			}),
+			MemoryPeer::Duplex(_) => None,
+		}
+	}
+}
+
+impl From<ClientMemoryTransportConfig> for ClientMemoryTransport {
+	fn from(config: ClientMemoryTransportConfig) -> Self {
+		Self {
+			config: Arc::new(config),
+		}
+	}
+}
+
+// region:    --- Support
+
+fn take_duplex_channels(peer: &MemoryPeer) -> Result<(CommTx, CommRx)> {
+	let MemoryPeer::Duplex(channels) = peer else {
+		return Err(Error::custom("Not an in-memory duplex"));
+	};
+	channels
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.take()
+		.ok_or_else(|| Error::custom("In-memory duplex already started (single use)"))
+}
+
+// endregion: --- Support
@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod memory_config;
+mod memory_transport;
+
+pub use memory_config::*;
+pub use memory_transport::*;
+
+// endregion: --- Modules
@@ -4,14 +4,24 @@ mod support;
 
 mod client_transport;
 mod comm_trx;
+mod custom_transport;
 mod error;
 mod http;
+mod memory;
+mod sse;
 mod stdio;
+#[cfg(feature = "websocket")]
+mod websocket;
 
 pub use client_transport::*;
 pub use comm_trx::*;
+pub use custom_transport::CustomClientTransport;
 pub use error::{Error, Result};
 pub use http::*;
+pub use memory::*;
+pub use sse::*;
 pub use stdio::*;
+#[cfg(feature = "websocket")]
+pub use websocket::*;
 
 // endregion: --- Modules
@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod sse_config;
+mod sse_transport;
+
+pub use sse_config::*;
+pub use sse_transport::*;
+
+// endregion: --- Modules
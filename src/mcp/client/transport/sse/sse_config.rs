@@ -0,0 +1,100 @@
+use crate::mcp::client::ClientHttpTransportConfig;
+use crate::mcp::client::auth::OAuthClient;
+use crate::mcp::client::transport::IntoBearerTokenProviderAsyncFn;
+use std::time::Duration;
+
+/// The config of the legacy HTTP+SSE transport (protocol version 2024-11-05),
+/// with the url of the SSE endpoint (e.g., `http://localhost:3001/sse`).
+///
+/// The headers, authorization, and `reqwest::Client` settings are the ones of the `ClientHttpTransportConfig`
+/// (which can be converted into this config).
+///
+/// NOTE: For the servers supporting both, prefer the Streamable HTTP transport (`ClientHttpTransportConfig`).
+pub struct ClientSseTransportConfig {
+	pub(crate) http: ClientHttpTransportConfig,
+}
+
+impl ClientSseTransportConfig {
+	pub fn new(url: impl Into<String>) -> Self {
+		Self {
+			http: ClientHttpTransportConfig::new(url),
+		}
+	}
+}
+
+/// Builders (see the `ClientHttpTransportConfig` ones)
+impl ClientSseTransportConfig {
+	/// Adds a static header, sent with every request (validated on connect).
+	pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.http = self.http.with_header(name, value);
+		self
+	}
+
+	/// Sets the bearer token provider, called before each request (so it can cache and refresh the token).
+	pub fn with_bearer_token_provider(mut self, provider: impl IntoBearerTokenProviderAsyncFn) -> Self {
+		self.http = self.http.with_bearer_token_provider(provider);
+		self
+	}
+
+	/// Sets a static bearer token (for a token which does not expire, e.g., an API key).
+	pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+		self.http = self.http.with_bearer_token(token);
+		self
+	}
+
+	/// Authorizes the requests with OAuth 2.1 (see `OAuthClient`), the 401 responses triggering the authorization.
+	pub fn with_oauth(mut self, oauth: OAuthClient) -> Self {
+		self.http = self.http.with_oauth(oauth);
+		self
+	}
+
+	pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+		self.http = self.http.with_connect_timeout(connect_timeout);
+		self
+	}
+
+	/// The timeout of each read of the response body.
+	///
+	/// NOTE: Applies to the SSE stream as well, so an idle stream disconnects after this timeout.
+	pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+		self.http = self.http.with_read_timeout(read_timeout);
+		self
+	}
+
+	/// Proxies all the requests through this proxy url (e.g., `http://proxy.local:8080`).
+	pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Self {
+		self.http = self.http.with_proxy(proxy_url);
+		self
+	}
+
+	/// Adds a PEM encoded root certificate (e.g., of an internal CA), trusted in addition to the built-in ones.
+	pub fn with_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+		self.http = self.http.with_root_certificate_pem(pem);
+		self
+	}
+
+	/// Uses this pre-built `reqwest::Client` (the timeouts, proxy, and root certificates are then ignored).
+	pub fn with_reqwest_client(mut self, reqwest_client: reqwest::Client) -> Self {
+		self.http = self.http.with_reqwest_client(reqwest_client);
+		self
+	}
+}
+
+/// Accessors
+impl ClientSseTransportConfig {
+	pub fn url(&self) -> &str {
+		&self.http.url
+	}
+}
+
+// region:    --- Froms
+
+/// The SSE url, headers, authorization, and `reqwest::Client` settings of the Streamable HTTP config
+/// (its `legacy_sse_fallback` does not apply).
+impl From<ClientHttpTransportConfig> for ClientSseTransportConfig {
+	fn from(http: ClientHttpTransportConfig) -> Self {
+		Self { http }
+	}
+}
+
+// endregion: --- Froms
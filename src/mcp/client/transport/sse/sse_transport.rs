@@ -0,0 +1,234 @@
+use crate::mcp::client::transport::support::{
+	MessageInfo, SharedS2cTx, check_status, content_type, report_transport_error,
+};
+use crate::mcp::client::transport::{
+	ClientSseTransportConfig, Error, HttpRequestHeaders, RequestTransportError, Result, TransportTrx,
+};
+use crate::mcp::transport::CommTx;
+use eventsource_stream::Eventsource;
+use futures::stream::StreamExt;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{RequestBuilder, Response, Url};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::AbortHandle;
+use tracing::{debug, error, warn};
+
+/// The max time to wait for the `endpoint` event after the GET.
+const ENDPOINT_EVENT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The legacy HTTP+SSE transport (protocol version 2024-11-05, client side).
+///
+/// - A GET on the SSE url opens the stream, whose first `endpoint` event gives the url to POST the messages to.
+/// - The server messages (responses, requests, notifications) are the `message` events of the stream.
+pub struct ClientSseTransport {
+	config: Arc<ClientSseTransportConfig>,
+}
+
+/// Lifecyle - start
+impl ClientSseTransport {
+	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
+		let TransportTrx {
+			c2s_rx,
+			s2c_tx,
+			s2c_aux_tx,
+			req_err_tx,
+		} = transport_trx;
+
+		let req_client = self.config.http.build_reqwest_client()?;
+		let request_headers = Arc::new(self.config.http.build_request_headers()?);
+		let s2c_tx = SharedS2cTx::new(s2c_tx);
+		let conn = SseConn::connect(
+			req_client,
+			request_headers,
+			self.config.url(),
+			s2c_tx,
+			s2c_aux_tx,
+			req_err_tx,
+		)
+		.await?;
+
+		tokio::spawn(async move {
+			while let Ok(msg) = c2s_rx.recv().await {
+				conn.send_message(msg).await;
+			}
+			// The client is gone, so, close the stream.
+			conn.close();
+		});
+
+		Ok(())
+	}
+}
+
+// region:    --- SseConn
+
+/// A legacy HTTP+SSE connection (also used by the Streamable HTTP transport for its legacy fallback).
+pub(crate) struct SseConn {
+	req_client: reqwest::Client,
+	/// The static headers and bearer token (added to every request).
+	request_headers: Arc<HttpRequestHeaders>,
+	/// The SSE url (the OAuth protected resource).
+	url: Url,
+	/// The url given by the `endpoint` event, to POST the messages to.
+	endpoint: Url,
+	/// The SSE stream reader task.
+	reader: AbortHandle,
+	s2c_aux_tx: CommTx,
+	req_err_tx: flume::Sender<RequestTransportError>,
+}
+
+impl SseConn {
+	/// Opens the SSE stream and waits for the `endpoint` event.
+	pub(crate) async fn connect(
+		req_client: reqwest::Client,
+		request_headers: Arc<HttpRequestHeaders>,
+		url: &str,
+		s2c_tx: SharedS2cTx,
+		s2c_aux_tx: CommTx,
+		req_err_tx: flume::Sender<RequestTransportError>,
+	) -> Result<Self> {
+		let url = Url::parse(url).map_err(Error::custom_from_err)?;
+
+		// -- Open the stream
+		let req = request_headers.apply(req_client.get(url.clone())).await?;
+		let req = req.header(ACCEPT, "text/event-stream");
+		let res = send(&req_client, &request_headers, &url, req).await?;
+		if content_type(&res).as_deref() != Some("text/event-stream") {
+			return Err(Error::custom(format!(
+				"MCP Server SSE endpoint '{url}' did not respond with a 'text/event-stream'"
+			)));
+		}
+		let mut stream = res.bytes_stream().eventsource();
+
+		// -- Wait for the endpoint event
+		let endpoint_event = tokio::time::timeout(ENDPOINT_EVENT_TIMEOUT, async {
+			while let Some(event) = stream.next().await {
+				match event {
+					Ok(event) if event.event == "endpoint" => return Ok(event.data),
+					Ok(event) => debug!(event = %event.event, "SSE event before the endpoint event ignored"),
+					Err(err) => return Err(Error::custom(format!("SSE stream error. Cause: {err}"))),
+				}
+			}
+			Err(Error::custom("SSE stream ended before the endpoint event"))
+		})
+		.await
+		.map_err(|_| Error::custom("SSE stream did not send the endpoint event in time"))??;
+
+		// NOTE: The endpoint is usually relative (e.g., `/messages?sessionId=...`), and must be on the same origin.
+		let endpoint = url.join(endpoint_event.trim()).map_err(Error::custom_from_err)?;
+		if endpoint.origin() != url.origin() {
+			return Err(Error::custom(format!(
+				"MCP Server SSE endpoint '{endpoint}' is not on the origin of '{url}'"
+			)));
+		}
+		debug!(%endpoint, "MCP SSE endpoint received");
+
+		// -- Forward the message events
+		// NOTE: The s2c_tx is dropped when the stream ends, so that the client sees the transport disconnected.
+		let reader_s2c_tx = s2c_tx;
+		let reader_s2c_aux_tx = s2c_aux_tx.clone();
+		let reader = tokio::spawn(async move {
+			while let Some(event) = stream.next().await {
+				let event = match event {
+					Ok(event) => event,
+					Err(err) => {
+						warn!("MCP SSE stream error. Cause: {err}");
+						break;
+					}
+				};
+				if !(event.event.is_empty() || event.event == "message") || event.data.trim().is_empty() {
+					continue;
+				}
+				if let Err(err) = reader_s2c_tx.send(event.data).await {
+					error!(%err, "while sending sse event data to s2c_tx.");
+					break;
+				}
+			}
+			// NOTE: The legacy SSE stream cannot be resumed, so, the transport is disconnected.
+			let _ = reader_s2c_aux_tx.send("HTTP transport error: SSE stream ended").await;
+			reader_s2c_tx.take();
+		});
+
+		Ok(Self {
+			req_client,
+			request_headers,
+			url,
+			endpoint,
+			reader: reader.abort_handle(),
+			s2c_aux_tx,
+			req_err_tx,
+		})
+	}
+
+	/// POSTs a message to the endpoint (the server response being sent on the SSE stream).
+	pub(crate) async fn send_message(&self, msg: String) {
+		let info = MessageInfo::from_msg(&msg);
+
+		if let Err(err) = self.post(msg).await {
+			report_transport_error(&self.req_err_tx, &self.s2c_aux_tx, &info, err).await;
+		}
+	}
+
+	async fn post(&self, msg: String) -> Result<()> {
+		let req = self.request_headers.apply(self.req_client.post(self.endpoint.clone())).await?;
+		let req = req.header(CONTENT_TYPE, "application/json").body(msg);
+		send(&self.req_client, &self.request_headers, &self.url, req).await?;
+		Ok(())
+	}
+
+	pub(crate) fn close(&self) {
+		self.reader.abort();
+	}
+}
+
+/// Sends the request, mapping the non successful responses to errors (see `check_status`).
+///
+/// With OAuth, a 401 authorizes the client (see `HttpRequestHeaders::reauthorize`), and sends the request again.
+async fn send(
+	req_client: &reqwest::Client,
+	request_headers: &HttpRequestHeaders,
+	url: &Url,
+	req: RequestBuilder,
+) -> Result<Response> {
+	let req = req.build()?;
+	let retry_req = req.try_clone();
+
+	let res = req_client.execute(req).await?;
+
+	let (www_authenticate, body, retry_req) = match (check_status(res).await, retry_req) {
+		(Err(Error::Unauthorized { www_authenticate, body }), Some(retry_req)) => (www_authenticate, body, retry_req),
+		(res, _) => return res,
+	};
+	let Some(retry_req) = request_headers
+		.reauthorize(url.as_str(), www_authenticate.as_deref(), retry_req)
+		.await?
+	else {
+		return Err(Error::Unauthorized { www_authenticate, body });
+	};
+
+	let res = req_client.execute(retry_req).await?;
+	check_status(res).await
+}
+
+// endregion: --- SseConn
+
+// region:    --- Froms
+
+impl ClientSseTransport {
+	/// A new transport (not started) with the same config (for the reconnect).
+	pub(crate) fn new_from_config(&self) -> Self {
+		Self {
+			config: self.config.clone(),
+		}
+	}
+}
+
+impl From<ClientSseTransportConfig> for ClientSseTransport {
+	fn from(config: ClientSseTransportConfig) -> Self {
+		Self {
+			config: Arc::new(config),
+		}
+	}
+}
+
+// endregion: --- Froms
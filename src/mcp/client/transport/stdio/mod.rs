@@ -1,9 +1,11 @@
 // region:    --- Modules
 
 mod stdio_config;
+mod stdio_stderr;
 mod stdio_transport;
 
 pub use stdio_config::*;
+pub use stdio_stderr::*;
 pub use stdio_transport::*;
 
 // endregion: --- Modules
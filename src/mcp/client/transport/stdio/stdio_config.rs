@@ -1,7 +1,55 @@
+use crate::mcp::client::transport::StdioStderr;
+use std::time::Duration;
+
+/// The default wait of each step of the graceful shutdown (see `ClientStdioTransportConfig::with_shutdown_grace_period`).
+pub const DEFAULT_SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// The environment variables inherited by default when the environment is cleared
+/// (see `ClientStdioTransportConfig::with_env_clear(..)`).
+#[cfg(not(windows))]
+pub const DEFAULT_ENV_ALLOWLIST: &[&str] = &["HOME", "LOGNAME", "PATH", "SHELL", "TERM", "USER"];
+#[cfg(windows)]
+pub const DEFAULT_ENV_ALLOWLIST: &[&str] = &[
+	"APPDATA",
+	"HOMEDRIVE",
+	"HOMEPATH",
+	"LOCALAPPDATA",
+	"PATH",
+	"PATHEXT",
+	"PROCESSOR_ARCHITECTURE",
+	"SYSTEMDRIVE",
+	"SYSTEMROOT",
+	"TEMP",
+	"USERNAME",
+	"USERPROFILE",
+];
+
+/// The config of the stdio transport (the command of the MCP server to spawn).
+///
+/// By default, the child inherits the whole environment of this process, and its stderr lines
+/// are forwarded to tracing.
+///
+/// ```ignore
+/// let config = ClientStdioTransportConfig::new("npx", ["-y", "@modelcontextprotocol/server-everything"], None)
+///     .with_env_clear(DEFAULT_ENV_ALLOWLIST.iter().copied())
+///     .with_env("API_KEY", api_key)
+///     .with_stderr(StdioStderr::Capture(stderr_buffer.clone()));
+/// ```
 pub struct ClientStdioTransportConfig {
 	pub cmd: String,
 	pub args: Vec<String>,
 	pub current_dir: Option<String>,
+
+	/// The environment variables set for the child (after the clear, if any).
+	pub(crate) envs: Vec<(String, String)>,
+	/// When set, the child environment is cleared, but for these inherited variables.
+	pub(crate) env_allowlist: Option<Vec<String>>,
+	/// When true, resolves the command in the child PATH before spawning (logging the resolved path,
+	/// and failing with the searched directories when not found).
+	pub(crate) path_diagnostics: bool,
+	pub(crate) stderr: StdioStderr,
+	/// How long to wait for the exit after closing the stdin, then after the SIGTERM (see `Client::close`).
+	pub(crate) shutdown_grace_period: Duration,
 }
 
 impl ClientStdioTransportConfig {
@@ -14,6 +62,90 @@ impl ClientStdioTransportConfig {
 	{
 		let cmd = cmd.into();
 		let args = args.into_iter().map(Into::into).collect();
-		Self { cmd, args, current_dir }
+		Self {
+			cmd,
+			args,
+			current_dir,
+			envs: Vec::new(),
+			env_allowlist: None,
+			path_diagnostics: false,
+			stderr: StdioStderr::default(),
+			shutdown_grace_period: DEFAULT_SHUTDOWN_GRACE_PERIOD,
+		}
+	}
+}
+
+/// Builders
+impl ClientStdioTransportConfig {
+	/// Sets an environment variable for the child (overriding the inherited one).
+	pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.envs.push((name.into(), value.into()));
+		self
+	}
+
+	pub fn with_envs<I, K, V>(mut self, envs: I) -> Self
+	where
+		I: IntoIterator<Item = (K, V)>,
+		K: Into<String>,
+		V: Into<String>,
+	{
+		self.envs.extend(envs.into_iter().map(|(k, v)| (k.into(), v.into())));
+		self
+	}
+
+	/// Clears the child environment, but for the variables of this allowlist (inherited from this process),
+	/// and the ones set with `with_env(..)`.
+	///
+	/// NOTE: Without `PATH` in the allowlist (or set), the command must be a path.
+	///       See `DEFAULT_ENV_ALLOWLIST` for a sensible allowlist.
+	pub fn with_env_clear<I>(mut self, allowlist: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<String>,
+	{
+		self.env_allowlist = Some(allowlist.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Resolves the command in the child PATH before spawning, without a shell
+	/// (e.g., to diagnose a `npx` not found when the environment is cleared).
+	pub fn with_path_diagnostics(mut self, path_diagnostics: bool) -> Self {
+		self.path_diagnostics = path_diagnostics;
+		self
+	}
+
+	pub fn with_stderr(mut self, stderr: StdioStderr) -> Self {
+		self.stderr = stderr;
+		self
+	}
+
+	/// How long `Client::close` waits for the server exit after closing its stdin,
+	/// then after the SIGTERM, before killing it.
+	pub fn with_shutdown_grace_period(mut self, shutdown_grace_period: Duration) -> Self {
+		self.shutdown_grace_period = shutdown_grace_period;
+		self
+	}
+}
+
+/// Support
+impl ClientStdioTransportConfig {
+	/// The PATH the child will see (the set one, or the inherited one if not cleared out).
+	pub(crate) fn child_path_var(&self) -> Option<String> {
+		if let Some((_, path)) = self.envs.iter().rev().find(|(name, _)| is_path_var(name)) {
+			return Some(path.clone());
+		}
+		let inherited = match self.env_allowlist.as_ref() {
+			Some(allowlist) => allowlist.iter().any(|name| is_path_var(name)),
+			None => true,
+		};
+		if inherited { std::env::var("PATH").ok() } else { None }
+	}
+}
+
+fn is_path_var(name: &str) -> bool {
+	if cfg!(windows) {
+		name.eq_ignore_ascii_case("PATH")
+	} else {
+		name == "PATH"
 	}
 }
@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// What to do with the stderr lines of the MCP server process.
+#[derive(Debug, Clone, Default)]
+pub enum StdioStderr {
+	/// Forwards each line to tracing (at the info level, with the `server_stderr` field).
+	#[default]
+	Tracing,
+	/// Keeps the last lines in this ring buffer (e.g., to show them when the server fails).
+	Capture(StderrRingBuffer),
+	/// Does not pipe the stderr (the child writes to null).
+	Discard,
+}
+
+// region:    --- StderrRingBuffer
+
+/// The last lines of the stderr of the MCP server (the oldest ones being dropped when full).
+///
+/// Clone it before giving it to the config, and read the lines from the clone.
+#[derive(Debug, Clone)]
+pub struct StderrRingBuffer {
+	capacity: usize,
+	lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl StderrRingBuffer {
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+		}
+	}
+}
+
+/// Accessors
+impl StderrRingBuffer {
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Returns the captured lines, oldest first.
+	pub fn lines(&self) -> Vec<String> {
+		self.lock().iter().cloned().collect()
+	}
+
+	pub fn clear(&self) {
+		self.lock().clear();
+	}
+}
+
+/// Support
+impl StderrRingBuffer {
+	pub(crate) fn push(&self, line: String) {
+		if self.capacity == 0 {
+			return;
+		}
+		let mut lines = self.lock();
+		if lines.len() == self.capacity {
+			lines.pop_front();
+		}
+		lines.push_back(line);
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<String>> {
+		self.lines.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+// endregion: --- StderrRingBuffer
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_stderr_ring_buffer_keeps_last_lines() -> Result<()> {
+		// -- Setup & Fixtures
+		let buffer = StderrRingBuffer::new(2);
+		let reader = buffer.clone();
+
+		// -- Exec
+		for line in ["one", "two", "three"] {
+			buffer.push(line.to_string());
+		}
+
+		// -- Check
+		assert_eq!(reader.lines(), vec!["two".to_string(), "three".to_string()]);
+		reader.clear();
+		assert!(buffer.lines().is_empty());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,11 +1,18 @@
 use crate::mcp::client::transport::stdio::stdio_config::ClientStdioTransportConfig;
 use crate::mcp::client::transport::support::StdioHandles;
-use crate::mcp::client::transport::{Error, Result, TransportTrx};
+use crate::mcp::client::transport::{Error, Result, StdioStderr, TransportTrx};
 use crate::mcp::support::truncate;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
 use tokio::process::{ChildStdin, Command};
-use tracing::{debug, error, info};
+use tokio::sync::{oneshot, watch};
+use tracing::{debug, error, info, warn};
+
+/// How long the stdout is read after the server exit (for its last messages).
+const STDOUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
 
 pub struct ClientStdioTransport {
 	config: Arc<ClientStdioTransportConfig>,
@@ -13,61 +20,104 @@ pub struct ClientStdioTransport {
 }
 
 pub struct ClientStdioTransportInner {
-	#[allow(unused)]
-	stdio_handles: StdioHandles,
+	pid: Option<u32>,
+	/// Closes the child stdin (the first step of the graceful shutdown).
+	stdin_close_tx: Mutex<Option<oneshot::Sender<()>>>,
+	/// Kills the child (also when dropped, i.e., when the transport is dropped).
+	kill_tx: Mutex<Option<oneshot::Sender<()>>>,
+	/// Set by the supervisor task when the child exits.
+	exit_status_rx: watch::Receiver<Option<ExitStatus>>,
+	stdio_handles: tokio::sync::Mutex<Option<StdioHandles>>,
 }
 
 /// Lifecycle - start
 impl ClientStdioTransport {
 	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
-		let TransportTrx {
-			c2s_rx,
-			s2c_tx,
-			s2c_aux_tx,
-		} = transport_trx;
+		// NOTE: The stderr is handled as configured (not forwarded to the `s2c_aux_tx`).
+		let TransportTrx { c2s_rx, s2c_tx, .. } = transport_trx;
 
 		// -- Build the command
-		let mut cmd = Command::new(&self.config.cmd);
-		if let Some(ref dir) = self.config.current_dir {
+		let config = &self.config;
+		let mut cmd = Command::new(&config.cmd);
+		if let Some(ref dir) = config.current_dir {
 			cmd.current_dir(dir);
 		}
-		if !self.config.args.is_empty() {
-			cmd.args(&self.config.args);
+		if !config.args.is_empty() {
+			cmd.args(&config.args);
+		}
+		if let Some(allowlist) = config.env_allowlist.as_ref() {
+			cmd.env_clear();
+			for name in allowlist.iter() {
+				if let Some(value) = std::env::var_os(name) {
+					cmd.env(name, value);
+				}
+			}
 		}
+		cmd.envs(config.envs.iter().map(|(k, v)| (k, v)));
+		let stderr = match config.stderr {
+			StdioStderr::Discard => std::process::Stdio::null(),
+			StdioStderr::Tracing | StdioStderr::Capture(_) => std::process::Stdio::piped(),
+		};
 		cmd.stdin(std::process::Stdio::piped()) // Use std pipe for Tokio Command setup
 			.stdout(std::process::Stdio::piped())
-			.stderr(std::process::Stdio::piped())
+			.stderr(stderr)
 			.kill_on_drop(true); // Ensure child is killed if `Child` struct is dropped
 
+		// -- Resolve the command (diagnostics only, the spawn does its own resolution)
+		if config.path_diagnostics {
+			let path_var = config.child_path_var();
+			match resolve_command(&config.cmd, path_var.as_deref()) {
+				Ok(resolved) => info!(cmd = %config.cmd, resolved = %resolved.display(), "stdio command resolved"),
+				Err(searched) => {
+					return Err(Error::CommandNotFound {
+						cmd: config.cmd.clone(),
+						searched: searched.iter().map(|dir| dir.display().to_string()).collect(),
+					});
+				}
+			}
+		}
+
 		// -- Run the command
 
-		let mut child = cmd.spawn().map_err(Error::custom_from_err)?;
+		let mut child = cmd.spawn().map_err(|err| {
+			Error::custom(format!(
+				"Cannot spawn '{}'. Cause: {err}{}",
+				config.cmd,
+				if err.kind() == std::io::ErrorKind::NotFound && !config.path_diagnostics {
+					" (see `with_path_diagnostics(true)` for the searched PATH)"
+				} else {
+					""
+				}
+			))
+		})?;
 
 		let mut child_stdin = child.stdin.take().expect("Failed to get stdin");
 		let child_stdout = child.stdout.take().expect("Failed to get stdout");
-		let child_stderr = child.stderr.take().expect("Failed to get stderr");
+		let child_stderr = child.stderr.take();
 
 		// -- STDERR  (line by line)
-		// Read the child_stderr and send them via stdout_tx to stdout_rx
+		// Forward the child_stderr lines to tracing, or capture them (nothing to read when discarded)
+		let stderr_mode = config.stderr.clone();
 		let stderr_handle = tokio::spawn(async move {
+			let Some(child_stderr) = child_stderr else {
+				return;
+			};
 			let reader = BufReader::new(child_stderr);
 			let mut lines = reader.lines();
 
 			loop {
 				match lines.next_line().await {
-					Ok(Some(line)) => {
-						if let Err(err) = s2c_aux_tx.send(line).await {
-							eprintln!("ERROR while sending stderr line. Cause: {err}");
-							// Decide if the task should terminate on send error
-							break;
-						}
-					}
+					Ok(Some(line)) => match &stderr_mode {
+						StdioStderr::Tracing => info!(server_stderr = %line, "stderr"),
+						StdioStderr::Capture(buffer) => buffer.push(line),
+						StdioStderr::Discard => (),
+					},
 					Ok(None) => {
 						// End of stream
 						break;
 					}
 					Err(e) => {
-						eprintln!("ERROR reading stderr line: {}", e);
+						error!("Error reading stderr line: {}", e);
 						break;
 					}
 				}
@@ -77,7 +127,8 @@ impl ClientStdioTransport {
 
 		// -- STDOUT (line by line)
 		// Read the  child_stdout and send them via stdout_tx to stdout_rx
-		let stdout_handle = tokio::spawn(async move {
+		// NOTE: Ends on the end of stream (i.e., the child exited), which drops the `s2c_tx` (the client gets disconnected).
+		let mut stdout_handle = tokio::spawn(async move {
 			let reader = BufReader::new(child_stdout);
 			let mut lines = reader.lines();
 
@@ -86,11 +137,13 @@ impl ClientStdioTransport {
 					Ok(Some(line)) => {
 						debug!(payload_excerpt = %truncate(&line, 64), "message received");
 						if let Err(err) = s2c_tx.send(line).await {
-							error!(%err, "while sending stdout line");
+							debug!(%err, "Client gone, stop reading stdout");
+							break;
 						}
 					}
 					Ok(None) => {
-						info!("stdout readline nothing .. end ");
+						// End of stream
+						break;
 					}
 					Err(e) => {
 						error!("Error reading stdout line: {}", e);
@@ -102,28 +155,152 @@ impl ClientStdioTransport {
 		});
 
 		// -- STDIN
-		// listen the stdin_rx and forward them to child_stdin
+		// listen the stdin_rx and forward them to child_stdin (until closed by the shutdown)
+		let (stdin_close_tx, mut stdin_close_rx) = oneshot::channel::<()>();
 		let stdin_handle = tokio::spawn(async move {
-			while let Ok(txt) = c2s_rx.recv().await {
+			loop {
+				let txt = tokio::select! {
+					res = c2s_rx.recv() => match res {
+						Ok(txt) => txt,
+						Err(_) => break,
+					},
+					_ = &mut stdin_close_rx => break,
+				};
 				if let Err(err) = send_to_stdin(&mut child_stdin, &txt).await {
 					error!("ERROR sending to stdin. Cause: {err}");
 					// Decide if the task should terminate on send error
 					break;
 				}
 			}
+			// NOTE: Dropping the child_stdin closes it (the server should exit on its end of stream).
 			info!("STDIN Task Ended");
 		});
 
+		// -- SUPERVISOR
+		// Waits for the child exit (or kills it), then lets the stdout drain
+		let pid = child.id();
+		let (kill_tx, kill_rx) = oneshot::channel::<()>();
+		let (exit_status_tx, exit_status_rx) = watch::channel::<Option<ExitStatus>>(None);
+		let supervisor_handle = tokio::spawn(async move {
+			let status = tokio::select! {
+				status = child.wait() => status,
+				// NOTE: Also when the kill_tx is dropped (the transport got dropped).
+				_ = kill_rx => {
+					if let Err(err) = child.start_kill() {
+						error!("Cannot kill the stdio server. Cause: {err}");
+					}
+					child.wait().await
+				}
+			};
+			match status {
+				Ok(status) => {
+					info!(%status, "stdio server exited");
+					exit_status_tx.send_replace(Some(status));
+				}
+				Err(err) => error!("Cannot wait for the stdio server exit. Cause: {err}"),
+			}
+
+			// A grand child (e.g., of `npx`) can keep the stdout open, so the stdout task is ended after the drain timeout.
+			if tokio::time::timeout(STDOUT_DRAIN_TIMEOUT, &mut stdout_handle).await.is_err() {
+				stdout_handle.abort();
+			}
+		});
+
 		// -- Build the ClientTransportController
-		let stdio_handles = StdioHandles::new(child, stdin_handle, stdout_handle, stderr_handle);
-		self.inner = Some(Arc::new(ClientStdioTransportInner { stdio_handles }));
+		let stdio_handles = StdioHandles::new(stdin_handle, stderr_handle, supervisor_handle);
+		self.inner = Some(Arc::new(ClientStdioTransportInner {
+			pid,
+			stdin_close_tx: Mutex::new(Some(stdin_close_tx)),
+			kill_tx: Mutex::new(Some(kill_tx)),
+			exit_status_rx,
+			stdio_handles: tokio::sync::Mutex::new(Some(stdio_handles)),
+		}));
 
 		Ok(())
 	}
 }
 
+/// Lifecycle - close
+impl ClientStdioTransport {
+	/// Shuts down the server process gracefully, and returns its exit status.
+	///
+	/// Closes the stdin, waits for the grace period, sends a SIGTERM (unix only), waits again, then kills it.
+	/// The transport tasks are joined.
+	pub(crate) async fn close(&self) -> Result<Option<ExitStatus>> {
+		let Some(inner) = self.inner.as_ref() else {
+			return Ok(None);
+		};
+		let grace_period = self.config.shutdown_grace_period;
+
+		// -- 1. Close the stdin
+		if let Some(stdin_close_tx) = take_sender(&inner.stdin_close_tx) {
+			debug!("stdio close - closing stdin");
+			let _ = stdin_close_tx.send(());
+		}
+		let mut exit_status = inner.wait_exit_status(grace_period).await;
+
+		// -- 2. SIGTERM
+		#[cfg(unix)]
+		if exit_status.is_none() {
+			if let Some(pid) = inner.pid.and_then(|pid| rustix::process::Pid::from_raw(pid as i32)) {
+				debug!(pid = pid.as_raw_nonzero().get(), "stdio close - sending SIGTERM");
+				if let Err(err) = rustix::process::kill_process(pid, rustix::process::Signal::TERM) {
+					debug!("Cannot send SIGTERM to the stdio server. Cause: {err}");
+				}
+				exit_status = inner.wait_exit_status(grace_period).await;
+			}
+		}
+
+		// -- 3. SIGKILL
+		if exit_status.is_none() {
+			if let Some(kill_tx) = take_sender(&inner.kill_tx) {
+				warn!("stdio server did not exit gracefully, killing it");
+				let _ = kill_tx.send(());
+			}
+			exit_status = inner.wait_exit_status(grace_period).await;
+		}
+
+		// -- Join the tasks
+		if let Some(stdio_handles) = inner.stdio_handles.lock().await.take() {
+			stdio_handles.join(grace_period).await;
+		}
+
+		match exit_status {
+			Some(status) => Ok(Some(status)),
+			None => Err(Error::custom("stdio server did not exit after being killed")),
+		}
+	}
+
+	/// The exit status of the server process, waiting up to the timeout for it (None if still running).
+	pub(crate) async fn exit_status(&self, timeout: Duration) -> Option<ExitStatus> {
+		self.inner.as_ref()?.wait_exit_status(timeout).await
+	}
+}
+
+impl ClientStdioTransportInner {
+	async fn wait_exit_status(&self, timeout: Duration) -> Option<ExitStatus> {
+		let mut exit_status_rx = self.exit_status_rx.clone();
+		let wait = exit_status_rx.wait_for(|status| status.is_some());
+		match tokio::time::timeout(timeout, wait).await {
+			Ok(Ok(status)) => *status,
+			// Timeout, or the supervisor ended without an exit status
+			_ => *self.exit_status_rx.borrow(),
+		}
+	}
+}
+
 // region:    --- Froms
 
+impl ClientStdioTransport {
+	/// A new transport (not started) with the same config (for the reconnect).
+	pub(crate) fn new_from_config(&self) -> Self {
+		Self {
+			config: self.config.clone(),
+			inner: None,
+		}
+	}
+}
+
 impl From<ClientStdioTransportConfig> for ClientStdioTransport {
 	fn from(config: ClientStdioTransportConfig) -> Self {
 		let config = Arc::new(config);
@@ -135,6 +312,10 @@ impl From<ClientStdioTransportConfig> for ClientStdioTransport {
 
 // region:    --- Support
 
+fn take_sender(sender: &Mutex<Option<oneshot::Sender<()>>>) -> Option<oneshot::Sender<()>> {
+	sender.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
 async fn send_to_stdin(child_stdin: &mut ChildStdin, payload: &str) -> Result<()> {
 	debug!(payload_excerpt = %truncate(payload, 64), "sending message");
 
@@ -158,4 +339,139 @@ async fn send_to_stdin(child_stdin: &mut ChildStdin, payload: &str) -> Result<()
 	Ok(())
 }
 
+/// Resolves the command like the spawn would, without a shell (for the diagnostics).
+///
+/// Returns the searched directories when not found.
+fn resolve_command(cmd: &str, path_var: Option<&str>) -> core::result::Result<PathBuf, Vec<PathBuf>> {
+	let cmd_path = Path::new(cmd);
+	// A command with a path is not searched in the PATH.
+	if cmd_path.components().count() > 1 {
+		return find_executable(cmd_path).ok_or_else(Vec::new);
+	}
+
+	let dirs: Vec<PathBuf> = path_var.map(|path| std::env::split_paths(path).collect()).unwrap_or_default();
+	for dir in dirs.iter() {
+		if let Some(resolved) = find_executable(&dir.join(cmd)) {
+			return Ok(resolved);
+		}
+	}
+	Err(dirs)
+}
+
+/// Returns the executable file of this path (trying the `PATHEXT` extensions on Windows).
+fn find_executable(path: &Path) -> Option<PathBuf> {
+	if cfg!(windows) && path.extension().is_none() {
+		let exts = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+		return exts
+			.split(';')
+			.filter(|ext| !ext.is_empty())
+			.map(|ext| path.with_extension(ext.trim_start_matches('.')))
+			.find(|candidate| is_executable(candidate));
+	}
+	is_executable(path).then(|| path.to_path_buf())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+	use std::os::unix::fs::PermissionsExt as _;
+	path.metadata()
+		.is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+	path.is_file()
+}
+
 // endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mcp::client::transport::StderrRingBuffer;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_stdio_close_graceful_then_kill() -> Result<()> {
+		// -- Setup & Fixtures
+		let grace_period = Duration::from_millis(200);
+		// exits on the stdin end of stream
+		let graceful = ClientStdioTransportConfig::new("cat", Vec::<String>::new(), None);
+		// ignores the stdin end of stream and the SIGTERM
+		let stubborn =
+			ClientStdioTransportConfig::new("sh", ["-c", "trap '' TERM; cat; while :; do sleep 1; done"], None);
+
+		for (config, expected_code) in [(graceful, Some(0)), (stubborn, None)] {
+			let mut transport = ClientStdioTransport::from(config.with_shutdown_grace_period(grace_period));
+			let (_client_trx, transport_trx) = crate::mcp::client::transport::new_trx_pair();
+			transport.start(transport_trx).await?;
+
+			// -- Exec
+			let status = transport.close().await?.ok_or("should have an exit status")?;
+
+			// -- Check
+			assert_eq!(status.code(), expected_code);
+		}
+
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_stdio_env_set_allowlisted_and_cleared() -> Result<()> {
+		// -- Setup & Fixtures
+		// NOTE: `CARGO_MANIFEST_DIR` is set by cargo for this test process (so, inherited unless cleared).
+		let script = r#"echo "$A:$HOME:$CARGO_MANIFEST_DIR" >&2"#;
+		let home = std::env::var("HOME").unwrap_or_default();
+		let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")?;
+		let inherited = ClientStdioTransportConfig::new("sh", ["-c", script], None).with_env("A", "set");
+		let cleared = ClientStdioTransportConfig::new("sh", ["-c", script], None)
+			.with_env("A", "set")
+			.with_env_clear(["HOME"]);
+
+		for (config, expected) in [
+			(inherited, format!("set:{home}:{manifest_dir}")),
+			(cleared, format!("set:{home}:")),
+		] {
+			let stderr = StderrRingBuffer::new(10);
+			let config = config.with_stderr(StdioStderr::Capture(stderr.clone()));
+			let mut transport = ClientStdioTransport::from(config);
+			let (_client_trx, transport_trx) = crate::mcp::client::transport::new_trx_pair();
+
+			// -- Exec
+			transport.start(transport_trx).await?;
+			let status = transport.close().await?.ok_or("should have an exit status")?;
+
+			// -- Check
+			assert_eq!(status.code(), Some(0));
+			assert_eq!(stderr.lines(), [expected]);
+		}
+
+		Ok(())
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_stdio_resolve_command() -> Result<()> {
+		// -- Setup & Fixtures
+		let path_var = "/nonexistent-dir:/bin:/usr/bin";
+
+		// -- Exec
+		let resolved = resolve_command("sh", Some(path_var)).map_err(|_| "sh not found")?;
+		let not_found = resolve_command("surely-not-a-command", Some(path_var));
+		let no_path = resolve_command("sh", None);
+
+		// -- Check
+		assert!(resolved.ends_with("sh"));
+		assert_eq!(not_found.err().map(|dirs| dirs.len()), Some(3));
+		assert_eq!(no_path.err(), Some(Vec::new()));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
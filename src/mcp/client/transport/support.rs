@@ -1,24 +1,142 @@
-use tokio::process::Child;
+use crate::RpcId;
+use crate::mcp::client::transport::{Error, RequestTransportError, Result};
+use crate::mcp::transport::CommTx;
+use reqwest::header::{CONTENT_TYPE, WWW_AUTHENTICATE};
+use reqwest::{Response, StatusCode};
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tokio::task::JoinHandle;
+use tracing::{error, warn};
 
 // region:    --- StdioHandles
 
-#[allow(unused)]
+/// The tasks of the stdio transport (the stdout task being owned by the supervisor).
 pub(super) struct StdioHandles {
-	child: Child,
 	stdin: JoinHandle<()>,
-	stdout: JoinHandle<()>,
 	stderr: JoinHandle<()>,
+	supervisor: JoinHandle<()>,
 }
 
 impl StdioHandles {
-	pub fn new(child: Child, stdin: JoinHandle<()>, stdout: JoinHandle<()>, stderr: JoinHandle<()>) -> Self {
+	pub fn new(stdin: JoinHandle<()>, stderr: JoinHandle<()>, supervisor: JoinHandle<()>) -> Self {
 		Self {
-			child,
 			stdin,
-			stdout,
 			stderr,
+			supervisor,
+		}
+	}
+
+	/// Joins the tasks, aborting the ones not ended within the timeout.
+	pub async fn join(self, timeout: Duration) {
+		for (name, mut handle) in [("stdin", self.stdin), ("stderr", self.stderr), ("supervisor", self.supervisor)] {
+			if tokio::time::timeout(timeout, &mut handle).await.is_err() {
+				warn!(task = name, "stdio task not ended, aborting it");
+				handle.abort();
+			}
 		}
 	}
 }
 // endregion: --- StdioHandles
+
+// region:    --- Http Support
+
+/// The JSON-RPC info of a client message needed by the transport.
+pub(super) struct MessageInfo {
+	pub id: Option<Value>,
+	pub method: Option<String>,
+}
+
+impl MessageInfo {
+	pub fn from_msg(msg: &str) -> Self {
+		let value = serde_json::from_str::<Value>(msg).unwrap_or_default();
+		Self {
+			id: value.get("id").cloned(),
+			method: value.get("method").and_then(Value::as_str).map(String::from),
+		}
+	}
+
+	/// The id of the message when a request (responses to the server requests have an id, but no method).
+	pub fn request_id(&self) -> Option<Value> {
+		self.method.as_ref().and(self.id.clone())
+	}
+}
+
+/// Maps the non successful (non 2xx) responses to `Error::HttpStatus` (or `Error::Unauthorized` for a 401).
+pub(super) async fn check_status(res: Response) -> Result<Response> {
+	let status = res.status();
+	if status.is_success() {
+		return Ok(res);
+	}
+	let www_authenticate = res
+		.headers()
+		.get(WWW_AUTHENTICATE)
+		.and_then(|v| v.to_str().ok())
+		.map(String::from);
+	let body = res.text().await.unwrap_or_default();
+	if status == StatusCode::UNAUTHORIZED {
+		return Err(Error::Unauthorized { www_authenticate, body });
+	}
+	Err(Error::HttpStatus {
+		status: status.as_u16(),
+		body,
+	})
+}
+
+/// The mime type of the response content type (without the parameters, like `charset`).
+pub(super) fn content_type(res: &Response) -> Option<String> {
+	let content_type = res.headers().get(CONTENT_TYPE)?.to_str().ok()?;
+	let mime = content_type.split(';').next().unwrap_or_default().trim();
+	Some(mime.to_ascii_lowercase())
+}
+
+/// Reports a message which failed at the transport level to the client.
+///
+/// The error is sent to the aux channel, and a request fails with it (so that the caller does not wait for the timeout).
+pub(super) async fn report_transport_error(
+	req_err_tx: &flume::Sender<RequestTransportError>,
+	s2c_aux_tx: &CommTx,
+	info: &MessageInfo,
+	err: Error,
+) {
+	error!(method = ?info.method, "MCP HTTP transport error. Cause: {err}");
+
+	let _ = s2c_aux_tx.send(format!("HTTP transport error: {err}")).await;
+
+	let Some(rpc_id) = info.request_id().and_then(|id| RpcId::from_value(id).ok()) else {
+		return;
+	};
+	if req_err_tx.send(RequestTransportError { rpc_id, error: err }).is_err() {
+		error!("while sending the transport error of the request to req_err_tx.");
+	}
+}
+
+/// The `s2c_tx` shared by the tasks of an HTTP connection.
+///
+/// Taken when the legacy SSE stream ends, so that the last `s2c_tx` gets dropped,
+/// and the client sees the transport gone (disconnected).
+#[derive(Clone)]
+pub(crate) struct SharedS2cTx(Arc<RwLock<Option<CommTx>>>);
+
+impl SharedS2cTx {
+	pub fn new(s2c_tx: CommTx) -> Self {
+		Self(Arc::new(RwLock::new(Some(s2c_tx))))
+	}
+
+	pub async fn send(&self, msg: impl Into<String>) -> Result<()> {
+		// NOTE: Cloned, so that the lock is not held across the await.
+		let s2c_tx = self.0.read().unwrap_or_else(|e| e.into_inner()).clone();
+		let Some(s2c_tx) = s2c_tx else {
+			return Err(Error::custom("Transport disconnected"));
+		};
+		s2c_tx.send(msg).await?;
+		Ok(())
+	}
+
+	/// Drops the shared `s2c_tx` (the messages sent afterward fail).
+	pub fn take(&self) {
+		self.0.write().unwrap_or_else(|e| e.into_inner()).take();
+	}
+}
+
+// endregion: --- Http Support
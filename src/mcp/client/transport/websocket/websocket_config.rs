@@ -0,0 +1,145 @@
+use crate::mcp::client::transport::{BearerTokenProviderAsyncFn, Error, IntoBearerTokenProviderAsyncFn, Result};
+use crate::mcp::transport::WsKeepalive;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest as _;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+
+/// The config of the WebSocket transport (the url, e.g., `wss://mcp.example.com/ws`).
+///
+/// Each MCP message is a text frame. Pings are sent every `ping_interval`, and the connection is
+/// considered lost when no frame is received within the `pong_timeout` after a ping.
+///
+/// ```ignore
+/// let config = ClientWebSocketTransportConfig::new("wss://mcp.example.com/ws")
+///     .with_header("x-api-version", "2")
+///     .with_bearer_token(api_key)
+///     .with_ping_interval(Some(Duration::from_secs(15)));
+/// ```
+pub struct ClientWebSocketTransportConfig {
+	pub url: String,
+
+	/// Static headers, sent with the handshake request.
+	pub(crate) headers: Vec<(String, String)>,
+	/// Called before each connect for the `Authorization: Bearer <token>` header.
+	pub(crate) bearer_token_provider: Option<Arc<Box<dyn BearerTokenProviderAsyncFn>>>,
+	pub(crate) connect_timeout: Option<Duration>,
+	pub(crate) keepalive: WsKeepalive,
+}
+
+impl ClientWebSocketTransportConfig {
+	pub fn new(url: impl Into<String>) -> Self {
+		Self {
+			url: url.into(),
+			headers: Vec::new(),
+			bearer_token_provider: None,
+			connect_timeout: None,
+			keepalive: WsKeepalive::default(),
+		}
+	}
+}
+
+/// Builders
+impl ClientWebSocketTransportConfig {
+	/// Adds a static header, sent with the handshake request (validated on connect).
+	pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.headers.push((name.into(), value.into()));
+		self
+	}
+
+	/// Sets the bearer token provider, called before each connect (and reconnect).
+	pub fn with_bearer_token_provider(mut self, provider: impl IntoBearerTokenProviderAsyncFn) -> Self {
+		self.bearer_token_provider = Some(provider.into_bearer_token_provider());
+		self
+	}
+
+	/// Sets a static bearer token (for a token which does not expire, e.g., an API key).
+	pub fn with_bearer_token(self, token: impl Into<String>) -> Self {
+		let token = token.into();
+		self.with_bearer_token_provider(move || async move { Ok(token) })
+	}
+
+	/// The timeout of the connection, including the WebSocket handshake.
+	pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+		self.connect_timeout = Some(connect_timeout);
+		self
+	}
+
+	/// The interval of the keepalive pings (default `DEFAULT_WEBSOCKET_PING_INTERVAL`), None for no pings.
+	pub fn with_ping_interval(mut self, ping_interval: Option<Duration>) -> Self {
+		self.keepalive.ping_interval = ping_interval;
+		self
+	}
+
+	/// How long to wait for a frame after a ping (default `DEFAULT_WEBSOCKET_PONG_TIMEOUT`).
+	pub fn with_pong_timeout(mut self, pong_timeout: Duration) -> Self {
+		self.keepalive.pong_timeout = pong_timeout;
+		self
+	}
+}
+
+/// Builds
+impl ClientWebSocketTransportConfig {
+	/// Builds the handshake request (validating the static headers, and calling the bearer token provider).
+	pub(crate) async fn build_request(&self) -> Result<Request> {
+		let mut request = self.url.as_str().into_client_request()?;
+		let headers = request.headers_mut();
+		for (name, value) in self.headers.iter() {
+			let name = HeaderName::try_from(name.as_str())
+				.map_err(|err| Error::custom(format!("Invalid header name '{name}'. Cause: {err}")))?;
+			let value = HeaderValue::try_from(value.as_str())
+				.map_err(|err| Error::custom(format!("Invalid header value for '{name}'. Cause: {err}")))?;
+			headers.append(name, value);
+		}
+
+		if let Some(bearer_token_provider) = self.bearer_token_provider.as_ref() {
+			let token = bearer_token_provider
+				.exec_fn()
+				.await
+				.map_err(|err| Error::custom(format!("Bearer token provider failed. Cause: {err}")))?;
+			let value = HeaderValue::try_from(format!("Bearer {token}"))
+				.map_err(|err| Error::custom(format!("Invalid bearer token. Cause: {err}")))?;
+			headers.insert(AUTHORIZATION, value);
+		}
+
+		Ok(request)
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_websocket_config_build_request() -> Result<()> {
+		// -- Setup & Fixtures
+		let config = ClientWebSocketTransportConfig::new("ws://localhost:3001/ws")
+			.with_header("x-tenant", "acme")
+			.with_bearer_token("token-123");
+
+		// -- Exec
+		let request = config.build_request().await?;
+
+		// -- Check
+		assert_eq!(request.uri().to_string(), "ws://localhost:3001/ws");
+		assert_eq!(
+			request.headers().get("x-tenant").map(|v| v.as_bytes()),
+			Some(&b"acme"[..])
+		);
+		assert_eq!(
+			request.headers().get(AUTHORIZATION).map(|v| v.as_bytes()),
+			Some(&b"Bearer token-123"[..])
+		);
+		assert!(request.headers().contains_key("sec-websocket-key"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,106 @@
+use crate::mcp::client::transport::{ClientWebSocketTransportConfig, Error, Result, TransportTrx};
+use crate::mcp::transport::run_ws_conn;
+use std::sync::{Arc, Mutex};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::WWW_AUTHENTICATE;
+use tracing::debug;
+
+/// The WebSocket transport (client side), each MCP message being a text frame.
+pub struct ClientWebSocketTransport {
+	config: Arc<ClientWebSocketTransportConfig>,
+	/// The error the connection ended with (e.g., the close code of the server), for the disconnect reason.
+	closed_error: Arc<Mutex<Option<String>>>,
+}
+
+/// Lifecyle - start
+impl ClientWebSocketTransport {
+	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
+		let TransportTrx {
+			c2s_rx,
+			s2c_tx,
+			s2c_aux_tx,
+			..
+		} = transport_trx;
+
+		// -- Connect (with the handshake)
+		let request = self.config.build_request().await?;
+		let connect = tokio_tungstenite::connect_async(request);
+		let connect_res = match self.config.connect_timeout {
+			Some(connect_timeout) => tokio::time::timeout(connect_timeout, connect)
+				.await
+				.map_err(|_| Error::custom(format!("WebSocket connect timed out after {connect_timeout:?}")))?,
+			None => connect.await,
+		};
+		let (ws, _) = connect_res.map_err(handshake_error)?;
+
+		// -- Run the connection
+		let keepalive = self.config.keepalive;
+		let closed_error = self.closed_error.clone();
+		tokio::spawn(async move {
+			match run_ws_conn(ws, &c2s_rx, &s2c_tx, keepalive).await {
+				Some(err) => {
+					let err = err.to_string();
+					*closed_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(err.clone());
+					let _ = s2c_aux_tx.send(format!("WebSocket transport error: {err}")).await;
+				}
+				None => debug!("WebSocket connection closed"),
+			}
+			// NOTE: Dropped after the closed error is set, as it disconnects the client.
+			drop(s2c_tx);
+		});
+
+		Ok(())
+	}
+
+	/// A new transport (not started) with the same config, to reconnect.
+	pub(crate) fn new_from_config(&self) -> Self {
+		Self::from_config(self.config.clone())
+	}
+
+	/// The error the connection ended with (None if still open, or closed normally).
+	pub(crate) fn closed_error(&self) -> Option<String> {
+		self.closed_error.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	fn from_config(config: Arc<ClientWebSocketTransportConfig>) -> Self {
+		Self {
+			config,
+			closed_error: Default::default(),
+		}
+	}
+}
+
+impl From<ClientWebSocketTransportConfig> for ClientWebSocketTransport {
+	fn from(config: ClientWebSocketTransportConfig) -> Self {
+		Self::from_config(Arc::new(config))
+	}
+}
+
+// region:    --- Support
+
+/// Maps the non 101 handshake responses to the HTTP errors (e.g., a 401 to `Error::Unauthorized`).
+fn handshake_error(err: tokio_tungstenite::tungstenite::Error) -> Error {
+	let tokio_tungstenite::tungstenite::Error::Http(response) = err else {
+		return err.into();
+	};
+	let body = response
+		.body()
+		.as_ref()
+		.map(|body| String::from_utf8_lossy(body).to_string())
+		.unwrap_or_default();
+	if response.status() == StatusCode::UNAUTHORIZED {
+		let www_authenticate = response
+			.headers()
+			.get(WWW_AUTHENTICATE)
+			.and_then(|value| value.to_str().ok())
+			.map(str::to_string);
+		Error::Unauthorized { www_authenticate, body }
+	} else {
+		Error::HttpStatus {
+			status: response.status().as_u16(),
+			body,
+		}
+	}
+}
+
+// endregion: --- Support
@@ -1,4 +1,4 @@
-use crate::mcp::{GenericMeta, IntoMcpNotification, IntoMcpRequest, McpNotification, RequestMeta};
+use crate::mcp::{EmptyResult, GenericMeta, IntoMcpNotification, IntoMcpRequest, McpNotification, RequestMeta};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -50,13 +50,9 @@ impl SetLevelParams {
 
 impl IntoMcpRequest<SetLevelParams> for SetLevelParams {
 	const METHOD: &'static str = "logging/setLevel";
-	type McpResult = ();
+	type McpResult = EmptyResult;
 }
 
-// Note: The result for SetLevelRequest is `EmptyResult`, which translates to a standard JSON-RPC success response
-// with an empty `result` object (potentially containing only `_meta`).
-// No specific struct is needed for `EmptyResult` itself beyond standard result handling.
-
 // endregion: --- SetLevelRequest
 
 // region:    --- LoggingMessageNotification
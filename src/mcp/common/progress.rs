@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 /// A progress token, used to associate progress notifications with the original request.
 ///
 /// TS Ref: `ProgressToken`
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, From)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, From)]
 #[serde(untagged)]
 pub enum ProgressToken {
 	#[from(String, &str, &String)]
@@ -32,12 +32,10 @@ pub struct ProgressNotificationParams {
 	pub progress_token: ProgressToken,
 
 	/// The progress thus far. This should increase every time progress is made, even if the total is unknown.
-	/// NOTE: For now, integer only
-	pub progress: i64,
+	pub progress: f64,
 
 	/// Total number of items to process (or total progress required), if known.
-	/// NOTE: For now, integer only
-	pub total: Option<i64>,
+	pub total: Option<f64>,
 
 	/// An optional message describing the current progress.
 	pub message: Option<String>,
@@ -45,7 +43,7 @@ pub struct ProgressNotificationParams {
 
 /// Builders
 impl ProgressNotificationParams {
-	pub fn new(progress_token: impl Into<ProgressToken>, progress: i64) -> Self {
+	pub fn new(progress_token: impl Into<ProgressToken>, progress: f64) -> Self {
 		Self {
 			meta: None,
 			progress_token: progress_token.into(),
@@ -60,7 +58,7 @@ impl ProgressNotificationParams {
 		self
 	}
 
-	pub fn with_total(mut self, total: i64) -> Self {
+	pub fn with_total(mut self, total: f64) -> Self {
 		self.total = Some(total);
 		self
 	}
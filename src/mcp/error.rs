@@ -1,5 +1,8 @@
+use crate::RpcId;
 use crate::mcp::McpError;
+use crate::mcp::client::ClientState;
 use derive_more::{Display, From};
+use rpc_router::RpcError;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -12,6 +15,10 @@ pub enum Error {
 	#[from]
 	McpError(McpError),
 
+	/// A JSON-RPC error to be sent back as is to the peer (the responder adds the request id).
+	#[from]
+	Rpc(RpcError),
+
 	// -- McpMessage Errors
 	McpMessageNotAnObject,
 	McpMessageInvalidStructure(String),
@@ -25,6 +32,51 @@ pub enum Error {
 		target_type: &'static str,
 	},
 
+	// -- Protocol Version
+	ProtocolVersionUnknown(String),
+	/// The server answered with a protocol version the client does not support (the client disconnects).
+	ProtocolVersionMismatch {
+		server_version: String,
+		supported_versions: Vec<String>,
+	},
+
+	// -- Client
+	/// The request cannot be sent in the current client lifecycle state
+	/// (e.g., before `connect` completed, or `initialize` sent twice).
+	ClientNotReady {
+		method: String,
+		state: ClientState,
+	},
+	ClientAlreadyConnected {
+		state: ClientState,
+	},
+	/// The connection got closed before the response (e.g., the stdio server process exited, or `Client::close`).
+	ClientDisconnected {
+		reason: String,
+	},
+	/// The request was cancelled before its response (see `Client::cancel_request`).
+	RequestCancelled {
+		rpc_id: RpcId,
+		method: String,
+	},
+	/// No response within the request timeout (the request got cancelled).
+	Timeout {
+		rpc_id: RpcId,
+		method: String,
+	},
+	/// The request failed at the transport level, the `cause` telling why
+	/// (e.g., `transport::Error::HttpStatus` for a 5xx, or `transport::Error::Reqwest` for a connection refused).
+	TransportRequest {
+		rpc_id: RpcId,
+		method: String,
+		cause: crate::mcp::client::transport::Error,
+	},
+	/// The user rejected the request (e.g., returned by a sampling handler).
+	/// Sent back to the server as a JSON-RPC error with the code `Error::CODE_USER_REJECTED`.
+	UserRejected {
+		reason: Option<String>,
+	},
+
 	// -- Sub Modules
 	Transport(String),
 }
@@ -43,6 +95,53 @@ impl Error {
 
 // endregion: --- Custom
 
+// region:    --- RpcError
+
+impl Error {
+	/// MCP error code for a `resources/read` of an unknown resource.
+	pub const CODE_RESOURCE_NOT_FOUND: i64 = -32002;
+
+	/// The JSON-RPC error for a `resources/read` of an unknown resource.
+	pub fn resource_not_found(uri: impl Into<String>) -> Self {
+		let uri = uri.into();
+		Error::Rpc(RpcError {
+			code: Self::CODE_RESOURCE_NOT_FOUND,
+			message: format!("Resource not found: {uri}"),
+			data: Some(serde_json::json!({ "uri": uri })),
+		})
+	}
+
+	/// MCP error code for a request rejected by the user (e.g., `sampling/createMessage`).
+	pub const CODE_USER_REJECTED: i64 = -1;
+
+	/// The error to return when the user rejected the request (e.g., from a sampling handler).
+	pub fn user_rejected(reason: impl Into<String>) -> Self {
+		Error::UserRejected {
+			reason: Some(reason.into()),
+		}
+	}
+
+	/// Converts this error into the JSON-RPC error to be sent back to the peer.
+	///
+	/// `Rpc` and `McpError` keep their code and message, `UserRejected` gets `CODE_USER_REJECTED`,
+	/// and any other error becomes an internal error.
+	pub fn into_rpc_error(self) -> RpcError {
+		match self {
+			Error::Rpc(rpc_error) => rpc_error,
+			Error::McpError(mcp_error) => mcp_error.error,
+			Error::UserRejected { reason } => RpcError {
+				code: Self::CODE_USER_REJECTED,
+				message: "User rejected the request".to_string(),
+				data: reason.map(|reason| serde_json::json!({ "reason": reason })),
+			},
+			Error::Custom(message) => RpcError::from_internal_error(Some(message.into())),
+			other => RpcError::from_internal_error(Some(other.to_string().into())),
+		}
+	}
+}
+
+// endregion: --- RpcError
+
 // region:    --- Error Boilerplate
 
 impl std::error::Error for Error {}
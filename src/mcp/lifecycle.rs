@@ -1,4 +1,6 @@
-use crate::mcp::{ClientCapabilities, GenericMeta, Implementation, IntoMcpRequest, RequestMeta, ServerCapabilities};
+use crate::mcp::{
+	ClientCapabilities, EmptyResult, GenericMeta, Implementation, IntoMcpRequest, RequestMeta, ServerCapabilities,
+};
 use serde::{Deserialize, Serialize};
 
 // region:    --- InitializeRequest
@@ -90,6 +92,33 @@ pub struct InitializeResult {
 	pub instructions: Option<String>,
 }
 
+/// Builders
+impl InitializeResult {
+	pub fn new(
+		protocol_version: impl Into<String>,
+		capabilities: ServerCapabilities,
+		server_info: Implementation,
+	) -> Self {
+		Self {
+			meta: None,
+			protocol_version: protocol_version.into(),
+			capabilities,
+			server_info,
+			instructions: None,
+		}
+	}
+
+	pub fn with_meta(mut self, meta: GenericMeta) -> Self {
+		self.meta = Some(meta);
+		self
+	}
+
+	pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+		self.instructions = Some(instructions.into());
+		self
+	}
+}
+
 // endregion: --- InitializeRequest
 
 // region:    --- PingRequest
@@ -111,11 +140,10 @@ pub struct PingParams {
 
 impl IntoMcpRequest<PingParams> for PingParams {
 	const METHOD: &'static str = "ping";
-	type McpResult = ();
+	type McpResult = EmptyResult;
 }
 
 // Note: The result for PingRequest is `EmptyResult`, which translates to a standard JSON-RPC success response
 // with an empty `result` object (potentially containing only `_meta`).
-// No specific struct is needed for `EmptyResult` itself beyond standard result handling.
 
 // endregion: --- PingRequest
@@ -12,6 +12,10 @@ pub struct McpError {
 }
 
 impl McpError {
+	pub fn new(id: impl Into<RpcId>, error: RpcError) -> Self {
+		Self { id: id.into(), error }
+	}
+
 	pub fn stringify(&self) -> Result<String> {
 		serde_json::to_string(&self).map_err(Error::custom_from_err)
 	}
@@ -9,6 +9,7 @@ mod lifecycle;
 mod messages;
 mod notifications;
 mod prompts;
+mod protocol_version;
 mod resources;
 mod roots;
 mod sampling;
@@ -21,13 +22,19 @@ pub use lifecycle::*;
 pub use messages::*;
 pub use notifications::*;
 pub use prompts::*;
+pub use protocol_version::*;
 pub use resources::*;
 pub use roots::*;
 pub use sampling::*;
 pub use tools::*;
 
 pub mod client;
+pub mod server;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transport;
 
 // endregion: --- Modules
 
+/// The protocol version modeled by the types of this crate (the one requested by default).
 pub const LATEST_PROTOCOL_VERSION: &str = "2025-03-26";
@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 /// TS Ref: `CancelledNotification`
 #[serde_with::skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CancelledNotificationParams {
 	#[serde(rename = "_meta")]
 	pub meta: Option<GenericMeta>,
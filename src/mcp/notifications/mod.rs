@@ -2,8 +2,10 @@
 
 mod data_change;
 mod lifecycle;
+mod server_notification;
 
 pub use data_change::*;
 pub use lifecycle::*;
+pub use server_notification::ServerNotification;
 
 // endregion: --- Modules
@@ -0,0 +1,138 @@
+use crate::mcp::{
+	CancelledNotificationParams, IntoMcpNotification, LoggingMessageNotificationParams, McpNotification,
+	ProgressNotificationParams, PromptListChangedNotificationParams, ResourceListChangedNotificationParams,
+	ResourceUpdatedNotificationParams, ToolListChangedNotificationParams,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::warn;
+
+/// A notification sent from the server to the client, typed by method.
+///
+/// Unknown methods (or params failing to deserialize) are kept as `Other` with the raw notification.
+#[derive(Debug, Clone)]
+pub enum ServerNotification {
+	LoggingMessage(LoggingMessageNotificationParams),
+	Progress(ProgressNotificationParams),
+	ResourceUpdated(ResourceUpdatedNotificationParams),
+	ResourceListChanged(ResourceListChangedNotificationParams),
+	ToolListChanged(ToolListChangedNotificationParams),
+	PromptListChanged(PromptListChangedNotificationParams),
+	Cancelled(CancelledNotificationParams),
+	Other(McpNotification<Value>),
+}
+
+impl ServerNotification {
+	/// The methods of the typed variants (i.e., all except `Other`).
+	pub const KNOWN_METHODS: &'static [&'static str] = &[
+		LoggingMessageNotificationParams::METHOD,
+		ProgressNotificationParams::METHOD,
+		ResourceUpdatedNotificationParams::METHOD,
+		ResourceListChangedNotificationParams::METHOD,
+		ToolListChangedNotificationParams::METHOD,
+		PromptListChangedNotificationParams::METHOD,
+		CancelledNotificationParams::METHOD,
+	];
+
+	pub fn method(&self) -> &str {
+		match self {
+			ServerNotification::LoggingMessage(_) => LoggingMessageNotificationParams::METHOD,
+			ServerNotification::Progress(_) => ProgressNotificationParams::METHOD,
+			ServerNotification::ResourceUpdated(_) => ResourceUpdatedNotificationParams::METHOD,
+			ServerNotification::ResourceListChanged(_) => ResourceListChangedNotificationParams::METHOD,
+			ServerNotification::ToolListChanged(_) => ToolListChangedNotificationParams::METHOD,
+			ServerNotification::PromptListChanged(_) => PromptListChangedNotificationParams::METHOD,
+			ServerNotification::Cancelled(_) => CancelledNotificationParams::METHOD,
+			ServerNotification::Other(notification) => &notification.method,
+		}
+	}
+
+	pub fn is_known_method(method: &str) -> bool {
+		Self::KNOWN_METHODS.contains(&method)
+	}
+
+	/// Deserializes the notification params (missing params are treated as an empty object).
+	///
+	/// Returns None (with a warning) when the params do not match the type.
+	pub(crate) fn parse_params<P: DeserializeOwned>(notification: &McpNotification<Value>) -> Option<P> {
+		let params = notification.params.clone().unwrap_or_else(|| Value::Object(Default::default()));
+		match serde_json::from_value(params) {
+			Ok(params) => Some(params),
+			Err(err) => {
+				warn!(method = %notification.method, "Invalid notification params. Cause: {err}");
+				None
+			}
+		}
+	}
+}
+
+// region:    --- Froms
+
+impl From<McpNotification<Value>> for ServerNotification {
+	fn from(notification: McpNotification<Value>) -> Self {
+		let res = match notification.method.as_str() {
+			LoggingMessageNotificationParams::METHOD => Self::parse_params(&notification).map(Self::LoggingMessage),
+			ProgressNotificationParams::METHOD => Self::parse_params(&notification).map(Self::Progress),
+			ResourceUpdatedNotificationParams::METHOD => Self::parse_params(&notification).map(Self::ResourceUpdated),
+			ResourceListChangedNotificationParams::METHOD => {
+				Self::parse_params(&notification).map(Self::ResourceListChanged)
+			}
+			ToolListChangedNotificationParams::METHOD => Self::parse_params(&notification).map(Self::ToolListChanged),
+			PromptListChangedNotificationParams::METHOD => {
+				Self::parse_params(&notification).map(Self::PromptListChanged)
+			}
+			CancelledNotificationParams::METHOD => Self::parse_params(&notification).map(Self::Cancelled),
+			_ => None,
+		};
+		res.unwrap_or(ServerNotification::Other(notification))
+	}
+}
+
+// endregion: --- Froms
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_server_notification_from_mcp_notification() -> Result<()> {
+		// -- Setup & Fixtures
+		let cancelled: McpNotification<Value> = serde_json::from_value(json!({
+			"jsonrpc": "2.0",
+			"method": "notifications/cancelled",
+			"params": {"requestId": 12, "reason": "Too slow"}
+		}))?;
+		let tools_changed: McpNotification<Value> = serde_json::from_value(json!({
+			"jsonrpc": "2.0",
+			"method": "notifications/tools/list_changed"
+		}))?;
+		let unknown: McpNotification<Value> = serde_json::from_value(json!({
+			"jsonrpc": "2.0",
+			"method": "notifications/custom",
+			"params": {"some": "value"}
+		}))?;
+
+		// -- Exec
+		let cancelled = ServerNotification::from(cancelled);
+		let tools_changed = ServerNotification::from(tools_changed);
+		let unknown = ServerNotification::from(unknown);
+
+		// -- Check
+		let ServerNotification::Cancelled(cancelled) = cancelled else {
+			return Err("Should be Cancelled".into());
+		};
+		assert_eq!(cancelled.reason.as_deref(), Some("Too slow"));
+		assert!(matches!(tools_changed, ServerNotification::ToolListChanged(_)));
+		assert_eq!(unknown.method(), "notifications/custom");
+		assert!(matches!(unknown, ServerNotification::Other(_)));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -139,4 +139,30 @@ pub struct GetPromptResult {
 	pub messages: Vec<PromptMessage>,
 }
 
+/// Builders
+impl GetPromptResult {
+	pub fn new(messages: Vec<PromptMessage>) -> Self {
+		Self {
+			meta: None,
+			description: None,
+			messages,
+		}
+	}
+
+	pub fn with_meta(mut self, meta: GenericMeta) -> Self {
+		self.meta = Some(meta);
+		self
+	}
+
+	pub fn with_description(mut self, description: impl Into<String>) -> Self {
+		self.description = Some(description.into());
+		self
+	}
+
+	pub fn append_message(mut self, message: PromptMessage) -> Self {
+		self.messages.push(message);
+		self
+	}
+}
+
 // endregion: --- GetPromptRequest
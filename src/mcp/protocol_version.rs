@@ -0,0 +1,90 @@
+use crate::mcp::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// The versions supported by default (client and server), the first one being the preferred one.
+///
+/// NOTE: `2025-06-18` is known (see `ProtocolVersion`), but not enabled by default, since the types
+///       of this crate model the `2025-03-26` spec. It can be enabled with `Client::set_protocol_versions`.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[ProtocolVersion] =
+	&[ProtocolVersion::V2025_03_26, ProtocolVersion::V2024_11_05];
+
+/// A revision of the MCP spec, identified by its date (e.g., `2025-03-26`).
+///
+/// Used for the version negotiation (`InitializeParams.protocol_version` / `InitializeResult.protocol_version`)
+/// and to gate the version-specific behaviors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+	/// HTTP+SSE transport.
+	V2024_11_05,
+	/// Streamable HTTP transport, JSON-RPC batching.
+	V2025_03_26,
+	/// `mcp-protocol-version` HTTP header, no more JSON-RPC batching.
+	V2025_06_18,
+}
+
+impl ProtocolVersion {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ProtocolVersion::V2024_11_05 => "2024-11-05",
+			ProtocolVersion::V2025_03_26 => "2025-03-26",
+			ProtocolVersion::V2025_06_18 => "2025-06-18",
+		}
+	}
+
+	/// JSON-RPC batches (arrays of messages) are only part of the `2025-03-26` spec.
+	pub fn supports_batching(&self) -> bool {
+		matches!(self, ProtocolVersion::V2025_03_26)
+	}
+
+	/// Since `2025-06-18`, the HTTP client must send the `mcp-protocol-version` header after the initialization.
+	pub fn has_protocol_version_header(&self) -> bool {
+		*self >= ProtocolVersion::V2025_06_18
+	}
+}
+
+impl fmt::Display for ProtocolVersion {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl FromStr for ProtocolVersion {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		match s {
+			"2024-11-05" => Ok(ProtocolVersion::V2024_11_05),
+			"2025-03-26" => Ok(ProtocolVersion::V2025_03_26),
+			"2025-06-18" => Ok(ProtocolVersion::V2025_06_18),
+			other => Err(Error::ProtocolVersionUnknown(other.to_string())),
+		}
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_protocol_version_parse_and_gates() -> Result<()> {
+		// -- Exec
+		let v_2024: ProtocolVersion = "2024-11-05".parse()?;
+		let v_2025_03: ProtocolVersion = "2025-03-26".parse()?;
+		let v_2025_06: ProtocolVersion = "2025-06-18".parse()?;
+
+		// -- Check
+		assert_eq!(v_2025_03.to_string(), crate::mcp::LATEST_PROTOCOL_VERSION);
+		assert!(!v_2024.supports_batching() && v_2025_03.supports_batching() && !v_2025_06.supports_batching());
+		assert!(!v_2025_03.has_protocol_version_header() && v_2025_06.has_protocol_version_header());
+		assert!("2023-01-01".parse::<ProtocolVersion>().is_err());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,5 +1,5 @@
 use super::types::{Resource, ResourceContents, ResourceTemplate};
-use crate::mcp::{Cursor, GenericMeta, IntoMcpRequest, PaginationParams, RequestMeta};
+use crate::mcp::{Cursor, EmptyResult, GenericMeta, IntoMcpRequest, PaginationParams, RequestMeta};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
@@ -223,7 +223,8 @@ impl SubscribeParams {
 
 impl IntoMcpRequest<SubscribeParams> for SubscribeParams {
 	const METHOD: &'static str = "resources/subscribe";
-	type McpResult = ();
+	/// NOTE: Was `()` before 0.0.5, which cannot parse the `{}` result of the spec (as `SetLevelParams`).
+	type McpResult = EmptyResult;
 }
 
 // endregion: --- SubscribeRequest
@@ -263,7 +264,42 @@ impl UnsubscribeParams {
 
 impl IntoMcpRequest<UnsubscribeParams> for UnsubscribeParams {
 	const METHOD: &'static str = "resources/unsubscribe";
-	type McpResult = ();
+	/// NOTE: Was `()` before 0.0.5, which cannot parse the `{}` result of the spec (as `SetLevelParams`).
+	type McpResult = EmptyResult;
 }
 
 // endregion: --- UnsubscribeRequest
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_subscribe_result_from_empty_object() -> Result<()> {
+		// -- Setup & Fixtures
+		type SubscribeResult = <SubscribeParams as IntoMcpRequest<SubscribeParams>>::McpResult;
+		type UnsubscribeResult = <UnsubscribeParams as IntoMcpRequest<UnsubscribeParams>>::McpResult;
+
+		// -- Exec
+		let subscribe_res: SubscribeResult = serde_json::from_value(json!({}))?;
+		let unsubscribe_res: UnsubscribeResult = serde_json::from_value(json!({}))?;
+		let unit_res = serde_json::from_value::<()>(json!({}));
+
+		// -- Check
+		assert!(subscribe_res.meta.is_none());
+		assert!(unsubscribe_res.meta.is_none());
+		assert!(
+			unit_res.is_err(),
+			"the former unit result cannot parse the spec empty result"
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
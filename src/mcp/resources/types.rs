@@ -126,10 +126,13 @@ impl ResourceTemplate {
 ///
 /// JC-NOTE: Not sure why the TS Spec had a 's' on this one. Only one content
 ///
+/// NOTE: Untagged, as in the spec, where `TextResourceContents` and `BlobResourceContents` have no `type`,
+///       and differ by their `text` / `blob` property (e.g., `{"uri": "file:///a.txt", "text": "..."}`).
+///
 /// TS Ref: `ResourceContents`
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase", tag = "type")]
+#[serde(untagged)]
 pub enum ResourceContents {
 	#[serde(rename_all = "camelCase")]
 	Text {
@@ -195,3 +198,46 @@ impl Root {
 		self
 	}
 }
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_resource_contents_serde_round_trip() -> Result<()> {
+		// -- Setup & Fixtures
+		let text_json = json!({"uri": "file:///a.txt", "mimeType": "text/plain", "text": "Hello"});
+		let blob_json = json!({"uri": "file:///a.bin", "blob": "AQID"});
+
+		// -- Exec
+		let text: ResourceContents = serde_json::from_value(text_json.clone())?;
+		let blob: ResourceContents = serde_json::from_value(blob_json.clone())?;
+
+		// -- Check
+		let ResourceContents::Text {
+			mime_type,
+			text: content,
+			..
+		} = &text
+		else {
+			return Err("Should be text".into());
+		};
+		assert_eq!(mime_type.as_deref(), Some("text/plain"));
+		assert_eq!(content, "Hello");
+		let ResourceContents::Blob { blob: bytes, .. } = &blob else {
+			return Err("Should be blob".into());
+		};
+		assert_eq!(bytes, &vec![1, 2, 3]);
+		assert_eq!(serde_json::to_value(&text)?, text_json);
+		assert_eq!(serde_json::to_value(&blob)?, blob_json);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,13 @@
+use super::transport::ServerTransport; // internal type
+
+pub trait Sealed {}
+
+/// A trait for types that can be converted into an internal `ServerTransport`.
+///
+/// This allows the `Server::serve` method to accept various configurations
+/// (like `ServerStdioTransportConfig`) without exposing the internal
+/// `ServerTransport` enum directly in the signature.
+pub trait IntoServerTransport: Sealed {
+	/// Performs the conversion into a `ServerTransport`.
+	fn into_server_transport(self) -> ServerTransport;
+}
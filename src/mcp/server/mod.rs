@@ -0,0 +1,29 @@
+// region:    --- Modules
+
+mod into_server_transport;
+mod prompt_handler;
+mod prompt_registry;
+mod resource_provider;
+mod session;
+mod tool_args;
+mod tool_handler;
+mod tool_registry;
+pub(crate) mod transport;
+
+mod server_impl;
+
+pub use server_impl::*;
+
+// --- Re-exports (hand picks)
+pub use into_server_transport::IntoServerTransport;
+pub use transport::ServerMemoryTransportConfig;
+pub use transport::ServerStdioTransportConfig;
+#[cfg(feature = "websocket")]
+pub use transport::{DEFAULT_WEBSOCKET_HANDSHAKE_TIMEOUT, ServerWebSocketTransportConfig};
+
+pub use prompt_handler::*;
+pub use resource_provider::{ResourceNotifier, ResourceProvider};
+pub use tool_args::ToolArgs;
+pub use tool_handler::*;
+
+// endregion: --- Modules
@@ -0,0 +1,115 @@
+use crate::mcp::{GetPromptResult, Result};
+use rpc_router::RpcError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The type-erased prompt render function called by the server on `prompts/get`.
+///
+/// The arguments are given as the JSON object of the `GetPromptParams.arguments` (string values).
+pub trait PromptHandlerAsyncFn: Send + Sync {
+	fn exec_fn(&self, arguments: Value) -> Pin<Box<dyn Future<Output = Result<GetPromptResult>> + Send>>;
+}
+
+impl std::fmt::Debug for dyn PromptHandlerAsyncFn {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "PromptHandlerAsyncFn")
+	}
+}
+
+// region:    --- Adapter for generic async functions
+
+struct GenericFnAdapter<FN, ARGS, FUT>
+where
+	FN: FnOnce(ARGS) -> FUT + Send + Sync + Clone + 'static,
+	ARGS: DeserializeOwned + Send + 'static,
+	FUT: Future<Output = Result<GetPromptResult>> + Send + 'static,
+{
+	f: FN,
+	_phantom: std::marker::PhantomData<fn(ARGS) -> FUT>,
+}
+
+impl<FN, ARGS, FUT> PromptHandlerAsyncFn for GenericFnAdapter<FN, ARGS, FUT>
+where
+	FN: FnOnce(ARGS) -> FUT + Send + Sync + Clone + 'static,
+	ARGS: DeserializeOwned + Send + 'static,
+	FUT: Future<Output = Result<GetPromptResult>> + Send + 'static,
+{
+	fn exec_fn(&self, arguments: Value) -> Pin<Box<dyn Future<Output = Result<GetPromptResult>> + Send>> {
+		// -- Parse the arguments (invalid arguments are a protocol error)
+		let args = match serde_json::from_value::<ARGS>(arguments) {
+			Ok(args) => args,
+			Err(err) => {
+				let rpc_error = RpcError::from_invalid_params(Some(format!("Invalid prompt arguments. {err}").into()));
+				return Box::pin(async move { Err(rpc_error.into()) });
+			}
+		};
+
+		Box::pin((self.f.clone())(args))
+	}
+}
+
+// endregion: --- Adapter for generic async functions
+
+// region:    --- IntoPromptHandlerAsyncFn Trait ---
+
+/// Converts an `async fn(Args) -> Result<GetPromptResult>` (with `Args: Deserialize`) into a prompt handler.
+///
+/// `Args` can be a `HashMap<String, String>` to get the raw arguments.
+///
+/// NOTE: The `ARGS` generic is only there to allow the blanket implementation for functions.
+pub trait IntoPromptHandlerAsyncFn<ARGS> {
+	fn into_prompt_handler(self) -> Arc<Box<dyn PromptHandlerAsyncFn>>;
+}
+
+impl IntoPromptHandlerAsyncFn<Value> for Arc<Box<dyn PromptHandlerAsyncFn>> {
+	fn into_prompt_handler(self) -> Arc<Box<dyn PromptHandlerAsyncFn>> {
+		self
+	}
+}
+
+impl<F, ARGS, Fut> IntoPromptHandlerAsyncFn<ARGS> for F
+where
+	F: FnOnce(ARGS) -> Fut + Send + Sync + Clone + 'static,
+	ARGS: DeserializeOwned + Send + 'static,
+	Fut: Future<Output = Result<GetPromptResult>> + Send + 'static,
+{
+	fn into_prompt_handler(self) -> Arc<Box<dyn PromptHandlerAsyncFn>> {
+		let adapter = GenericFnAdapter {
+			f: self,
+			_phantom: std::marker::PhantomData,
+		};
+		Arc::new(Box::new(adapter))
+	}
+}
+
+// endregion: --- IntoPromptHandlerAsyncFn Trait ---
+
+// region:    --- CompleterAsyncFn
+
+/// The type-erased completer called by the server on `completion/complete` for one prompt argument.
+///
+/// Takes the current value of the argument and returns the candidate values.
+pub trait CompleterAsyncFn: Send + Sync {
+	fn exec_fn(&self, value: String) -> Pin<Box<dyn Future<Output = Result<Vec<String>>> + Send>>;
+}
+
+impl std::fmt::Debug for dyn CompleterAsyncFn {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "CompleterAsyncFn")
+	}
+}
+
+impl<F, Fut> CompleterAsyncFn for F
+where
+	F: Fn(String) -> Fut + Send + Sync + 'static,
+	Fut: Future<Output = Result<Vec<String>>> + Send + 'static,
+{
+	fn exec_fn(&self, value: String) -> Pin<Box<dyn Future<Output = Result<Vec<String>>> + Send>> {
+		Box::pin(self(value))
+	}
+}
+
+// endregion: --- CompleterAsyncFn
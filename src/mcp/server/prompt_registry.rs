@@ -0,0 +1,135 @@
+use crate::mcp::server::{CompleterAsyncFn, PromptHandlerAsyncFn};
+use crate::mcp::{
+	CompleteParams, CompleteResult, CompletionReference, CompletionResultData, GetPromptParams, GetPromptResult,
+	Prompt, Result,
+};
+use rpc_router::RpcError;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::error;
+
+/// Max number of values in a `CompleteResult` (per spec).
+const MAX_COMPLETION_VALUES: usize = 100;
+
+/// The prompts registered on a `Server`, in registration order, with their argument completers.
+#[derive(Clone, Default)]
+pub(crate) struct PromptRegistry {
+	entries: Vec<PromptEntry>,
+	/// Completers by `(prompt_name, argument_name)`.
+	completers: HashMap<(String, String), Arc<Box<dyn CompleterAsyncFn>>>,
+}
+
+#[derive(Clone)]
+struct PromptEntry {
+	prompt: Prompt,
+	handler: Arc<Box<dyn PromptHandlerAsyncFn>>,
+}
+
+impl PromptRegistry {
+	/// Registers a prompt. A prompt with the same name gets replaced.
+	pub(crate) fn register(&mut self, prompt: Prompt, handler: Arc<Box<dyn PromptHandlerAsyncFn>>) {
+		let entry = PromptEntry { prompt, handler };
+		match self.entries.iter_mut().find(|e| e.prompt.name == entry.prompt.name) {
+			Some(existing) => *existing = entry,
+			None => self.entries.push(entry),
+		}
+	}
+
+	pub(crate) fn register_completer(
+		&mut self,
+		prompt_name: String,
+		argument_name: String,
+		completer: Arc<Box<dyn CompleterAsyncFn>>,
+	) {
+		self.completers.insert((prompt_name, argument_name), completer);
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	pub(crate) fn has_completers(&self) -> bool {
+		!self.completers.is_empty()
+	}
+
+	pub(crate) fn prompts(&self) -> Vec<Prompt> {
+		self.entries.iter().map(|e| e.prompt.clone()).collect()
+	}
+
+	/// Renders the prompt.
+	///
+	/// Unknown prompt, missing required arguments, or invalid arguments are returned as JSON-RPC invalid params errors.
+	pub(crate) async fn get(&self, params: GetPromptParams) -> Result<GetPromptResult> {
+		let GetPromptParams { name, arguments, .. } = params;
+
+		let entry = self.find_entry(&name)?;
+		let arguments = arguments.unwrap_or_default();
+
+		// -- Check the required arguments
+		let missing: Vec<&str> = entry
+			.prompt
+			.arguments
+			.iter()
+			.flatten()
+			.filter(|arg| arg.required == Some(true) && !arguments.contains_key(&arg.name))
+			.map(|arg| arg.name.as_str())
+			.collect();
+		if !missing.is_empty() {
+			let message = format!(
+				"Missing required argument(s) for prompt '{name}': {}",
+				missing.join(", ")
+			);
+			return Err(RpcError::from_invalid_params(Some(message.into())).into());
+		}
+
+		// -- Render
+		let arguments = Value::Object(arguments.into_iter().map(|(k, v)| (k, Value::String(v))).collect::<Map<_, _>>());
+		let fut = entry.handler.exec_fn(arguments);
+
+		// Note: Run in its own task, so that a panicking render function becomes an error response.
+		match tokio::spawn(fut).await {
+			Ok(res) => res,
+			Err(join_err) => {
+				error!(prompt = %name, "Prompt function failed. Cause: {join_err}");
+				Err(RpcError::from_internal_error(Some(format!("Prompt '{name}' failed").into())).into())
+			}
+		}
+	}
+
+	/// Completes a prompt argument value.
+	///
+	/// An argument without completer (or a resource reference) gets no values.
+	pub(crate) async fn complete(&self, params: CompleteParams) -> Result<CompleteResult> {
+		let CompleteParams {
+			reference, argument, ..
+		} = params;
+
+		let mut values = match reference {
+			CompletionReference::Prompt(prompt_ref) => {
+				self.find_entry(&prompt_ref.name)?;
+				match self.completers.get(&(prompt_ref.name, argument.name)) {
+					Some(completer) => completer.exec_fn(argument.value).await?,
+					None => Vec::new(),
+				}
+			}
+			CompletionReference::Resource(_) => Vec::new(),
+		};
+
+		let total = values.len();
+		values.truncate(MAX_COMPLETION_VALUES);
+		let completion = CompletionResultData::new()
+			.with_has_more(total > values.len())
+			.with_total(total as u64)
+			.with_values(values);
+
+		Ok(CompleteResult { meta: None, completion })
+	}
+
+	fn find_entry(&self, name: &str) -> Result<&PromptEntry> {
+		self.entries
+			.iter()
+			.find(|e| e.prompt.name == name)
+			.ok_or_else(|| RpcError::from_invalid_params(Some(format!("Unknown prompt: {name}").into())).into())
+	}
+}
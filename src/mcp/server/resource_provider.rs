@@ -0,0 +1,113 @@
+use crate::mcp::{Resource, ResourceContents, ResourceTemplate, Result};
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::broadcast;
+
+/// Serves the resources of a `Server` (`resources/list`, `resources/templates/list`, `resources/read`,
+/// `resources/subscribe`, and `resources/unsubscribe`).
+///
+/// The server tracks the subscriptions per session, so `subscribe` / `unsubscribe` are only hooks
+/// (e.g., to start or stop watching a file). Changes are signaled with the `Server::resource_notifier()`.
+///
+/// NOTE: For an unknown resource, `read_resource` should return `Error::resource_not_found(uri)`.
+pub trait ResourceProvider: Send + Sync + 'static {
+	/// The resources for `resources/list`.
+	fn list_resources(&self) -> impl Future<Output = Result<Vec<Resource>>> + Send;
+
+	/// The resource templates for `resources/templates/list` (default none).
+	fn list_resource_templates(&self) -> impl Future<Output = Result<Vec<ResourceTemplate>>> + Send {
+		async { Ok(Vec::new()) }
+	}
+
+	/// The contents of a resource (or resource template instance) for `resources/read`.
+	fn read_resource(&self, uri: &str) -> impl Future<Output = Result<Vec<ResourceContents>>> + Send;
+
+	/// Called when a client subscribes to a resource. Returning an error rejects the subscription.
+	fn subscribe(&self, uri: &str) -> impl Future<Output = Result<()>> + Send {
+		let _ = uri;
+		async { Ok(()) }
+	}
+
+	/// Called when a client unsubscribes from a resource.
+	fn unsubscribe(&self, uri: &str) -> impl Future<Output = Result<()>> + Send {
+		let _ = uri;
+		async { Ok(()) }
+	}
+}
+
+// region:    --- DynResourceProvider
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Object safe version of the `ResourceProvider` (what the server stores).
+pub(crate) trait DynResourceProvider: Send + Sync {
+	fn list_resources(&self) -> BoxFuture<'_, Result<Vec<Resource>>>;
+	fn list_resource_templates(&self) -> BoxFuture<'_, Result<Vec<ResourceTemplate>>>;
+	fn read_resource<'a>(&'a self, uri: &'a str) -> BoxFuture<'a, Result<Vec<ResourceContents>>>;
+	fn subscribe<'a>(&'a self, uri: &'a str) -> BoxFuture<'a, Result<()>>;
+	fn unsubscribe<'a>(&'a self, uri: &'a str) -> BoxFuture<'a, Result<()>>;
+}
+
+impl<T: ResourceProvider> DynResourceProvider for T {
+	fn list_resources(&self) -> BoxFuture<'_, Result<Vec<Resource>>> {
+		Box::pin(ResourceProvider::list_resources(self))
+	}
+
+	fn list_resource_templates(&self) -> BoxFuture<'_, Result<Vec<ResourceTemplate>>> {
+		Box::pin(ResourceProvider::list_resource_templates(self))
+	}
+
+	fn read_resource<'a>(&'a self, uri: &'a str) -> BoxFuture<'a, Result<Vec<ResourceContents>>> {
+		Box::pin(ResourceProvider::read_resource(self, uri))
+	}
+
+	fn subscribe<'a>(&'a self, uri: &'a str) -> BoxFuture<'a, Result<()>> {
+		Box::pin(ResourceProvider::subscribe(self, uri))
+	}
+
+	fn unsubscribe<'a>(&'a self, uri: &'a str) -> BoxFuture<'a, Result<()>> {
+		Box::pin(ResourceProvider::unsubscribe(self, uri))
+	}
+}
+
+// endregion: --- DynResourceProvider
+
+// region:    --- ResourceNotifier
+
+/// A change signaled by the resource provider, dispatched to all the sessions of the server.
+#[derive(Debug, Clone)]
+pub(crate) enum ResourceChange {
+	Updated(String),
+	ListChanged,
+}
+
+/// Handle to signal resource changes to the connected clients.
+///
+/// - `notify_updated` sends a `notifications/resources/updated` to the sessions subscribed to this uri.
+/// - `notify_list_changed` sends a `notifications/resources/list_changed` to all sessions.
+#[derive(Clone)]
+pub struct ResourceNotifier {
+	tx: broadcast::Sender<ResourceChange>,
+}
+
+impl ResourceNotifier {
+	pub(crate) fn new() -> Self {
+		let (tx, _rx) = broadcast::channel(64);
+		Self { tx }
+	}
+
+	pub(crate) fn subscribe(&self) -> broadcast::Receiver<ResourceChange> {
+		self.tx.subscribe()
+	}
+
+	pub fn notify_updated(&self, uri: impl Into<String>) {
+		// Note: Fails only when no session is connected, which is fine.
+		let _ = self.tx.send(ResourceChange::Updated(uri.into()));
+	}
+
+	pub fn notify_list_changed(&self) {
+		let _ = self.tx.send(ResourceChange::ListChanged);
+	}
+}
+
+// endregion: --- ResourceNotifier
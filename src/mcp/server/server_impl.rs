@@ -0,0 +1,731 @@
+use crate::mcp::Result;
+use crate::mcp::server::prompt_registry::PromptRegistry;
+use crate::mcp::server::resource_provider::DynResourceProvider;
+use crate::mcp::server::session::ServerSession;
+use crate::mcp::server::tool_registry::ToolRegistry;
+use crate::mcp::server::transport::{ServerTransport, ServerTrx};
+use crate::mcp::server::{
+	CompleterAsyncFn, IntoPromptHandlerAsyncFn, IntoServerTransport, IntoToolHandlerAsyncFn, ResourceNotifier,
+	ResourceProvider, ToolArgs,
+};
+use crate::mcp::{
+	Implementation, Prompt, ServerCapabilities, ServerPromptsCapabilities, ServerResourcesCapabilities,
+	ServerToolsCapabilities, Tool,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct Server {
+	inner: Arc<ServerInner>,
+	instructions: Option<String>,
+	tools: ToolRegistry,
+	prompts: PromptRegistry,
+	resource_provider: Option<Arc<dyn DynResourceProvider>>,
+}
+
+struct ServerInner {
+	name: String,
+	version: String,
+	resource_notifier: ResourceNotifier,
+}
+
+/// Constructors & Serve
+impl Server {
+	pub fn new(server_name: impl Into<String>, server_version: impl Into<String>) -> Server {
+		let inner = ServerInner {
+			name: server_name.into(),
+			version: server_version.into(),
+			resource_notifier: ResourceNotifier::new(),
+		};
+
+		Self {
+			inner: inner.into(),
+			instructions: None,
+			tools: ToolRegistry::default(),
+			prompts: PromptRegistry::default(),
+			resource_provider: None,
+		}
+	}
+
+	/// Serves MCP over a transport configuration until the client disconnects (e.g., stdin closed),
+	/// or, for `ServerWebSocketTransportConfig`, with a session per connection until the listener fails.
+	///
+	/// Accepts any type that implements `IntoServerTransport`, such as `ServerStdioTransportConfig`.
+	pub async fn serve(&self, transport_source: impl IntoServerTransport) -> Result<()> {
+		let mut transport: ServerTransport = transport_source.into_server_transport();
+		transport.serve(self).await
+	}
+
+	/// Runs one session over an already started transport, until the client side closes.
+	pub(crate) async fn serve_trx(&self, server_trx: ServerTrx) -> Result<()> {
+		let ServerTrx { c2s_rx, s2c_tx } = server_trx;
+		let session = ServerSession::new(self.clone(), s2c_tx);
+		session.run(c2s_rx).await
+	}
+}
+
+/// Public Accessors
+impl Server {
+	pub fn name(&self) -> &str {
+		&self.inner.name
+	}
+
+	pub fn version(&self) -> &str {
+		&self.inner.version
+	}
+
+	pub fn instructions(&self) -> Option<&str> {
+		self.instructions.as_deref()
+	}
+
+	/// The `Implementation` sent as `server_info` in the `InitializeResult`.
+	pub fn server_info(&self) -> Implementation {
+		Implementation::new(self.name(), self.version())
+	}
+
+	/// The capabilities sent in the `InitializeResult`, derived from what has been registered.
+	pub fn capabilities(&self) -> ServerCapabilities {
+		let mut capabilities = ServerCapabilities::default();
+		if !self.tools.is_empty() {
+			capabilities.tools = Some(ServerToolsCapabilities::default());
+		}
+		if !self.prompts.is_empty() {
+			capabilities.prompts = Some(ServerPromptsCapabilities::default());
+		}
+		capabilities.completions = self.prompts.has_completers();
+		if self.resource_provider.is_some() {
+			capabilities.resources = Some(ServerResourcesCapabilities {
+				subscribe: Some(true),
+				list_changed: Some(true),
+			});
+		}
+		capabilities
+	}
+
+	/// The handle to notify the connected clients of resource changes.
+	///
+	/// Can be cloned and moved to the code watching the resources (e.g., a file watcher task).
+	pub fn resource_notifier(&self) -> ResourceNotifier {
+		self.inner.resource_notifier.clone()
+	}
+}
+
+/// Crate Accessors
+impl Server {
+	pub(crate) fn tool_registry(&self) -> &ToolRegistry {
+		&self.tools
+	}
+
+	pub(crate) fn prompt_registry(&self) -> &PromptRegistry {
+		&self.prompts
+	}
+
+	pub(crate) fn resource_provider(&self) -> Option<&Arc<dyn DynResourceProvider>> {
+		self.resource_provider.as_ref()
+	}
+}
+
+/// Setters
+impl Server {
+	/// Instructions describing how to use the server, sent to the client in the `InitializeResult`.
+	pub fn set_instructions(&mut self, instructions: impl Into<String>) {
+		self.instructions = Some(instructions.into());
+	}
+}
+
+/// Handlers
+impl Server {
+	/// Registers a tool with its handler function, which will be listed in `tools/list` and called on `tools/call`.
+	///
+	/// The handler is an `async fn(Args) -> Result<CallToolResult>` where `Args: ToolArgs` is built
+	/// from the `CallToolParams.arguments`, and gives the tool input schema. A tool with the same name gets replaced.
+	///
+	/// - Invalid arguments are returned to the client as a JSON-RPC invalid params error.
+	/// - A handler `Err` is returned as a `CallToolResult` with `is_error: true`
+	///   (unless it is an `Error::Rpc` or `Error::McpError`, which are returned as JSON-RPC errors).
+	pub fn register_tool<ARGS: ToolArgs>(
+		&mut self,
+		name: impl Into<String>,
+		description: impl Into<String>,
+		tool_handler: impl IntoToolHandlerAsyncFn<ARGS>,
+	) {
+		let tool = Tool::new(name, ARGS::input_schema()).with_description(description);
+		self.register_tool_with(tool, tool_handler);
+	}
+
+	/// Same as `register_tool`, but with the given `Tool` (e.g., to override the input schema, or add annotations).
+	pub fn register_tool_with<ARGS>(&mut self, tool: Tool, tool_handler: impl IntoToolHandlerAsyncFn<ARGS>) {
+		let tool_handler = tool_handler.into_tool_handler();
+		self.tools.register(tool, tool_handler);
+	}
+
+	/// Registers a prompt with its render function, which will be listed in `prompts/list` and called on `prompts/get`.
+	///
+	/// The render function is an `async fn(Args) -> Result<GetPromptResult>` where `Args: Deserialize` is built
+	/// from the `GetPromptParams.arguments` (e.g., a struct of `String` fields, or a `HashMap<String, String>`).
+	/// A prompt with the same name gets replaced.
+	///
+	/// The arguments marked as `required` in the `Prompt` are checked before calling the render function,
+	/// and a JSON-RPC invalid params error is returned to the client when one is missing.
+	pub fn register_prompt<ARGS>(&mut self, prompt: Prompt, prompt_handler: impl IntoPromptHandlerAsyncFn<ARGS>) {
+		let prompt_handler = prompt_handler.into_prompt_handler();
+		self.prompts.register(prompt, prompt_handler);
+	}
+
+	/// Registers the completer of a prompt argument, called on `completion/complete`.
+	///
+	/// The completer is an `async fn(String) -> Result<Vec<String>>` taking the current argument value.
+	/// Only the first 100 values are sent (with `has_more` set accordingly).
+	pub fn register_prompt_completer(
+		&mut self,
+		prompt_name: impl Into<String>,
+		argument_name: impl Into<String>,
+		completer: impl CompleterAsyncFn + 'static,
+	) {
+		let completer: Arc<Box<dyn CompleterAsyncFn>> = Arc::new(Box::new(completer));
+		self.prompts
+			.register_completer(prompt_name.into(), argument_name.into(), completer);
+	}
+
+	/// Sets the provider serving the `resources/*` requests. Replaces any previous provider.
+	///
+	/// The subscriptions are tracked per session by the server, and the `resource_notifier()`
+	/// is used to trigger the `notifications/resources/updated` and `notifications/resources/list_changed`.
+	pub fn register_resource_provider(&mut self, provider: impl ResourceProvider) {
+		self.resource_provider = Some(Arc::new(provider));
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mcp::server::transport::{TransportTrx, new_trx_pair};
+	use crate::mcp::{
+		CallToolResult, GetPromptResult, McpMessage, MessageContent, PromptArgument, PromptMessage, Resource,
+		ResourceContents, Role, ToolInputProperty, ToolInputSchema,
+	};
+	use rpc_router::RpcError;
+	use serde_json::{Value, json};
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::time::Duration;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_server_initialize_and_ping() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut server = Server::new("test-server", "0.1.0");
+		server.set_instructions("Some instructions");
+		let transport_trx = start_server(server);
+
+		// -- Exec
+		let init_res = exchange(&transport_trx, fx_initialize_request(1)).await?;
+		let ping_res = exchange(&transport_trx, json!({"jsonrpc": "2.0", "id": 2, "method": "ping"})).await?;
+
+		// -- Check
+		let McpMessage::Response(init_res) = init_res else {
+			return Err("Should be a response".into());
+		};
+		assert_eq!(init_res.result["serverInfo"]["name"], "test-server");
+		assert_eq!(init_res.result["protocolVersion"], crate::mcp::LATEST_PROTOCOL_VERSION);
+		assert_eq!(init_res.result["instructions"], "Some instructions");
+		let McpMessage::Response(ping_res) = ping_res else {
+			return Err("Should be a response".into());
+		};
+		assert_eq!(ping_res.result, json!({}));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_initialize_protocol_version() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport_trx = start_server(Server::new("test-server", "0.1.0"));
+		let mut init_old = fx_initialize_request(1);
+		init_old["params"]["protocolVersion"] = "2024-11-05".into();
+
+		// -- Exec
+		let old_res = exchange(&transport_trx, init_old).await?.try_into_response()?;
+		let transport_trx = start_server(Server::new("test-server", "0.1.0"));
+		let mut init_unknown = fx_initialize_request(1);
+		init_unknown["params"]["protocolVersion"] = "2099-01-01".into();
+		let unknown_res = exchange(&transport_trx, init_unknown).await?.try_into_response()?;
+
+		// -- Check
+		assert_eq!(old_res.result["protocolVersion"], "2024-11-05");
+		assert_eq!(
+			unknown_res.result["protocolVersion"],
+			crate::mcp::LATEST_PROTOCOL_VERSION
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_request_before_initialize() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport_trx = start_server(Server::new("test-server", "0.1.0"));
+
+		// -- Exec
+		let res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}),
+		)
+		.await?;
+
+		// -- Check
+		let McpMessage::Error(mcp_error) = res else {
+			return Err("Should be an error".into());
+		};
+		assert_eq!(mcp_error.error.code, RpcError::CODE_INVALID_REQUEST);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_method_not_found() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport_trx = start_server(Server::new("test-server", "0.1.0"));
+		exchange(&transport_trx, fx_initialize_request(1)).await?;
+
+		// -- Exec
+		let res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 2, "method": "not/a/method"}),
+		)
+		.await?;
+
+		// -- Check
+		let McpMessage::Error(mcp_error) = res else {
+			return Err("Should be an error".into());
+		};
+		assert_eq!(mcp_error.error.code, RpcError::CODE_METHOD_NOT_FOUND);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_tools_list_and_call() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport_trx = start_server(fx_server_with_add_tool());
+		exchange(&transport_trx, fx_initialize_request(1)).await?;
+
+		// -- Exec
+		let list_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+		)
+		.await?;
+		let call_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "add", "arguments": {"a": 1, "b": 2.5}}}),
+		)
+		.await?;
+
+		// -- Check
+		let list_res = list_res.try_into_response()?;
+		assert_eq!(list_res.result["tools"][0]["name"], "add");
+		assert_eq!(list_res.result["tools"][0]["inputSchema"]["type"], "object");
+		assert_eq!(
+			list_res.result["tools"][0]["inputSchema"]["required"],
+			json!(["a", "b"])
+		);
+		assert_eq!(
+			list_res.result["tools"][0]["inputSchema"]["properties"]["a"]["type"],
+			"number"
+		);
+		assert_eq!(list_res.result["tools"][0]["description"], "Adds two numbers");
+		// The overridden schema of the `echo` tool (raw arguments).
+		assert_eq!(list_res.result["tools"][1]["name"], "echo");
+		assert_eq!(
+			list_res.result["tools"][1]["inputSchema"]["required"],
+			json!(["message"])
+		);
+		let call_res = call_res.try_into_response()?;
+		assert_eq!(call_res.result["content"][0]["text"], "3.5");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_tools_call_errors() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport_trx = start_server(fx_server_with_add_tool());
+		exchange(&transport_trx, fx_initialize_request(1)).await?;
+
+		// -- Exec
+		let invalid_args_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "add", "arguments": {"a": "one"}}}),
+		)
+		.await?;
+		let unknown_tool_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "nope"}}),
+		)
+		.await?;
+		let tool_err_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 4, "method": "tools/call", "params": {"name": "add", "arguments": {"a": 1, "b": -1}}}),
+		)
+		.await?;
+
+		// -- Check
+		let McpMessage::Error(invalid_args_err) = invalid_args_res else {
+			return Err("Should be an error".into());
+		};
+		assert_eq!(invalid_args_err.error.code, RpcError::CODE_INVALID_PARAMS);
+		let McpMessage::Error(unknown_tool_err) = unknown_tool_res else {
+			return Err("Should be an error".into());
+		};
+		assert_eq!(unknown_tool_err.error.code, RpcError::CODE_INVALID_PARAMS);
+		let tool_err_res = tool_err_res.try_into_response()?;
+		assert_eq!(tool_err_res.result["isError"], true);
+		assert_eq!(tool_err_res.result["content"][0]["text"], "Only positive numbers");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_resources_list_and_read() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut server = Server::new("test-server", "0.1.0");
+		server.register_resource_provider(FxResourceProvider);
+		let transport_trx = start_server(server);
+		let init_res = exchange(&transport_trx, fx_initialize_request(1)).await?.try_into_response()?;
+
+		// -- Exec
+		let list_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 2, "method": "resources/list"}),
+		)
+		.await?;
+		let templates_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 3, "method": "resources/templates/list"}),
+		)
+		.await?;
+		let read_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 4, "method": "resources/read", "params": {"uri": "file:///a.txt"}}),
+		)
+		.await?;
+		let read_unknown_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 5, "method": "resources/read", "params": {"uri": "file:///nope.txt"}}),
+		)
+		.await?;
+
+		// -- Check
+		assert_eq!(init_res.result["capabilities"]["resources"]["subscribe"], true);
+		let list_res = list_res.try_into_response()?;
+		assert_eq!(list_res.result["resources"][0]["uri"], "file:///a.txt");
+		let templates_res = templates_res.try_into_response()?;
+		assert_eq!(templates_res.result["resourceTemplates"], json!([]));
+		let read_res = read_res.try_into_response()?;
+		assert_eq!(
+			read_res.result["contents"][0],
+			json!({"uri": "file:///a.txt", "text": "Content of a"})
+		);
+		let McpMessage::Error(read_unknown_err) = read_unknown_res else {
+			return Err("Should be an error".into());
+		};
+		assert_eq!(read_unknown_err.error.code, crate::mcp::Error::CODE_RESOURCE_NOT_FOUND);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_resources_subscribe_and_notify() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut server = Server::new("test-server", "0.1.0");
+		server.register_resource_provider(FxResourceProvider);
+		let notifier = server.resource_notifier();
+		let transport_trx = start_server(server);
+		exchange(&transport_trx, fx_initialize_request(1)).await?;
+		exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 2, "method": "resources/subscribe", "params": {"uri": "file:///a.txt"}}),
+		)
+		.await?
+		.try_into_response()?;
+
+		// -- Exec
+		notifier.notify_updated("file:///b.txt"); // not subscribed
+		notifier.notify_updated("file:///a.txt");
+		notifier.notify_list_changed();
+		let first = transport_trx.s2c_rx.recv().await?.parse::<McpMessage>()?;
+		let second = transport_trx.s2c_rx.recv().await?.parse::<McpMessage>()?;
+		exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 3, "method": "resources/unsubscribe", "params": {"uri": "file:///a.txt"}}),
+		)
+		.await?
+		.try_into_response()?;
+		notifier.notify_updated("file:///a.txt"); // not subscribed anymore
+		let ping_res = exchange(&transport_trx, json!({"jsonrpc": "2.0", "id": 4, "method": "ping"})).await?;
+
+		// -- Check
+		let McpMessage::Notification(first) = first else {
+			return Err("Should be a notification".into());
+		};
+		assert_eq!(first.method, "notifications/resources/updated");
+		assert_eq!(first.params.ok_or("Should have params")?["uri"], "file:///a.txt");
+		let McpMessage::Notification(second) = second else {
+			return Err("Should be a notification".into());
+		};
+		assert_eq!(second.method, "notifications/resources/list_changed");
+		// The ping response comes first, so no updated notification was sent after unsubscribe.
+		assert!(matches!(ping_res, McpMessage::Response(_)));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_resources_subscribe_once_and_unsubscribe_at_end() -> Result<()> {
+		// -- Setup & Fixtures
+		let provider = FxCountingResourceProvider::default();
+		let (subscribe_count, unsubscribe_count) =
+			(provider.subscribe_count.clone(), provider.unsubscribe_count.clone());
+		let mut server = Server::new("test-server", "0.1.0");
+		server.register_resource_provider(provider);
+		let (server_trx, transport_trx) = new_trx_pair();
+		let server_task = tokio::spawn(async move { server.serve_trx(server_trx).await });
+		exchange(&transport_trx, fx_initialize_request(1)).await?;
+
+		// -- Exec
+		for (id, uri) in [(2, "file:///a.txt"), (3, "file:///a.txt"), (4, "file:///b.txt")] {
+			exchange(
+				&transport_trx,
+				json!({"jsonrpc": "2.0", "id": id, "method": "resources/subscribe", "params": {"uri": uri}}),
+			)
+			.await?
+			.try_into_response()?;
+		}
+		let subscribe_count_before_end = subscribe_count.load(Ordering::SeqCst);
+		let unsubscribe_count_before_end = unsubscribe_count.load(Ordering::SeqCst);
+		drop(transport_trx); // ends the session
+		tokio::time::timeout(Duration::from_secs(2), server_task).await???;
+
+		// -- Check
+		// The repeated subscribe to a.txt does not reach the provider.
+		assert_eq!(subscribe_count_before_end, 2);
+		assert_eq!(unsubscribe_count_before_end, 0);
+		assert_eq!(unsubscribe_count.load(Ordering::SeqCst), 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_prompts_list_and_get() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport_trx = start_server(fx_server_with_prompt());
+		let init_res = exchange(&transport_trx, fx_initialize_request(1)).await?.try_into_response()?;
+
+		// -- Exec
+		let list_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 2, "method": "prompts/list"}),
+		)
+		.await?;
+		let get_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 3, "method": "prompts/get", "params": {"name": "greet", "arguments": {"name": "Mike"}}}),
+		)
+		.await?;
+		let missing_arg_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 4, "method": "prompts/get", "params": {"name": "greet", "arguments": {"style": "formal"}}}),
+		)
+		.await?;
+
+		// -- Check
+		assert_eq!(init_res.result["capabilities"]["completions"], json!({}));
+		let list_res = list_res.try_into_response()?;
+		assert_eq!(list_res.result["prompts"][0]["name"], "greet");
+		assert_eq!(list_res.result["prompts"][0]["arguments"][0]["required"], true);
+		let get_res = get_res.try_into_response()?;
+		assert_eq!(get_res.result["messages"][0]["role"], "user");
+		assert_eq!(get_res.result["messages"][0]["content"]["text"], "Say hello to Mike");
+		let McpMessage::Error(missing_arg_err) = missing_arg_res else {
+			return Err("Should be an error".into());
+		};
+		assert_eq!(missing_arg_err.error.code, RpcError::CODE_INVALID_PARAMS);
+		let data = missing_arg_err.error.data.ok_or("Should have data")?;
+		assert_eq!(data, "Missing required argument(s) for prompt 'greet': name");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_server_prompts_complete() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport_trx = start_server(fx_server_with_prompt());
+		exchange(&transport_trx, fx_initialize_request(1)).await?;
+
+		// -- Exec
+		let complete_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 2, "method": "completion/complete", "params": {
+				"ref": {"type": "ref/prompt", "name": "greet"},
+				"argument": {"name": "style", "value": "fo"}
+			}}),
+		)
+		.await?;
+		let no_completer_res = exchange(
+			&transport_trx,
+			json!({"jsonrpc": "2.0", "id": 3, "method": "completion/complete", "params": {
+				"ref": {"type": "ref/prompt", "name": "greet"},
+				"argument": {"name": "name", "value": "M"}
+			}}),
+		)
+		.await?;
+
+		// -- Check
+		let complete_res = complete_res.try_into_response()?;
+		assert_eq!(complete_res.result["completion"]["values"], json!(["formal"]));
+		assert_eq!(complete_res.result["completion"]["hasMore"], false);
+		let no_completer_res = no_completer_res.try_into_response()?;
+		assert_eq!(no_completer_res.result["completion"]["values"], json!([]));
+
+		Ok(())
+	}
+
+	// region:    --- Support
+
+	#[derive(serde::Deserialize)]
+	struct GreetArgs {
+		name: String,
+		style: Option<String>,
+	}
+
+	async fn greet(args: GreetArgs) -> crate::mcp::Result<GetPromptResult> {
+		let style = args.style.map(|s| format!(" ({s})")).unwrap_or_default();
+		let message = PromptMessage::new(
+			Role::User,
+			MessageContent::new_text(format!("Say hello to {}{style}", args.name)),
+		);
+		Ok(GetPromptResult::new(vec![message]))
+	}
+
+	fn fx_server_with_prompt() -> Server {
+		let mut server = Server::new("test-server", "0.1.0");
+		let prompt = Prompt::new("greet")
+			.append_argument(PromptArgument::new("name").with_required(true))
+			.append_argument(PromptArgument::new("style"));
+		server.register_prompt(prompt, greet);
+		server.register_prompt_completer("greet", "style", async |value: String| {
+			let values = ["casual", "formal"].into_iter().filter(|v| v.starts_with(&value));
+			Ok(values.map(String::from).collect())
+		});
+		server
+	}
+
+	struct FxResourceProvider;
+
+	impl ResourceProvider for FxResourceProvider {
+		async fn list_resources(&self) -> crate::mcp::Result<Vec<Resource>> {
+			Ok(vec![Resource::new("file:///a.txt", "a.txt")])
+		}
+
+		async fn read_resource(&self, uri: &str) -> crate::mcp::Result<Vec<ResourceContents>> {
+			if uri != "file:///a.txt" {
+				return Err(crate::mcp::Error::resource_not_found(uri));
+			}
+			Ok(vec![ResourceContents::Text {
+				uri: uri.to_string(),
+				mime_type: None,
+				text: "Content of a".to_string(),
+			}])
+		}
+	}
+
+	/// Counts the `subscribe` / `unsubscribe` calls.
+	#[derive(Default)]
+	struct FxCountingResourceProvider {
+		subscribe_count: Arc<AtomicUsize>,
+		unsubscribe_count: Arc<AtomicUsize>,
+	}
+
+	impl ResourceProvider for FxCountingResourceProvider {
+		async fn list_resources(&self) -> crate::mcp::Result<Vec<Resource>> {
+			Ok(Vec::new())
+		}
+
+		async fn read_resource(&self, uri: &str) -> crate::mcp::Result<Vec<ResourceContents>> {
+			Err(crate::mcp::Error::resource_not_found(uri))
+		}
+
+		async fn subscribe(&self, _uri: &str) -> crate::mcp::Result<()> {
+			self.subscribe_count.fetch_add(1, Ordering::SeqCst);
+			Ok(())
+		}
+
+		async fn unsubscribe(&self, _uri: &str) -> crate::mcp::Result<()> {
+			self.unsubscribe_count.fetch_add(1, Ordering::SeqCst);
+			Ok(())
+		}
+	}
+
+	#[derive(serde::Deserialize)]
+	struct AddArgs {
+		a: f64,
+		b: f64,
+	}
+
+	impl ToolArgs for AddArgs {
+		fn input_schema() -> ToolInputSchema {
+			ToolInputSchema::object()
+				.append_required_property("a", ToolInputProperty::number())
+				.append_required_property("b", ToolInputProperty::number())
+		}
+	}
+
+	async fn add(args: AddArgs) -> crate::mcp::Result<CallToolResult> {
+		if args.a < 0. || args.b < 0. {
+			return Err("Only positive numbers".into());
+		}
+		Ok(CallToolResult::from_text((args.a + args.b).to_string()))
+	}
+
+	fn fx_server_with_add_tool() -> Server {
+		let mut server = Server::new("test-server", "0.1.0");
+		server.register_tool("add", "Adds two numbers", add);
+		let echo_schema = ToolInputSchema::object().append_required_property("message", ToolInputProperty::string());
+		server.register_tool_with(Tool::new("echo", echo_schema), async |args: Value| {
+			Ok(CallToolResult::from_text(args["message"].to_string()))
+		});
+		server
+	}
+
+	fn start_server(server: Server) -> TransportTrx {
+		let (server_trx, transport_trx) = new_trx_pair();
+		tokio::spawn(async move { server.serve_trx(server_trx).await });
+		transport_trx
+	}
+
+	async fn exchange(transport_trx: &TransportTrx, message: Value) -> Result<McpMessage> {
+		transport_trx.c2s_tx.send(message.to_string()).await?;
+		let res = transport_trx.s2c_rx.recv().await?;
+		Ok(res.parse::<McpMessage>()?)
+	}
+
+	fn fx_initialize_request(id: i64) -> Value {
+		json!({
+			"jsonrpc": "2.0",
+			"id": id,
+			"method": "initialize",
+			"params": {
+				"protocolVersion": crate::mcp::LATEST_PROTOCOL_VERSION,
+				"capabilities": {},
+				"clientInfo": {"name": "test-client", "version": "0.1.0"}
+			}
+		})
+	}
+
+	// endregion: --- Support
+}
+
+// endregion: --- Tests
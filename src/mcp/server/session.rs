@@ -0,0 +1,415 @@
+use crate::RpcId;
+use crate::mcp::server::Server;
+use crate::mcp::server::resource_provider::{DynResourceProvider, ResourceChange};
+use crate::mcp::support::{parse_params, to_result_value, truncate};
+use crate::mcp::transport::{CommRx, CommTx};
+use crate::mcp::{
+	CallToolParams, CompleteParams, EmptyResult, Error, GetPromptParams, InitializeParams, InitializeResult,
+	InitializedNotificationParams, IntoMcpNotification, IntoMcpRequest, ListPromptsParams, ListPromptsResult,
+	ListResourceTemplatesParams, ListResourceTemplatesResult, ListResourcesParams, ListResourcesResult,
+	ListToolsParams, ListToolsResult, McpError, McpMessage, McpNotification, McpRequest, McpResponse, PingParams,
+	ProtocolVersion, ReadResourceParams, ReadResourceResult, ResourceListChangedNotificationParams,
+	ResourceUpdatedNotificationParams, Result, SUPPORTED_PROTOCOL_VERSIONS, SubscribeParams, UnsubscribeParams,
+};
+use rpc_router::RpcError;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use tokio::task::JoinSet;
+use tracing::{debug, error, info, warn};
+
+/// The lifecycle state of one client connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionState {
+	/// Waiting for the `initialize` request.
+	Uninitialized,
+	/// `initialize` answered, waiting for the `notifications/initialized`.
+	Initializing,
+	/// Initialization completed.
+	Ready,
+}
+
+/// One client connection served by a `Server`.
+#[derive(Clone)]
+pub(crate) struct ServerSession {
+	inner: Arc<SessionInner>,
+}
+
+struct SessionInner {
+	server: Server,
+	s2c_tx: CommTx,
+	state: RwLock<SessionState>,
+	/// The resource uris this client subscribed to (`resources/subscribe`).
+	resource_subscriptions: RwLock<HashSet<String>>,
+}
+
+/// Constructor & Run
+impl ServerSession {
+	pub(crate) fn new(server: Server, s2c_tx: CommTx) -> Self {
+		let inner = SessionInner {
+			server,
+			s2c_tx,
+			state: RwLock::new(SessionState::Uninitialized),
+			resource_subscriptions: RwLock::new(HashSet::new()),
+		};
+		Self { inner: inner.into() }
+	}
+
+	/// Processes the client messages until the `c2s_rx` gets closed.
+	///
+	/// Each request is processed in its own task, so a long tool call does not block the session.
+	pub(crate) async fn run(&self, c2s_rx: CommRx) -> Result<()> {
+		let mut request_tasks = JoinSet::new();
+
+		// -- Forward the resource changes for the lifetime of the session
+		let resource_changes_task = self.server().resource_provider().is_some().then(|| {
+			let session = self.clone();
+			let changes_rx = self.server().resource_notifier().subscribe();
+			tokio::spawn(async move { session.run_resource_changes(changes_rx).await })
+		});
+
+		while let Ok(msg) = c2s_rx.recv().await {
+			let mcp_message = match msg.parse::<McpMessage>() {
+				Ok(mcp_message) => mcp_message,
+				Err(err) => {
+					error!(message = %truncate(&msg, 256), "Parsing received McpMessage");
+					let rpc_error = RpcError::from_parse_error(Some(err.to_string().into()));
+					self.send_message(McpError::new(RpcId::Null, rpc_error)).await?;
+					continue;
+				}
+			};
+
+			match mcp_message {
+				// NOTE: initialize is processed inline so that the lifecycle state is set
+				//       before any following message (e.g., `notifications/initialized`).
+				McpMessage::Request(mcp_request) if mcp_request.method == InitializeParams::METHOD => {
+					self.process_request(mcp_request).await
+				}
+				McpMessage::Request(mcp_request) => {
+					let session = self.clone();
+					request_tasks.spawn(async move { session.process_request(mcp_request).await });
+				}
+				McpMessage::Notification(mcp_notification) => self.process_notification(mcp_notification),
+				McpMessage::Response(mcp_response) => {
+					warn!(rpc_id = %mcp_response.id, "MCP Response from client not supported yet")
+				}
+				McpMessage::Error(mcp_error) => {
+					warn!(rpc_id = %mcp_error.id, "MCP Error from client not supported yet")
+				}
+			}
+
+			// Clean up the completed request tasks
+			while request_tasks.try_join_next().is_some() {}
+		}
+
+		// -- Let the in-flight requests send their responses
+		while request_tasks.join_next().await.is_some() {}
+
+		if let Some(resource_changes_task) = resource_changes_task {
+			resource_changes_task.abort();
+		}
+
+		// -- Release the subscriptions left by the client
+		self.unsubscribe_all().await;
+
+		info!("MCP Session Ended");
+
+		Ok(())
+	}
+}
+
+/// Send
+impl ServerSession {
+	pub(crate) async fn send_message(&self, message: impl Into<McpMessage>) -> Result<()> {
+		let payload = message.into().stringify()?;
+		self.inner.s2c_tx.send(payload).await?;
+		Ok(())
+	}
+
+	pub(crate) async fn send_notification<P>(&self, params: P) -> Result<()>
+	where
+		P: IntoMcpNotification + Serialize,
+	{
+		let payload = params.into_mcp_notification().stringify()?;
+		self.inner.s2c_tx.send(payload).await?;
+		Ok(())
+	}
+}
+
+/// Accessors
+impl ServerSession {
+	fn server(&self) -> &Server {
+		&self.inner.server
+	}
+
+	fn state(&self) -> SessionState {
+		*self.inner.state.read().unwrap_or_else(|e| e.into_inner())
+	}
+
+	fn set_state(&self, state: SessionState) {
+		*self.inner.state.write().unwrap_or_else(|e| e.into_inner()) = state;
+	}
+
+	fn resource_provider(&self, method: &str) -> Result<&Arc<dyn DynResourceProvider>> {
+		self.server()
+			.resource_provider()
+			.ok_or_else(|| RpcError::from_method_not_found(Some(method.into())).into())
+	}
+
+	fn is_subscribed(&self, uri: &str) -> bool {
+		self.inner
+			.resource_subscriptions
+			.read()
+			.unwrap_or_else(|e| e.into_inner())
+			.contains(uri)
+	}
+}
+
+/// Requests
+impl ServerSession {
+	async fn process_request(&self, mcp_request: McpRequest) {
+		let rpc_id = mcp_request.id.clone();
+		debug!(rpc_id = %rpc_id, method = %mcp_request.method, "Received RPC Request");
+
+		let message: McpMessage = match self.route_request(mcp_request).await {
+			Ok(result) => McpResponse { id: rpc_id, result }.into(),
+			Err(err) => McpError::new(rpc_id, err.into_rpc_error()).into(),
+		};
+
+		if let Err(err) = self.send_message(message).await {
+			error!("Fail to send response. Cause: {err}");
+		}
+	}
+
+	async fn route_request(&self, mcp_request: McpRequest) -> Result<Value> {
+		let McpRequest { method, params, .. } = mcp_request;
+		let method = method.as_str();
+
+		// -- Lifecycle guard
+		// Only ping is allowed before the initialize request.
+		let state = self.state();
+		if state == SessionState::Uninitialized && method != InitializeParams::METHOD && method != PingParams::METHOD {
+			return Err(invalid_request(format!(
+				"Server not initialized. Cannot process '{method}' before 'initialize'"
+			)));
+		}
+
+		match method {
+			InitializeParams::METHOD => {
+				let params: InitializeParams = parse_params(params)?;
+				let result = self.initialize(params)?;
+				to_result_value(result)
+			}
+			PingParams::METHOD => to_result_value(EmptyResult::new()),
+
+			// -- Tools
+			ListToolsParams::METHOD => {
+				let _params: ListToolsParams = parse_params(params)?;
+				let result = ListToolsResult {
+					meta: None,
+					next_cursor: None,
+					tools: self.server().tool_registry().tools(),
+				};
+				to_result_value(result)
+			}
+			CallToolParams::METHOD => {
+				let params: CallToolParams = parse_params(params)?;
+				let result = self.server().tool_registry().call(params).await?;
+				to_result_value(result)
+			}
+
+			// -- Prompts
+			ListPromptsParams::METHOD => {
+				let _params: ListPromptsParams = parse_params(params)?;
+				let result = ListPromptsResult {
+					meta: None,
+					next_cursor: None,
+					prompts: self.server().prompt_registry().prompts(),
+				};
+				to_result_value(result)
+			}
+			GetPromptParams::METHOD => {
+				let params: GetPromptParams = parse_params(params)?;
+				let result = self.server().prompt_registry().get(params).await?;
+				to_result_value(result)
+			}
+			CompleteParams::METHOD if self.server().prompt_registry().has_completers() => {
+				let params: CompleteParams = parse_params(params)?;
+				let result = self.server().prompt_registry().complete(params).await?;
+				to_result_value(result)
+			}
+
+			// -- Resources
+			ListResourcesParams::METHOD => {
+				let _params: ListResourcesParams = parse_params(params)?;
+				let result = ListResourcesResult {
+					meta: None,
+					next_cursor: None,
+					resources: self.resource_provider(method)?.list_resources().await?,
+				};
+				to_result_value(result)
+			}
+			ListResourceTemplatesParams::METHOD => {
+				let _params: ListResourceTemplatesParams = parse_params(params)?;
+				let result = ListResourceTemplatesResult {
+					meta: None,
+					next_cursor: None,
+					resource_templates: self.resource_provider(method)?.list_resource_templates().await?,
+				};
+				to_result_value(result)
+			}
+			ReadResourceParams::METHOD => {
+				let params: ReadResourceParams = parse_params(params)?;
+				let result = ReadResourceResult {
+					meta: None,
+					contents: self.resource_provider(method)?.read_resource(&params.uri).await?,
+				};
+				to_result_value(result)
+			}
+			SubscribeParams::METHOD => {
+				let params: SubscribeParams = parse_params(params)?;
+				let provider = self.resource_provider(method)?;
+				let inserted = self
+					.inner
+					.resource_subscriptions
+					.write()
+					.unwrap_or_else(|e| e.into_inner())
+					.insert(params.uri.clone());
+				// NOTE: A repeated subscribe does not call the provider again.
+				if inserted {
+					if let Err(err) = provider.subscribe(&params.uri).await {
+						self.inner
+							.resource_subscriptions
+							.write()
+							.unwrap_or_else(|e| e.into_inner())
+							.remove(&params.uri);
+						return Err(err);
+					}
+				}
+				to_result_value(EmptyResult::new())
+			}
+			UnsubscribeParams::METHOD => {
+				let params: UnsubscribeParams = parse_params(params)?;
+				let provider = self.resource_provider(method)?;
+				let removed = self
+					.inner
+					.resource_subscriptions
+					.write()
+					.unwrap_or_else(|e| e.into_inner())
+					.remove(&params.uri);
+				if removed {
+					provider.unsubscribe(&params.uri).await?;
+				}
+				to_result_value(EmptyResult::new())
+			}
+
+			_ => Err(RpcError::from_method_not_found(Some(method.into())).into()),
+		}
+	}
+
+	fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+		if self.state() != SessionState::Uninitialized {
+			return Err(invalid_request("Server already initialized"));
+		}
+
+		debug!(
+			client_name = %params.client_info.name,
+			protocol_version = %params.protocol_version,
+			"Initialize request"
+		);
+
+		// -- Negotiate the protocol version
+		// Per spec, answer with the requested version if supported, otherwise with the latest one.
+		let protocol_version = match params.protocol_version.parse::<ProtocolVersion>() {
+			Ok(version) if SUPPORTED_PROTOCOL_VERSIONS.contains(&version) => version.as_str(),
+			_ => crate::mcp::LATEST_PROTOCOL_VERSION,
+		};
+
+		let server = self.server();
+		let mut result = InitializeResult::new(protocol_version, server.capabilities(), server.server_info());
+		if let Some(instructions) = server.instructions() {
+			result = result.with_instructions(instructions);
+		}
+
+		self.set_state(SessionState::Initializing);
+
+		Ok(result)
+	}
+}
+
+/// Notifications
+impl ServerSession {
+	fn process_notification(&self, mcp_notification: McpNotification) {
+		match mcp_notification.method.as_str() {
+			InitializedNotificationParams::METHOD => {
+				if self.state() == SessionState::Uninitialized {
+					warn!("Received 'notifications/initialized' before 'initialize'. Ignored");
+					return;
+				}
+				self.set_state(SessionState::Ready);
+				info!("MCP Session initialized");
+			}
+			other => debug!(method = %other, "MCP Notification not handled"),
+		}
+	}
+
+	/// Sends the resource change notifications relevant to this session.
+	///
+	/// - `updated` only for the subscribed uris.
+	/// - `list_changed` once the session is initialized.
+	async fn run_resource_changes(&self, mut changes_rx: broadcast::Receiver<ResourceChange>) {
+		loop {
+			let change = match changes_rx.recv().await {
+				Ok(change) => change,
+				Err(broadcast::error::RecvError::Lagged(count)) => {
+					warn!(count, "Resource changes lagged. Some notifications were dropped");
+					continue;
+				}
+				Err(broadcast::error::RecvError::Closed) => break,
+			};
+
+			let res = match change {
+				ResourceChange::Updated(uri) if self.is_subscribed(&uri) => {
+					self.send_notification(ResourceUpdatedNotificationParams::new(uri)).await
+				}
+				ResourceChange::ListChanged if self.state() != SessionState::Uninitialized => {
+					self.send_notification(ResourceListChangedNotificationParams::new()).await
+				}
+				_ => continue,
+			};
+
+			if let Err(err) = res {
+				error!("Fail to send resource change notification. Cause: {err}");
+			}
+		}
+	}
+
+	/// Unsubscribes the provider from the uris this session is still subscribed to (at the session end).
+	async fn unsubscribe_all(&self) {
+		let Some(provider) = self.server().resource_provider() else {
+			return;
+		};
+		let uris: Vec<String> = self
+			.inner
+			.resource_subscriptions
+			.write()
+			.unwrap_or_else(|e| e.into_inner())
+			.drain()
+			.collect();
+		for uri in uris {
+			if let Err(err) = provider.unsubscribe(&uri).await {
+				warn!(uri = %uri, "Fail to unsubscribe resource at session end. Cause: {err}");
+			}
+		}
+	}
+}
+
+// region:    --- Support
+
+fn invalid_request(message: impl Into<String>) -> Error {
+	RpcError::from_invalid_request(Some(message.into().into())).into()
+}
+
+// endregion: --- Support
@@ -0,0 +1,33 @@
+use crate::mcp::ToolInputSchema;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// The arguments of a tool function (deserialized from the `CallToolParams.arguments`),
+/// which give the input schema of the tool (see `Server::register_tool`).
+///
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct AddArgs {
+///     a: f64,
+///     b: f64,
+/// }
+///
+/// impl ToolArgs for AddArgs {
+///     fn input_schema() -> ToolInputSchema {
+///         ToolInputSchema::object()
+///             .append_required_property("a", ToolInputProperty::number().with_description("First number"))
+///             .append_required_property("b", ToolInputProperty::number().with_description("Second number"))
+///     }
+/// }
+/// ```
+pub trait ToolArgs: DeserializeOwned + Send + 'static {
+	/// The JSON Schema of these arguments (an "object" schema).
+	fn input_schema() -> ToolInputSchema;
+}
+
+/// The raw arguments (any JSON object).
+impl ToolArgs for Value {
+	fn input_schema() -> ToolInputSchema {
+		ToolInputSchema::object()
+	}
+}
@@ -0,0 +1,86 @@
+use crate::mcp::{CallToolResult, Result};
+use rpc_router::RpcError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The type-erased tool function called by the server on `tools/call`.
+///
+/// The arguments are given as the JSON object of the `CallToolParams.arguments`.
+pub trait ToolHandlerAsyncFn: Send + Sync {
+	fn exec_fn(&self, arguments: Value) -> Pin<Box<dyn Future<Output = Result<CallToolResult>> + Send>>;
+}
+
+impl std::fmt::Debug for dyn ToolHandlerAsyncFn {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "ToolHandlerAsyncFn")
+	}
+}
+
+// region:    --- Adapter for generic async functions
+
+struct GenericFnAdapter<FN, ARGS, FUT>
+where
+	FN: FnOnce(ARGS) -> FUT + Send + Sync + Clone + 'static,
+	ARGS: DeserializeOwned + Send + 'static,
+	FUT: Future<Output = Result<CallToolResult>> + Send + 'static,
+{
+	f: FN,
+	_phantom: std::marker::PhantomData<fn(ARGS) -> FUT>,
+}
+
+impl<FN, ARGS, FUT> ToolHandlerAsyncFn for GenericFnAdapter<FN, ARGS, FUT>
+where
+	FN: FnOnce(ARGS) -> FUT + Send + Sync + Clone + 'static,
+	ARGS: DeserializeOwned + Send + 'static,
+	FUT: Future<Output = Result<CallToolResult>> + Send + 'static,
+{
+	fn exec_fn(&self, arguments: Value) -> Pin<Box<dyn Future<Output = Result<CallToolResult>> + Send>> {
+		// -- Parse the arguments (invalid arguments are a protocol error)
+		let args = match serde_json::from_value::<ARGS>(arguments) {
+			Ok(args) => args,
+			Err(err) => {
+				let rpc_error = RpcError::from_invalid_params(Some(format!("Invalid tool arguments. {err}").into()));
+				return Box::pin(async move { Err(rpc_error.into()) });
+			}
+		};
+
+		Box::pin((self.f.clone())(args))
+	}
+}
+
+// endregion: --- Adapter for generic async functions
+
+// region:    --- IntoToolHandlerAsyncFn Trait ---
+
+/// Converts an `async fn(Args) -> Result<CallToolResult>` (with `Args: Deserialize`) into a tool handler.
+///
+/// NOTE: The `ARGS` generic is only there to allow the blanket implementation for functions.
+pub trait IntoToolHandlerAsyncFn<ARGS> {
+	fn into_tool_handler(self) -> Arc<Box<dyn ToolHandlerAsyncFn>>;
+}
+
+impl IntoToolHandlerAsyncFn<Value> for Arc<Box<dyn ToolHandlerAsyncFn>> {
+	fn into_tool_handler(self) -> Arc<Box<dyn ToolHandlerAsyncFn>> {
+		self
+	}
+}
+
+impl<F, ARGS, Fut> IntoToolHandlerAsyncFn<ARGS> for F
+where
+	F: FnOnce(ARGS) -> Fut + Send + Sync + Clone + 'static,
+	ARGS: DeserializeOwned + Send + 'static,
+	Fut: Future<Output = Result<CallToolResult>> + Send + 'static,
+{
+	fn into_tool_handler(self) -> Arc<Box<dyn ToolHandlerAsyncFn>> {
+		let adapter = GenericFnAdapter {
+			f: self,
+			_phantom: std::marker::PhantomData,
+		};
+		Arc::new(Box::new(adapter))
+	}
+}
+
+// endregion: --- IntoToolHandlerAsyncFn Trait ---
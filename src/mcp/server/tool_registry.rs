@@ -0,0 +1,69 @@
+use crate::mcp::server::ToolHandlerAsyncFn;
+use crate::mcp::{CallToolParams, CallToolResult, Error, Result, Tool};
+use rpc_router::RpcError;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::error;
+
+/// The tools registered on a `Server`, in registration order.
+#[derive(Clone, Default)]
+pub(crate) struct ToolRegistry {
+	entries: Vec<ToolEntry>,
+}
+
+#[derive(Clone)]
+struct ToolEntry {
+	tool: Tool,
+	handler: Arc<Box<dyn ToolHandlerAsyncFn>>,
+}
+
+impl ToolRegistry {
+	/// Registers a tool. A tool with the same name gets replaced.
+	pub(crate) fn register(&mut self, tool: Tool, handler: Arc<Box<dyn ToolHandlerAsyncFn>>) {
+		let entry = ToolEntry { tool, handler };
+		match self.entries.iter_mut().find(|e| e.tool.name == entry.tool.name) {
+			Some(existing) => *existing = entry,
+			None => self.entries.push(entry),
+		}
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	pub(crate) fn tools(&self) -> Vec<Tool> {
+		self.entries.iter().map(|e| e.tool.clone()).collect()
+	}
+
+	/// Calls the tool handler.
+	///
+	/// - Unknown tool or invalid arguments are returned as JSON-RPC errors (`Error::Rpc`).
+	/// - Errors from the tool function are returned as a `CallToolResult` with `is_error: true`,
+	///   unless they are already JSON-RPC errors (`Error::Rpc` or `Error::McpError`).
+	pub(crate) async fn call(&self, params: CallToolParams) -> Result<CallToolResult> {
+		let CallToolParams { name, arguments, .. } = params;
+
+		let Some(entry) = self.entries.iter().find(|e| e.tool.name == name) else {
+			return Err(RpcError::from_invalid_params(Some(format!("Unknown tool: {name}").into())).into());
+		};
+
+		let arguments = Value::Object(arguments.map(Map::from_iter).unwrap_or_default());
+		let fut = entry.handler.exec_fn(arguments);
+
+		// Note: Run in its own task, so that a panicking tool becomes an error response.
+		let res = match tokio::spawn(fut).await {
+			Ok(res) => res,
+			Err(join_err) => {
+				error!(tool = %name, "Tool function failed. Cause: {join_err}");
+				return Err(RpcError::from_internal_error(Some(format!("Tool '{name}' failed").into())).into());
+			}
+		};
+
+		match res {
+			Ok(call_tool_result) => Ok(call_tool_result),
+			Err(err @ (Error::Rpc(_) | Error::McpError(_))) => Err(err),
+			Err(Error::Custom(message)) => Ok(CallToolResult::from_error_text(message)),
+			Err(err) => Ok(CallToolResult::from_error_text(err.to_string())),
+		}
+	}
+}
@@ -0,0 +1,28 @@
+use crate::mcp::transport::{CommRx, CommTx};
+
+/// The `Server` side of the channels between the server session and its transport.
+pub struct ServerTrx {
+	pub c2s_rx: CommRx,
+	pub s2c_tx: CommTx,
+}
+
+/// The transport side of the channels between the server session and its transport.
+pub struct TransportTrx {
+	pub c2s_tx: CommTx,
+	pub s2c_rx: CommRx,
+}
+
+pub fn new_trx_pair() -> (ServerTrx, TransportTrx) {
+	let (c2s_tx, c2s_rx) = flume::unbounded::<String>();
+	let (s2c_tx, s2c_rx) = flume::unbounded::<String>();
+
+	let server_trx = ServerTrx {
+		c2s_rx: c2s_rx.into(),
+		s2c_tx: s2c_tx.into(),
+	};
+	let transport_trx = TransportTrx {
+		c2s_tx: c2s_tx.into(),
+		s2c_rx: s2c_rx.into(),
+	};
+	(server_trx, transport_trx)
+}
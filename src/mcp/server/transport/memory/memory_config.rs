@@ -0,0 +1,19 @@
+use crate::mcp::transport::{CommRx, CommTx};
+
+/// The server end of an in-memory duplex (see `ClientMemoryTransportConfig::new_duplex()`).
+///
+/// Serve it with `Server::serve(..)`, or take its raw channels to script a fake server.
+pub struct ServerMemoryTransportConfig {
+	pub(crate) c2s_rx: CommRx,
+	pub(crate) s2c_tx: CommTx,
+}
+
+impl ServerMemoryTransportConfig {
+	/// The raw channels of the server end (the client messages, and the sender of the server messages),
+	/// to script a fake server.
+	///
+	/// NOTE: Dropping the `CommTx` disconnects the client.
+	pub fn into_channels(self) -> (CommRx, CommTx) {
+		(self.c2s_rx, self.s2c_tx)
+	}
+}
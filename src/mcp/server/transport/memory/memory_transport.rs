@@ -0,0 +1,58 @@
+use crate::mcp::server::transport::TransportTrx;
+use crate::mcp::server::transport::memory::memory_config::ServerMemoryTransportConfig;
+use crate::mcp::{Error, Result};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+/// The in-memory transport (server side), relaying the messages of the duplex client end.
+pub struct ServerMemoryTransport {
+	config: Option<ServerMemoryTransportConfig>,
+	s2c_handle: Option<JoinHandle<()>>,
+}
+
+/// Lifecycle - start & join
+impl ServerMemoryTransport {
+	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
+		let TransportTrx { c2s_tx, s2c_rx } = transport_trx;
+		let ServerMemoryTransportConfig {
+			c2s_rx: peer_c2s_rx,
+			s2c_tx: peer_s2c_tx,
+		} = self
+			.config
+			.take()
+			.ok_or_else(|| Error::custom("In-memory server transport already started"))?;
+
+		// -- Client to server
+		// NOTE: When the client end closes, c2s_tx is dropped, which ends the session.
+		tokio::spawn(async move {
+			peer_c2s_rx.forward_to(&c2s_tx).await;
+			debug!("In-memory c2s ended");
+		});
+
+		// -- Server to client
+		let s2c_handle = tokio::spawn(async move {
+			s2c_rx.forward_to(&peer_s2c_tx).await;
+			debug!("In-memory s2c ended");
+		});
+		self.s2c_handle = Some(s2c_handle);
+
+		Ok(())
+	}
+
+	/// Waits until all the server messages have been relayed to the client end.
+	pub(crate) async fn join(&mut self) -> Result<()> {
+		if let Some(s2c_handle) = self.s2c_handle.take() {
+			s2c_handle.await.map_err(Error::custom_from_err)?;
+		}
+		Ok(())
+	}
+}
+
+impl From<ServerMemoryTransportConfig> for ServerMemoryTransport {
+	fn from(config: ServerMemoryTransportConfig) -> Self {
+		Self {
+			config: Some(config),
+			s2c_handle: None,
+		}
+	}
+}
@@ -0,0 +1,17 @@
+// region:    --- Modules
+
+mod comm_trx;
+mod memory;
+mod server_transport;
+mod stdio;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+pub use comm_trx::*;
+pub use memory::*;
+pub use server_transport::*;
+pub use stdio::*;
+#[cfg(feature = "websocket")]
+pub use websocket::*;
+
+// endregion: --- Modules
@@ -0,0 +1,130 @@
+use super::comm_trx::{TransportTrx, new_trx_pair};
+use crate::mcp::server::transport::ServerMemoryTransport;
+use crate::mcp::server::transport::stdio::ServerStdioTransport;
+#[cfg(feature = "websocket")]
+use crate::mcp::server::transport::{ServerWebSocketTransport, ServerWebSocketTransportConfig};
+use crate::mcp::server::{self, IntoServerTransport, Server, ServerMemoryTransportConfig, ServerStdioTransportConfig};
+use crate::mcp::{Error, Result};
+use derive_more::From;
+
+#[derive(From)]
+#[allow(clippy::enum_variant_names)]
+pub enum ServerTransport {
+	StdioTransport(ServerStdioTransport),
+	MemoryTransport(ServerMemoryTransport),
+	#[cfg(feature = "websocket")]
+	WebSocketTransport(ServerWebSocketTransport),
+}
+
+impl ServerTransport {
+	/// Serves the server over this transport: one session (e.g., until stdin closes),
+	/// or, for WebSocket, a session per connection (until the listener fails).
+	pub(crate) async fn serve(&mut self, server: &Server) -> Result<()> {
+		#[cfg(feature = "websocket")]
+		if let ServerTransport::WebSocketTransport(transport) = self {
+			return transport.serve(server).await;
+		}
+
+		// -- Create the Trx Pair
+		let (server_trx, transport_trx) = new_trx_pair();
+
+		// -- Start the transport
+		self.start(transport_trx).await?;
+
+		// -- Run the session
+		server.serve_trx(server_trx).await?;
+
+		// -- Wait for the last messages to be delivered
+		self.join().await
+	}
+
+	/// Starts the transport of a single session.
+	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
+		match self {
+			ServerTransport::StdioTransport(transport) => transport.start(transport_trx).await?,
+			ServerTransport::MemoryTransport(transport) => transport.start(transport_trx).await?,
+			#[cfg(feature = "websocket")]
+			ServerTransport::WebSocketTransport(_) => {
+				return Err(Error::custom(
+					"The WebSocket transport runs a session per connection (see serve)",
+				));
+			}
+		};
+		Ok(())
+	}
+
+	/// Waits for the transport to deliver the last server messages.
+	pub(crate) async fn join(&mut self) -> Result<()> {
+		match self {
+			ServerTransport::StdioTransport(transport) => transport.join().await?,
+			ServerTransport::MemoryTransport(transport) => transport.join().await?,
+			#[cfg(feature = "websocket")]
+			ServerTransport::WebSocketTransport(_) => (),
+		};
+		Ok(())
+	}
+}
+
+// region:    --- Froms
+
+impl From<ServerStdioTransportConfig> for ServerTransport {
+	fn from(config: ServerStdioTransportConfig) -> Self {
+		let transport = ServerStdioTransport::from(config);
+		transport.into()
+	}
+}
+
+impl From<ServerMemoryTransportConfig> for ServerTransport {
+	fn from(config: ServerMemoryTransportConfig) -> Self {
+		let transport = ServerMemoryTransport::from(config);
+		transport.into()
+	}
+}
+
+#[cfg(feature = "websocket")]
+impl From<ServerWebSocketTransportConfig> for ServerTransport {
+	fn from(config: ServerWebSocketTransportConfig) -> Self {
+		let transport = ServerWebSocketTransport::from(config);
+		transport.into()
+	}
+}
+
+// endregion: --- Froms
+
+// region:    --- IntoServerTransport Impls
+
+/// Implement the public trait for the public config type.
+impl IntoServerTransport for ServerStdioTransportConfig {
+	fn into_server_transport(self) -> ServerTransport {
+		ServerTransport::from(self)
+	}
+}
+impl server::into_server_transport::Sealed for ServerStdioTransportConfig {}
+
+/// Implement the public trait for the public config type.
+impl IntoServerTransport for ServerMemoryTransportConfig {
+	fn into_server_transport(self) -> ServerTransport {
+		ServerTransport::from(self)
+	}
+}
+impl server::into_server_transport::Sealed for ServerMemoryTransportConfig {}
+
+/// Implement the public trait for the public config type.
+#[cfg(feature = "websocket")]
+impl IntoServerTransport for ServerWebSocketTransportConfig {
+	fn into_server_transport(self) -> ServerTransport {
+		ServerTransport::from(self)
+	}
+}
+#[cfg(feature = "websocket")]
+impl server::into_server_transport::Sealed for ServerWebSocketTransportConfig {}
+
+/// Identity implementation for internal consistency/use.
+impl IntoServerTransport for ServerTransport {
+	fn into_server_transport(self) -> ServerTransport {
+		self
+	}
+}
+impl server::into_server_transport::Sealed for ServerTransport {}
+
+// endregion: --- IntoServerTransport Impls
@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod stdio_config;
+mod stdio_transport;
+
+pub use stdio_config::*;
+pub use stdio_transport::*;
+
+// endregion: --- Modules
@@ -0,0 +1,12 @@
+/// Serves MCP over the current process stdin/stdout (newline-delimited JSON-RPC).
+///
+/// NOTE: stdout is reserved for the MCP messages, so logs (e.g., tracing) must be written to stderr.
+#[derive(Debug, Default)]
+pub struct ServerStdioTransportConfig {}
+
+impl ServerStdioTransportConfig {
+	/// Same as default (for API consistency)
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
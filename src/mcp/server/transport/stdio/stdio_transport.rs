@@ -0,0 +1,117 @@
+use crate::mcp::server::transport::TransportTrx;
+use crate::mcp::server::transport::stdio::stdio_config::ServerStdioTransportConfig;
+use crate::mcp::support::truncate;
+use crate::mcp::{Error, Result};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader, Stdout};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
+
+pub struct ServerStdioTransport {
+	#[allow(unused)]
+	config: Arc<ServerStdioTransportConfig>,
+	stdout_handle: Option<JoinHandle<()>>,
+}
+
+/// Lifecycle - start & join
+impl ServerStdioTransport {
+	pub(crate) async fn start(&mut self, transport_trx: TransportTrx) -> Result<()> {
+		let TransportTrx { c2s_tx, s2c_rx } = transport_trx;
+
+		// -- STDIN (line by line)
+		// Read the process stdin and send each message via c2s_tx to the server session.
+		// NOTE: When stdin is closed, c2s_tx is dropped, which ends the session.
+		tokio::spawn(async move {
+			let reader = BufReader::new(tokio::io::stdin());
+			let mut lines = reader.lines();
+
+			loop {
+				match lines.next_line().await {
+					Ok(Some(line)) => {
+						if line.trim().is_empty() {
+							continue;
+						}
+						debug!(payload_excerpt = %truncate(&line, 64), "message received");
+						if let Err(err) = c2s_tx.send(line).await {
+							error!(%err, "while sending stdin line");
+							break;
+						}
+					}
+					Ok(None) => {
+						// End of stream
+						break;
+					}
+					Err(e) => {
+						error!("Error reading stdin line: {}", e);
+						break;
+					}
+				}
+			}
+			info!("STDIN Task Ended");
+		});
+
+		// -- STDOUT
+		// Listen to the s2c_rx and write each message as one line to the process stdout.
+		let stdout_handle = tokio::spawn(async move {
+			let mut stdout = tokio::io::stdout();
+			while let Ok(txt) = s2c_rx.recv().await {
+				if let Err(err) = send_to_stdout(&mut stdout, &txt).await {
+					error!("ERROR sending to stdout. Cause: {err}");
+					break;
+				}
+			}
+			info!("STDOUT Task Ended");
+		});
+
+		self.stdout_handle = Some(stdout_handle);
+
+		Ok(())
+	}
+
+	/// Waits until all the server messages have been written to stdout.
+	///
+	/// NOTE: Resolves once all of the session `s2c_tx` senders have been dropped.
+	pub(crate) async fn join(&mut self) -> Result<()> {
+		if let Some(stdout_handle) = self.stdout_handle.take() {
+			stdout_handle.await.map_err(Error::custom_from_err)?;
+		}
+		Ok(())
+	}
+}
+
+// region:    --- Froms
+
+impl From<ServerStdioTransportConfig> for ServerStdioTransport {
+	fn from(config: ServerStdioTransportConfig) -> Self {
+		let config = Arc::new(config);
+		Self {
+			config,
+			stdout_handle: None,
+		}
+	}
+}
+
+// endregion: --- Froms
+
+// region:    --- Support
+
+async fn send_to_stdout(stdout: &mut Stdout, payload: &str) -> Result<()> {
+	debug!(payload_excerpt = %truncate(payload, 64), "sending message");
+
+	stdout
+		.write_all(payload.as_bytes())
+		.await
+		.map_err(|e| Error::custom(format!("Error writing payload to stdout: {e}")))?;
+	stdout
+		.write_all(b"\n")
+		.await
+		.map_err(|e| Error::custom(format!("Error writing newline to stdout: {e}")))?;
+	stdout
+		.flush()
+		.await
+		.map_err(|e| Error::custom(format!("Error flushing stdout: {e}")))?;
+
+	Ok(())
+}
+
+// endregion: --- Support
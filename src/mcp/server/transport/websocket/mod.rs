@@ -0,0 +1,9 @@
+// region:    --- Modules
+
+mod websocket_config;
+mod websocket_transport;
+
+pub use websocket_config::*;
+pub use websocket_transport::*;
+
+// endregion: --- Modules
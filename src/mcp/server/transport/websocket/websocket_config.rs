@@ -0,0 +1,69 @@
+use crate::mcp::transport::WsKeepalive;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// The default max time for a connection to complete the WebSocket handshake.
+pub const DEFAULT_WEBSOCKET_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves MCP over WebSocket (each MCP message being a text frame), with a server session per connection.
+///
+/// ```ignore
+/// server.serve(ServerWebSocketTransportConfig::new("127.0.0.1:3001")).await?;
+/// ```
+///
+/// NOTE: No TLS (`ws://`), so, for `wss://`, put it behind a TLS terminating proxy.
+pub struct ServerWebSocketTransportConfig {
+	pub(crate) bind: WebSocketBind,
+	pub(crate) keepalive: WsKeepalive,
+	/// A stalled peer gets dropped after it (so it does not hold a task forever).
+	pub(crate) handshake_timeout: Duration,
+}
+
+pub(crate) enum WebSocketBind {
+	Addr(String),
+	/// An already bound listener (e.g., on port 0, to know the port before serving).
+	Listener(Mutex<Option<TcpListener>>),
+}
+
+/// Constructors
+impl ServerWebSocketTransportConfig {
+	/// Binds this address (e.g., `127.0.0.1:3001`) on serve.
+	pub fn new(addr: impl Into<String>) -> Self {
+		Self {
+			bind: WebSocketBind::Addr(addr.into()),
+			keepalive: WsKeepalive::default(),
+			handshake_timeout: DEFAULT_WEBSOCKET_HANDSHAKE_TIMEOUT,
+		}
+	}
+
+	/// Accepts the connections of this listener.
+	pub fn from_listener(listener: TcpListener) -> Self {
+		Self {
+			bind: WebSocketBind::Listener(Mutex::new(Some(listener))),
+			keepalive: WsKeepalive::default(),
+			handshake_timeout: DEFAULT_WEBSOCKET_HANDSHAKE_TIMEOUT,
+		}
+	}
+}
+
+/// Builders
+impl ServerWebSocketTransportConfig {
+	/// The interval of the keepalive pings (default `DEFAULT_WEBSOCKET_PING_INTERVAL`), None for no pings.
+	pub fn with_ping_interval(mut self, ping_interval: Option<Duration>) -> Self {
+		self.keepalive.ping_interval = ping_interval;
+		self
+	}
+
+	/// How long to wait for a frame after a ping (default `DEFAULT_WEBSOCKET_PONG_TIMEOUT`).
+	pub fn with_pong_timeout(mut self, pong_timeout: Duration) -> Self {
+		self.keepalive.pong_timeout = pong_timeout;
+		self
+	}
+
+	/// The max time for a connection to complete the handshake (default `DEFAULT_WEBSOCKET_HANDSHAKE_TIMEOUT`).
+	pub fn with_handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+		self.handshake_timeout = handshake_timeout;
+		self
+	}
+}
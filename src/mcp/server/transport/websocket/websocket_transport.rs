@@ -0,0 +1,81 @@
+use crate::mcp::server::Server;
+use crate::mcp::server::transport::websocket::websocket_config::{ServerWebSocketTransportConfig, WebSocketBind};
+use crate::mcp::server::transport::{TransportTrx, new_trx_pair};
+use crate::mcp::transport::{WsKeepalive, run_ws_conn};
+use crate::mcp::{Error, Result};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+/// The WebSocket transport (server side), running a server session per connection.
+pub struct ServerWebSocketTransport {
+	config: ServerWebSocketTransportConfig,
+}
+
+/// Lifecycle - serve
+impl ServerWebSocketTransport {
+	/// Accepts the connections (a server session each), until the listener fails.
+	pub(crate) async fn serve(&mut self, server: &Server) -> Result<()> {
+		let listener = match &self.config.bind {
+			WebSocketBind::Addr(addr) => TcpListener::bind(addr.as_str()).await.map_err(Error::custom_from_err)?,
+			WebSocketBind::Listener(listener) => listener
+				.lock()
+				.unwrap_or_else(|e| e.into_inner())
+				.take()
+				.ok_or_else(|| Error::custom("WebSocket listener already served"))?,
+		};
+		if let Ok(local_addr) = listener.local_addr() {
+			info!(%local_addr, "WebSocket server listening");
+		}
+
+		loop {
+			let (stream, peer_addr) = listener.accept().await.map_err(Error::custom_from_err)?;
+			debug!(%peer_addr, "WebSocket connection accepted");
+			let server = server.clone();
+			let keepalive = self.config.keepalive;
+			let handshake_timeout = self.config.handshake_timeout;
+			tokio::spawn(async move {
+				if let Err(err) = serve_connection(server, stream, keepalive, handshake_timeout).await {
+					warn!(%peer_addr, "WebSocket connection failed. Cause: {err}");
+				}
+			});
+		}
+	}
+}
+
+impl From<ServerWebSocketTransportConfig> for ServerWebSocketTransport {
+	fn from(config: ServerWebSocketTransportConfig) -> Self {
+		Self { config }
+	}
+}
+
+// region:    --- Support
+
+/// Runs the handshake, then a server session over the connection (until either side closes).
+async fn serve_connection(
+	server: Server,
+	stream: TcpStream,
+	keepalive: WsKeepalive,
+	handshake_timeout: Duration,
+) -> Result<()> {
+	let ws = tokio::time::timeout(handshake_timeout, tokio_tungstenite::accept_async(stream))
+		.await
+		.map_err(|_| Error::custom(format!("WebSocket handshake timed out after {handshake_timeout:?}")))?
+		.map_err(Error::custom_from_err)?;
+
+	// NOTE: When the connection ends, c2s_tx is dropped, which ends the session.
+	let (server_trx, transport_trx) = new_trx_pair();
+	let session = tokio::spawn(async move { server.serve_trx(server_trx).await });
+
+	let TransportTrx { c2s_tx, s2c_rx } = transport_trx;
+	let closed_error = run_ws_conn(ws, &s2c_rx, &c2s_tx, keepalive).await;
+	drop(c2s_tx);
+	session.await.map_err(Error::custom_from_err)??;
+
+	match closed_error {
+		Some(err) => Err(Error::custom(err.to_string())),
+		None => Ok(()),
+	}
+}
+
+// endregion: --- Support
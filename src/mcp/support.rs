@@ -1,3 +1,7 @@
+use crate::mcp::Error;
+use rpc_router::RpcError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde::ser::{SerializeMap, Serializer};
 use serde_json::Value;
 
@@ -43,3 +47,17 @@ pub fn truncate(s: &str, max: usize) -> String {
 }
 
 // endregion: --- Text
+
+// region:    --- Request Params & Results
+
+/// Deserializes the request params. Missing params are treated as an empty object.
+pub(crate) fn parse_params<P: DeserializeOwned>(params: Option<Value>) -> crate::mcp::Result<P> {
+	let params = params.unwrap_or_else(|| Value::Object(Default::default()));
+	serde_json::from_value(params).map_err(|err| RpcError::from_invalid_params(Some(err.to_string().into())).into())
+}
+
+pub(crate) fn to_result_value(result: impl Serialize) -> crate::mcp::Result<Value> {
+	serde_json::to_value(result).map_err(Error::custom_from_err)
+}
+
+// endregion: --- Request Params & Results
@@ -0,0 +1,253 @@
+use crate::mcp::{Implementation, McpError};
+use rpc_router::RpcError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// The script of a `MockServer` (serializable, to run the same script in-memory and over stdio).
+///
+/// Besides the expectations, the mock answers `initialize` (with the requested protocol version) and `ping`,
+/// and replies a method not found error to any other request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockScript {
+	pub server_info: Implementation,
+
+	/// The `InitializeResult` capabilities. When None, derived from the methods of the expectations.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub capabilities: Option<Value>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub instructions: Option<String>,
+
+	/// The actions run once the client sent the `notifications/initialized`.
+	#[serde(default)]
+	pub on_initialized: Vec<MockAction>,
+
+	/// The first matching expectation (with remaining times) replies to the request.
+	#[serde(default)]
+	pub expectations: Vec<MockExpectation>,
+}
+
+/// Support
+impl MockScript {
+	/// The capabilities to advertise (the set ones, or derived from the expectations).
+	pub(crate) fn capabilities(&self) -> Value {
+		if let Some(capabilities) = self.capabilities.as_ref() {
+			return capabilities.clone();
+		}
+		let has_prefix = |prefix: &str| self.expectations.iter().any(|e| e.method.starts_with(prefix));
+		let mut capabilities = json!({});
+		if has_prefix("tools/") {
+			capabilities["tools"] = json!({});
+		}
+		if has_prefix("resources/") {
+			capabilities["resources"] = json!({"subscribe": has_prefix("resources/subscribe")});
+		}
+		if has_prefix("prompts/") {
+			capabilities["prompts"] = json!({});
+		}
+		if has_prefix("completion/") {
+			capabilities["completions"] = json!({});
+		}
+		if has_prefix("logging/") {
+			capabilities["logging"] = json!({});
+		}
+		capabilities
+	}
+}
+
+// region:    --- MockExpectation
+
+/// A request expectation: "on `method` (with these params), run these actions, then reply this".
+///
+/// ```ignore
+/// let expectation = MockExpectation::new("tools/call")
+///     .with_params(json!({"name": "add"}))
+///     .with_action(MockAction::delay(200))
+///     .respond(CallToolResult::from_text("3"));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockExpectation {
+	pub method: String,
+
+	/// Matches when the request params contain these (recursively, for the objects).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub params: Option<Value>,
+
+	/// How many requests this expectation replies to. None for no limit.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub times: Option<u32>,
+
+	/// The actions run before the reply (e.g., a delay, a notification, a sampling request).
+	#[serde(default)]
+	pub actions: Vec<MockAction>,
+
+	pub reply: MockReply,
+}
+
+/// Constructor & Builders
+impl MockExpectation {
+	/// A new expectation for this method (replying an empty result until `respond..` is called).
+	pub fn new(method: impl Into<String>) -> Self {
+		Self {
+			method: method.into(),
+			params: None,
+			times: None,
+			actions: Vec::new(),
+			reply: MockReply::Result { result: json!({}) },
+		}
+	}
+
+	pub fn with_params(mut self, params: Value) -> Self {
+		self.params = Some(params);
+		self
+	}
+
+	pub fn with_times(mut self, times: u32) -> Self {
+		self.times = Some(times);
+		self
+	}
+
+	pub fn with_action(mut self, action: MockAction) -> Self {
+		self.actions.push(action);
+		self
+	}
+
+	/// Replies with this result (e.g., a `CallToolResult`, or a `serde_json::Value`).
+	///
+	/// NOTE: A result that cannot be serialized is replied as `null`.
+	pub fn respond(mut self, result: impl Serialize) -> Self {
+		let result = serde_json::to_value(result).unwrap_or_default();
+		self.reply = MockReply::Result { result };
+		self
+	}
+
+	/// Replies with the error of this `McpError` (its id is the one of the request).
+	pub fn respond_error(mut self, error: McpError) -> Self {
+		self.reply = MockReply::Error { error: error.error };
+		self
+	}
+}
+
+/// Support
+impl MockExpectation {
+	pub(crate) fn matches(&self, method: &str, params: Option<&Value>) -> bool {
+		if self.method != method {
+			return false;
+		}
+		match (self.params.as_ref(), params) {
+			(None, _) => true,
+			(Some(expected), Some(actual)) => json_contains(actual, expected),
+			(Some(_), None) => false,
+		}
+	}
+}
+
+/// The reply of a `MockExpectation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MockReply {
+	Result { result: Value },
+	Error { error: RpcError },
+}
+
+// endregion: --- MockExpectation
+
+// region:    --- MockAction
+
+/// An action of the mock server (run on initialized, or before an expectation reply).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MockAction {
+	/// Waits for this many milliseconds.
+	Delay { ms: u64 },
+	/// Sends a notification to the client.
+	Notify {
+		method: String,
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		params: Option<Value>,
+	},
+	/// Sends a request to the client (e.g., `sampling/createMessage`), and waits for its response.
+	Request {
+		method: String,
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		params: Option<Value>,
+	},
+	/// Sends this message as is (e.g., a JSON-RPC batch, or a response to a cancelled request).
+	Send { message: Value },
+}
+
+/// Constructors
+impl MockAction {
+	pub fn delay(ms: u64) -> Self {
+		Self::Delay { ms }
+	}
+
+	/// NOTE: Params that cannot be serialized are sent as `null`.
+	pub fn notify(method: impl Into<String>, params: impl Serialize) -> Self {
+		Self::Notify {
+			method: method.into(),
+			params: Some(serde_json::to_value(params).unwrap_or_default()),
+		}
+	}
+
+	/// NOTE: Params that cannot be serialized are sent as `null`.
+	pub fn request(method: impl Into<String>, params: impl Serialize) -> Self {
+		Self::Request {
+			method: method.into(),
+			params: Some(serde_json::to_value(params).unwrap_or_default()),
+		}
+	}
+
+	pub fn send(message: Value) -> Self {
+		Self::Send { message }
+	}
+}
+
+// endregion: --- MockAction
+
+// region:    --- Support
+
+/// Returns true if the `actual` value contains the `expected` one
+/// (the expected object properties, recursively; otherwise, equal values).
+fn json_contains(actual: &Value, expected: &Value) -> bool {
+	match (actual, expected) {
+		(Value::Object(actual), Value::Object(expected)) => expected
+			.iter()
+			.all(|(name, expected)| actual.get(name).is_some_and(|actual| json_contains(actual, expected))),
+		(Value::Number(actual), Value::Number(expected)) => actual.as_f64() == expected.as_f64(),
+		_ => actual == expected,
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_mock_expectation_matches() -> Result<()> {
+		// -- Setup & Fixtures
+		let expectation = MockExpectation::new("tools/call").with_params(json!({"name": "add", "arguments": {"a": 1}}));
+
+		// -- Check
+		assert!(expectation.matches(
+			"tools/call",
+			Some(&json!({"name": "add", "arguments": {"a": 1.0, "b": 2}}))
+		));
+		assert!(!expectation.matches("tools/call", Some(&json!({"name": "add", "arguments": {"a": 2}}))));
+		assert!(!expectation.matches("tools/call", Some(&json!({"name": "echo"}))));
+		assert!(!expectation.matches("tools/call", None));
+		assert!(!expectation.matches("tools/list", None));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,311 @@
+use crate::mcp::client::ClientMemoryTransportConfig;
+use crate::mcp::server::ServerStdioTransportConfig;
+use crate::mcp::server::transport::{ServerTransport, ServerTrx, new_trx_pair};
+use crate::mcp::testing::{MockAction, MockExpectation, MockReply, MockScript};
+use crate::mcp::transport::{CommRx, CommTx};
+use crate::mcp::{
+	CallToolParams, CallToolResult, Implementation, InitializeParams, InitializedNotificationParams,
+	LATEST_PROTOCOL_VERSION, PingParams, Result,
+};
+use crate::mcp::{IntoMcpNotification as _, IntoMcpRequest as _};
+use rpc_router::RpcError;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tracing::{debug, warn};
+
+/// The max time a `MockAction::Request` waits for the client response.
+const MOCK_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A mock MCP server replying per its `MockScript`, in-memory or over stdio
+/// (e.g., with the `agentic-mock-server` binary).
+///
+/// ```ignore
+/// let mock = MockServer::new("mock-server", "0.1.0")
+///     .on_tool_call("add", CallToolResult::from_text("3"))
+///     .on_request(MockExpectation::new("tools/call").with_params(json!({"name": "fail"})).respond_error(mcp_error))
+///     .on_initialized(MockAction::request("sampling/createMessage", sampling_params));
+///
+/// client.connect(mock.serve_in_memory()).await?;
+///
+/// let calls = mock.received_requests("tools/call");
+/// ```
+#[derive(Clone)]
+pub struct MockServer {
+	script: MockScript,
+	/// All of the messages received from the client.
+	received: Arc<Mutex<Vec<Value>>>,
+}
+
+/// Constructors
+impl MockServer {
+	pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+		Self::from_script(MockScript {
+			server_info: Implementation::new(name, version),
+			capabilities: None,
+			instructions: None,
+			on_initialized: Vec::new(),
+			expectations: Vec::new(),
+		})
+	}
+
+	pub fn from_script(script: MockScript) -> Self {
+		Self {
+			script,
+			received: Default::default(),
+		}
+	}
+}
+
+/// Builders
+impl MockServer {
+	/// Sets the capabilities (otherwise derived from the methods of the expectations).
+	pub fn with_capabilities(mut self, capabilities: Value) -> Self {
+		self.script.capabilities = Some(capabilities);
+		self
+	}
+
+	pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+		self.script.instructions = Some(instructions.into());
+		self
+	}
+
+	pub fn on_request(mut self, expectation: MockExpectation) -> Self {
+		self.script.expectations.push(expectation);
+		self
+	}
+
+	/// Replies this result to the `tools/call` of this tool name.
+	pub fn on_tool_call(self, name: impl Into<String>, result: CallToolResult) -> Self {
+		let expectation = MockExpectation::new(CallToolParams::METHOD)
+			.with_params(json!({"name": name.into()}))
+			.respond(result);
+		self.on_request(expectation)
+	}
+
+	/// Runs this action once the client sent the `notifications/initialized` (in order with the other ones).
+	pub fn on_initialized(mut self, action: MockAction) -> Self {
+		self.script.on_initialized.push(action);
+		self
+	}
+}
+
+/// Accessors
+impl MockServer {
+	pub fn script(&self) -> &MockScript {
+		&self.script
+	}
+
+	/// All of the messages received from the client (requests, notifications, and responses).
+	pub fn received(&self) -> Vec<Value> {
+		self.received.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// The received requests (or notifications) of this method.
+	pub fn received_requests(&self, method: &str) -> Vec<Value> {
+		let received = self.received.lock().unwrap_or_else(|e| e.into_inner());
+		received.iter().filter(|msg| msg["method"] == method).cloned().collect()
+	}
+}
+
+/// Serve
+impl MockServer {
+	/// Runs the mock in a new task, returning the config to connect a client to it.
+	pub fn serve_in_memory(&self) -> ClientMemoryTransportConfig {
+		let (transport_config, server_end) = ClientMemoryTransportConfig::new_duplex();
+		let (c2s_rx, s2c_tx) = server_end.into_channels();
+		let mock = self.clone();
+		tokio::spawn(async move { mock.serve_channels(c2s_rx, s2c_tx).await });
+		transport_config
+	}
+
+	/// Serves over the current process stdin/stdout, until stdin closes.
+	pub async fn serve_stdio(&self) -> Result<()> {
+		let (server_trx, transport_trx) = new_trx_pair();
+		let mut transport = ServerTransport::from(ServerStdioTransportConfig::new());
+		transport.start(transport_trx).await?;
+
+		let ServerTrx { c2s_rx, s2c_tx } = server_trx;
+		self.serve_channels(c2s_rx, s2c_tx).await;
+
+		transport.join().await?;
+		Ok(())
+	}
+
+	/// Replies to the client messages of the `c2s_rx`, until it closes (e.g., to serve it over a custom transport).
+	pub async fn serve_channels(&self, c2s_rx: CommRx, s2c_tx: CommTx) {
+		let session = Arc::new(MockSession {
+			script: self.script.clone(),
+			s2c_tx,
+			times_used: Mutex::new(vec![0; self.script.expectations.len()]),
+			pending_requests: Default::default(),
+			next_request_id: AtomicU64::new(1),
+		});
+
+		while let Ok(msg) = c2s_rx.recv().await {
+			let msg: Value = match serde_json::from_str(&msg) {
+				Ok(msg) => msg,
+				Err(err) => {
+					warn!("Mock server cannot parse the message. Cause: {err}");
+					continue;
+				}
+			};
+			// NOTE: Batches get handled message by message.
+			let msgs = match msg {
+				Value::Array(msgs) => msgs,
+				msg => vec![msg],
+			};
+			for msg in msgs {
+				self.received.lock().unwrap_or_else(|e| e.into_inner()).push(msg.clone());
+				session.clone().handle_message(msg);
+			}
+		}
+		debug!("Mock server session ended");
+	}
+}
+
+// region:    --- MockSession
+
+struct MockSession {
+	script: MockScript,
+	s2c_tx: CommTx,
+	/// The number of replies of each expectation (same index).
+	times_used: Mutex<Vec<u32>>,
+	/// The requests sent to the client (by `MockAction::Request`), by id.
+	pending_requests: Mutex<HashMap<String, oneshot::Sender<Value>>>,
+	next_request_id: AtomicU64,
+}
+
+impl MockSession {
+	fn handle_message(self: Arc<Self>, msg: Value) {
+		let method = msg.get("method").and_then(Value::as_str).map(str::to_string);
+		match (method, msg.get("id")) {
+			// -- Request
+			(Some(method), Some(id)) => {
+				let id = id.clone();
+				tokio::spawn(async move { self.handle_request(id, &method, msg.get("params")).await });
+			}
+			// -- Notification
+			(Some(method), None) => {
+				if method == InitializedNotificationParams::METHOD {
+					tokio::spawn(async move {
+						for action in self.script.on_initialized.iter() {
+							self.run_action(action).await;
+						}
+					});
+				}
+			}
+			// -- Response (to a `MockAction::Request`)
+			(None, Some(id)) => {
+				let pending = self
+					.pending_requests
+					.lock()
+					.unwrap_or_else(|e| e.into_inner())
+					.remove(&id.to_string());
+				match pending {
+					Some(res_tx) => {
+						let _ = res_tx.send(msg);
+					}
+					None => debug!(%id, "Mock server got a response to an unknown request"),
+				}
+			}
+			(None, None) => warn!("Mock server got an invalid message"),
+		}
+	}
+
+	async fn handle_request(&self, id: Value, method: &str, params: Option<&Value>) {
+		let reply = match self.take_expectation(method, params) {
+			Some(expectation) => {
+				for action in expectation.actions.iter() {
+					self.run_action(action).await;
+				}
+				expectation.reply
+			}
+			None => self.default_reply(method, params),
+		};
+
+		let res = match reply {
+			MockReply::Result { result } => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+			MockReply::Error { error } => json!({"jsonrpc": "2.0", "id": id, "error": error}),
+		};
+		self.send(res).await;
+	}
+
+	/// The first matching expectation with remaining times (counted as used).
+	fn take_expectation(&self, method: &str, params: Option<&Value>) -> Option<MockExpectation> {
+		let mut times_used = self.times_used.lock().unwrap_or_else(|e| e.into_inner());
+		let (idx, expectation) = self.script.expectations.iter().enumerate().find(|(idx, expectation)| {
+			expectation.matches(method, params) && expectation.times.is_none_or(|times| times_used[*idx] < times)
+		})?;
+		times_used[idx] += 1;
+		Some(expectation.clone())
+	}
+
+	/// The reply without expectation (`initialize`, `ping`, or method not found).
+	fn default_reply(&self, method: &str, params: Option<&Value>) -> MockReply {
+		if method == InitializeParams::METHOD {
+			let protocol_version = params
+				.and_then(|params| params.get("protocolVersion"))
+				.and_then(Value::as_str)
+				.unwrap_or(LATEST_PROTOCOL_VERSION);
+			let mut result = json!({
+				"protocolVersion": protocol_version,
+				"capabilities": self.script.capabilities(),
+				"serverInfo": self.script.server_info,
+			});
+			if let Some(instructions) = self.script.instructions.as_ref() {
+				result["instructions"] = json!(instructions);
+			}
+			MockReply::Result { result }
+		} else if method == PingParams::METHOD {
+			MockReply::Result { result: json!({}) }
+		} else {
+			MockReply::Error {
+				error: RpcError::from_method_not_found(Some(method.into())),
+			}
+		}
+	}
+
+	async fn run_action(&self, action: &MockAction) {
+		match action {
+			MockAction::Delay { ms } => tokio::time::sleep(Duration::from_millis(*ms)).await,
+			MockAction::Notify { method, params } => {
+				let mut notification = json!({"jsonrpc": "2.0", "method": method});
+				if let Some(params) = params {
+					notification["params"] = params.clone();
+				}
+				self.send(notification).await;
+			}
+			MockAction::Request { method, params } => {
+				let id = json!(format!("mock-{}", self.next_request_id.fetch_add(1, Ordering::Relaxed)));
+				let (res_tx, res_rx) = oneshot::channel();
+				self.pending_requests
+					.lock()
+					.unwrap_or_else(|e| e.into_inner())
+					.insert(id.to_string(), res_tx);
+
+				let mut request = json!({"jsonrpc": "2.0", "id": id, "method": method});
+				if let Some(params) = params {
+					request["params"] = params.clone();
+				}
+				self.send(request).await;
+
+				if tokio::time::timeout(MOCK_REQUEST_TIMEOUT, res_rx).await.is_err() {
+					warn!(%method, "Mock server request timed out");
+				}
+			}
+			MockAction::Send { message } => self.send(message.clone()).await,
+		}
+	}
+
+	async fn send(&self, msg: Value) {
+		if self.s2c_tx.send(msg.to_string()).await.is_err() {
+			debug!("Mock server client gone");
+		}
+	}
+}
+
+// endregion: --- MockSession
@@ -0,0 +1,11 @@
+//! A scriptable mock MCP server, to test the clients without a real server (see `MockServer`).
+
+// region:    --- Modules
+
+mod mock_script;
+mod mock_server;
+
+pub use mock_script::*;
+pub use mock_server::*;
+
+// endregion: --- Modules
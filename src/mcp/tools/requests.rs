@@ -159,4 +159,42 @@ pub struct CallToolResult {
 	pub is_error: Option<bool>,
 }
 
+/// Builders
+impl CallToolResult {
+	pub fn new(content: Vec<MessageContent>) -> Self {
+		Self {
+			meta: None,
+			content,
+			is_error: None,
+		}
+	}
+
+	/// Creates a result with a single text content.
+	pub fn from_text(text: impl Into<String>) -> Self {
+		Self::new(vec![MessageContent::new_text(text)])
+	}
+
+	/// Creates an error result (`is_error: true`) with a single text content.
+	///
+	/// This is how tool errors should be reported, so that the LLM can see them.
+	pub fn from_error_text(text: impl Into<String>) -> Self {
+		Self::from_text(text).with_is_error(true)
+	}
+
+	pub fn with_meta(mut self, meta: GenericMeta) -> Self {
+		self.meta = Some(meta);
+		self
+	}
+
+	pub fn with_is_error(mut self, is_error: bool) -> Self {
+		self.is_error = Some(is_error);
+		self
+	}
+
+	pub fn append_content(mut self, content: MessageContent) -> Self {
+		self.content.push(content);
+		self
+	}
+}
+
 // endregion: --- CallToolRequest
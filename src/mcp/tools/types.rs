@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use serde_with::skip_serializing_none;
 
 /// Definition for a tool the client can call.
@@ -69,18 +69,138 @@ impl ToolInputSchema {
 		Self::default()
 	}
 
+	/// An `"object"` schema without properties (the type of the tool input schemas).
+	pub fn object() -> Self {
+		Self {
+			schema_type: "object".to_string(),
+			..Default::default()
+		}
+	}
+
 	/// Note: Usually a Map<String, JsonSchema> represented as serde_json::Value
 	pub fn with_properties(mut self, properties: Value) -> Self {
 		self.properties = Some(properties);
 		self
 	}
 
+	/// Adds an optional property to the schema.
+	pub fn append_property(mut self, name: impl Into<String>, property: ToolInputProperty) -> Self {
+		let properties = self.properties.get_or_insert_with(|| Value::Object(Map::new()));
+		// Note: if properties was set to a non object value, it gets replaced.
+		if !properties.is_object() {
+			*properties = Value::Object(Map::new());
+		}
+		if let Value::Object(map) = properties {
+			map.insert(name.into(), property.into_value());
+		}
+		self
+	}
+
+	/// Adds a property to the schema and marks it as required.
+	pub fn append_required_property(self, name: impl Into<String>, property: ToolInputProperty) -> Self {
+		let name = name.into();
+		self.append_property(name.clone(), property).append_required(name)
+	}
+
 	pub fn append_required(mut self, required_prop: impl Into<String>) -> Self {
 		self.required.get_or_insert_with(Vec::new).push(required_prop.into());
 		self
 	}
 }
 
+/// The JSON Schema of one property of a `ToolInputSchema`.
+///
+/// Covers the common cases, so that tool schemas do not have to be written as raw JSON.
+/// Use `ToolInputSchema::with_properties` for anything more advanced.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolInputProperty {
+	/// The JSON Schema type (e.g., "string", "number", "integer", "boolean", "array", "object")
+	#[serde(rename = "type")]
+	pub property_type: String,
+
+	/// A human-readable description of the property (used by the LLM).
+	pub description: Option<String>,
+
+	/// The allowed values, if restricted.
+	#[serde(rename = "enum")]
+	pub enum_values: Option<Vec<Value>>,
+
+	/// The schema of the items, for the "array" type.
+	pub items: Option<Box<ToolInputProperty>>,
+
+	/// The default value.
+	pub default: Option<Value>,
+}
+
+/// Constructors
+impl ToolInputProperty {
+	pub fn new(property_type: impl Into<String>) -> Self {
+		Self {
+			property_type: property_type.into(),
+			description: None,
+			enum_values: None,
+			items: None,
+			default: None,
+		}
+	}
+
+	pub fn string() -> Self {
+		Self::new("string")
+	}
+
+	pub fn number() -> Self {
+		Self::new("number")
+	}
+
+	pub fn integer() -> Self {
+		Self::new("integer")
+	}
+
+	pub fn boolean() -> Self {
+		Self::new("boolean")
+	}
+
+	pub fn array(items: ToolInputProperty) -> Self {
+		Self::new("array").with_items(items)
+	}
+}
+
+/// Builders
+impl ToolInputProperty {
+	pub fn with_description(mut self, description: impl Into<String>) -> Self {
+		self.description = Some(description.into());
+		self
+	}
+
+	pub fn with_enum_values<I>(mut self, values: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<Value>,
+	{
+		self.enum_values = Some(values.into_iter().map(Into::into).collect());
+		self
+	}
+
+	pub fn with_items(mut self, items: ToolInputProperty) -> Self {
+		self.items = Some(Box::new(items));
+		self
+	}
+
+	pub fn with_default(mut self, default: impl Into<Value>) -> Self {
+		self.default = Some(default.into());
+		self
+	}
+}
+
+impl ToolInputProperty {
+	fn into_value(self) -> Value {
+		// Note: Cannot fail, as all fields are plain JSON values.
+		serde_json::to_value(self).unwrap_or_default()
+	}
+}
+
 /// Additional properties describing a Tool to clients.
 ///
 /// NOTE: all properties in ToolAnnotations are **hints**.
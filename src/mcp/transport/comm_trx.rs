@@ -0,0 +1,71 @@
+use crate::mcp::transport::Result;
+use flume::{Receiver, Sender};
+use tracing::error;
+
+// region:    --- CommTx
+
+#[derive(Clone)]
+pub struct CommTx {
+	tx: Sender<String>,
+}
+
+impl CommTx {
+	pub async fn send(&self, item: impl Into<String>) -> Result<()> {
+		match self.tx.send_async(item.into()).await {
+			Ok(_) => Ok(()),
+			Err(err) => {
+				error!("Cannot send to CommTx");
+				Err(err.into())
+			}
+		}
+	}
+
+	/// Sends without waiting (the channel is unbounded), for non-async contexts (e.g., `Drop`).
+	pub fn try_send(&self, item: impl Into<String>) -> Result<()> {
+		match self.tx.try_send(item.into()) {
+			Ok(_) => Ok(()),
+			Err(err) => {
+				error!("Cannot try_send to CommTx");
+				Err(err.into())
+			}
+		}
+	}
+}
+
+impl From<Sender<String>> for CommTx {
+	fn from(tx: Sender<String>) -> Self {
+		Self { tx }
+	}
+}
+
+// endregion: --- CommTx
+
+// region:    --- CommRx
+
+pub struct CommRx {
+	rx: Receiver<String>,
+}
+
+impl CommRx {
+	pub async fn recv(&self) -> Result<String> {
+		let res = self.rx.recv_async().await?;
+		Ok(res)
+	}
+
+	/// Forwards the received messages to the `tx`, until either side closes (e.g., the in-memory transport).
+	pub(crate) async fn forward_to(&self, tx: &CommTx) {
+		while let Ok(msg) = self.rx.recv_async().await {
+			if tx.tx.send_async(msg).await.is_err() {
+				break;
+			}
+		}
+	}
+}
+
+impl From<Receiver<String>> for CommRx {
+	fn from(rx: Receiver<String>) -> Self {
+		Self { rx }
+	}
+}
+
+// endregion: --- CommRx
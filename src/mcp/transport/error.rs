@@ -0,0 +1,58 @@
+use derive_more::{Display, From};
+use flume::{RecvError, SendError, TrySendError};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// The errors of the transport support shared by the client and the server (channels, WebSocket connection).
+#[derive(Debug, Display, From)]
+#[display("{self:?}")]
+pub enum Error {
+	CommSend(String),
+	#[from]
+	CommRecv(RecvError),
+
+	/// The WebSocket got closed by the peer with a non normal close code (1006 when lost without close frame).
+	WebSocketClosed {
+		code: u16,
+		reason: String,
+	},
+	/// No frame (e.g., pong) received within the pong timeout after a ping.
+	WebSocketKeepaliveTimeout,
+
+	// -- Externals
+	#[cfg(feature = "websocket")]
+	#[from]
+	WebSocket(tokio_tungstenite::tungstenite::Error),
+}
+
+// region:    --- Froms
+
+impl From<SendError<String>> for Error {
+	fn from(value: SendError<String>) -> Self {
+		Self::CommSend(value.to_string())
+	}
+}
+
+impl From<TrySendError<String>> for Error {
+	fn from(value: TrySendError<String>) -> Self {
+		Self::CommSend(value.to_string())
+	}
+}
+
+// endregion: --- Froms
+
+// region:    --- Intos
+
+impl From<Error> for crate::mcp::Error {
+	fn from(value: Error) -> Self {
+		crate::mcp::Error::Transport(value.to_string())
+	}
+}
+
+// endregion: --- Intos
+
+// region:    --- Error Boilerplate
+
+impl std::error::Error for Error {}
+
+// endregion: --- Error Boilerplate
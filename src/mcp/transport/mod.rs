@@ -0,0 +1,15 @@
+// region:    --- Modules
+
+mod comm_trx;
+mod error;
+#[cfg(feature = "websocket")]
+mod websocket_conn;
+
+pub use comm_trx::*;
+pub use error::{Error, Result};
+#[cfg(feature = "websocket")]
+pub use websocket_conn::{DEFAULT_WEBSOCKET_PING_INTERVAL, DEFAULT_WEBSOCKET_PONG_TIMEOUT};
+#[cfg(feature = "websocket")]
+pub(crate) use websocket_conn::{WsKeepalive, run_ws_conn};
+
+// endregion: --- Modules
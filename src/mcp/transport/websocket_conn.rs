@@ -0,0 +1,157 @@
+use crate::mcp::support::truncate;
+use crate::mcp::transport::{CommRx, CommTx, Error};
+use futures::{SinkExt as _, StreamExt as _};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::time::{Instant, Interval};
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tracing::{debug, warn};
+
+/// The default interval of the keepalive pings.
+pub const DEFAULT_WEBSOCKET_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The default wait for a frame (e.g., the pong) after a ping, before considering the connection lost.
+pub const DEFAULT_WEBSOCKET_PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The ping/pong keepalive of a WebSocket connection (client and server).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WsKeepalive {
+	/// None for no pings (the pings of the peer still get their pong).
+	pub ping_interval: Option<Duration>,
+	pub pong_timeout: Duration,
+}
+
+impl Default for WsKeepalive {
+	fn default() -> Self {
+		Self {
+			ping_interval: Some(DEFAULT_WEBSOCKET_PING_INTERVAL),
+			pong_timeout: DEFAULT_WEBSOCKET_PONG_TIMEOUT,
+		}
+	}
+}
+
+/// Runs a WebSocket connection, until either side closes:
+/// - The `out_rx` messages are sent as text frames (a normal close frame is sent when it closes).
+/// - The received text frames are sent to the `in_tx`.
+///
+/// Returns the error of the connection end (None for a normal close, from either side).
+pub(crate) async fn run_ws_conn<S>(
+	mut ws: WebSocketStream<S>,
+	out_rx: &CommRx,
+	in_tx: &CommTx,
+	keepalive: WsKeepalive,
+) -> Option<Error>
+where
+	S: AsyncRead + AsyncWrite + Unpin,
+{
+	let mut ping_ticker = keepalive.ping_interval.map(|ping_interval| {
+		let mut ticker = tokio::time::interval_at(Instant::now() + ping_interval, ping_interval);
+		ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+		ticker
+	});
+	// Set by a ping, cleared by any frame received.
+	let mut pong_deadline: Option<Instant> = None;
+
+	loop {
+		tokio::select! {
+			// -- Outgoing
+			msg = out_rx.recv() => {
+				let Ok(msg) = msg else {
+					// Our side is done, so, close normally.
+					let _ = ws.close(None).await;
+					return None;
+				};
+				if let Err(err) = ws.send(Message::text(msg)).await {
+					return Some(err.into());
+				}
+			}
+
+			// -- Incoming
+			frame = ws.next() => {
+				pong_deadline = None;
+				match frame {
+					Some(Ok(Message::Text(text))) => {
+						if in_tx.send(text.as_str()).await.is_err() {
+							let _ = ws.close(None).await;
+							return None;
+						}
+					}
+					// NOTE: Lenient, the binary frames of UTF-8 JSON are accepted as well.
+					Some(Ok(Message::Binary(bytes))) => match String::from_utf8(bytes.to_vec()) {
+						Ok(text) => {
+							if in_tx.send(text).await.is_err() {
+								let _ = ws.close(None).await;
+								return None;
+							}
+						}
+						Err(_) => warn!("WebSocket binary frame is not UTF-8. Ignored"),
+					},
+					// The pong reply is queued on read, so flush it right away.
+					Some(Ok(Message::Ping(_))) => {
+						if let Err(err) = ws.flush().await {
+							return Some(err.into());
+						}
+					}
+					Some(Ok(Message::Pong(_))) | Some(Ok(Message::Frame(_))) => (),
+					Some(Ok(Message::Close(frame))) => return close_frame_error(frame),
+					Some(Err(err)) => return Some(err.into()),
+					None => {
+						return Some(Error::WebSocketClosed {
+							code: u16::from(CloseCode::Abnormal),
+							reason: "Connection lost".to_string(),
+						});
+					}
+				}
+			}
+
+			// -- Keepalive
+			_ = tick(ping_ticker.as_mut()) => {
+				if let Err(err) = ws.send(Message::Ping(Default::default())).await {
+					return Some(err.into());
+				}
+				pong_deadline.get_or_insert(Instant::now() + keepalive.pong_timeout);
+			}
+			_ = sleep_until(pong_deadline) => {
+				let frame = CloseFrame { code: CloseCode::Away, reason: "Keepalive timeout".into() };
+				let _ = ws.close(Some(frame)).await;
+				return Some(Error::WebSocketKeepaliveTimeout);
+			}
+		}
+	}
+}
+
+// region:    --- Support
+
+/// The error of a close frame received (None for a normal close).
+fn close_frame_error(frame: Option<CloseFrame>) -> Option<Error> {
+	let frame = frame?;
+	if frame.code == CloseCode::Normal {
+		debug!("WebSocket closed by the peer");
+		return None;
+	}
+	let code = u16::from(frame.code);
+	let reason = frame.reason.to_string();
+	warn!(code, reason = %truncate(&reason, 128), "WebSocket closed by the peer");
+	Some(Error::WebSocketClosed { code, reason })
+}
+
+async fn tick(ticker: Option<&mut Interval>) {
+	match ticker {
+		Some(ticker) => {
+			ticker.tick().await;
+		}
+		None => std::future::pending().await,
+	}
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+	match deadline {
+		Some(deadline) => tokio::time::sleep_until(deadline).await,
+		None => std::future::pending().await,
+	}
+}
+
+// endregion: --- Support
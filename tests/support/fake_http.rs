@@ -0,0 +1,239 @@
+//! A raw HTTP server recording the requests (for the Streamable HTTP and legacy SSE transport tests).
+
+use super::{Result, fx_initialize_result};
+use agentic::mcp::client::auth::OAuthClient;
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use axum::response::Response;
+use futures::StreamExt as _;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+
+/// The session id given by the `fx_mcp_reply` on initialize.
+pub const FX_SESSION_ID: &str = "session-1";
+
+/// A request received by the `FakeHttpServer`.
+#[derive(Debug, Clone)]
+pub struct FakeHttpRequest {
+	pub method: Method,
+	/// The path and query (e.g., `/messages?sessionId=1`).
+	pub path: String,
+	pub headers: HeaderMap,
+	/// The JSON body (None for an empty or non JSON body).
+	pub body: Option<Value>,
+}
+
+impl FakeHttpRequest {
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers.get(name).and_then(|v| v.to_str().ok())
+	}
+
+	/// The JSON-RPC method of the body (None for a response, or no body).
+	pub fn rpc_method(&self) -> Option<&str> {
+		self.body.as_ref()?.get("method")?.as_str()
+	}
+}
+
+/// Serves every request (any path) with the `handler`, and records them.
+pub struct FakeHttpServer {
+	pub url: String,
+	requests: Arc<Mutex<Vec<FakeHttpRequest>>>,
+}
+
+impl FakeHttpServer {
+	pub async fn start(handler: impl Fn(&FakeHttpRequest) -> Response + Send + Sync + 'static) -> Result<Self> {
+		let listener = TcpListener::bind("127.0.0.1:0").await?;
+		let url = format!("http://{}/mcp", listener.local_addr()?);
+		let requests: Arc<Mutex<Vec<FakeHttpRequest>>> = Arc::default();
+
+		let handler = Arc::new(handler);
+		let recorded = requests.clone();
+		let app = axum::Router::new().fallback(move |req: axum::extract::Request| {
+			let handler = handler.clone();
+			let recorded = recorded.clone();
+			async move {
+				let (parts, body) = req.into_parts();
+				let body = axum::body::to_bytes(body, usize::MAX).await.unwrap_or_default();
+				let req = FakeHttpRequest {
+					method: parts.method,
+					path: parts.uri.path_and_query().map(|pq| pq.to_string()).unwrap_or_default(),
+					headers: parts.headers,
+					body: serde_json::from_slice(&body).ok(),
+				};
+				recorded.lock().unwrap_or_else(|e| e.into_inner()).push(req.clone());
+				handler(&req)
+			}
+		});
+		tokio::spawn(async move { axum::serve(listener, app).await });
+
+		Ok(Self { url, requests })
+	}
+
+	pub fn requests(&self) -> Vec<FakeHttpRequest> {
+		self.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// The requests with this JSON-RPC method.
+	pub fn requests_of(&self, rpc_method: &str) -> Vec<FakeHttpRequest> {
+		self.requests()
+			.into_iter()
+			.filter(|req| req.rpc_method() == Some(rpc_method))
+			.collect()
+	}
+
+	/// The requests with this HTTP method (e.g., the GET streams).
+	pub fn requests_with(&self, method: Method) -> Vec<FakeHttpRequest> {
+		self.requests().into_iter().filter(|req| req.method == method).collect()
+	}
+}
+
+// region:    --- Responses
+
+/// The replies of a basic Streamable HTTP server (JSON responses, the session id on initialize).
+///
+/// - `initialize`: the `fx_initialize_result()` with the requested protocol version.
+/// - Other requests: an empty result. Notifications and responses: 202.
+/// - GET: 405 (no standalone stream), DELETE: 200.
+pub fn fx_mcp_reply(req: &FakeHttpRequest) -> Response {
+	if req.method == Method::GET {
+		return status_response(StatusCode::METHOD_NOT_ALLOWED);
+	}
+	if req.method == Method::DELETE {
+		return status_response(StatusCode::OK);
+	}
+
+	if req.body.is_none() {
+		return status_response(StatusCode::BAD_REQUEST);
+	}
+	let Some(response) = fx_mcp_response(req) else {
+		return status_response(StatusCode::ACCEPTED);
+	};
+
+	let mut res = json_response(response);
+	if req.rpc_method() == Some("initialize") {
+		res.headers_mut()
+			.insert("mcp-session-id", HeaderValue::from_static(FX_SESSION_ID));
+	}
+	res
+}
+
+/// The JSON-RPC response of the `fx_mcp_reply` (None for a notification, a response, or no body).
+pub fn fx_mcp_response(req: &FakeHttpRequest) -> Option<Value> {
+	let body = req.body.as_ref()?;
+	let (Some(id), Some(method)) = (body.get("id"), req.rpc_method()) else {
+		return None;
+	};
+
+	if method == "initialize" {
+		let mut result = fx_initialize_result();
+		result["protocolVersion"] = body.pointer("/params/protocolVersion").cloned().unwrap_or_default();
+		Some(json!({"jsonrpc": "2.0", "id": id, "result": result}))
+	} else {
+		Some(json!({"jsonrpc": "2.0", "id": id, "result": {}}))
+	}
+}
+
+pub fn json_response(value: Value) -> Response {
+	Response::builder()
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from(value.to_string()))
+		.unwrap_or_default()
+}
+
+pub fn status_response(status: StatusCode) -> Response {
+	Response::builder().status(status).body(Body::empty()).unwrap_or_default()
+}
+
+/// An SSE response streaming the events sent to the returned sender (until it is dropped).
+///
+/// The events are raw SSE texts (see `sse_event`).
+pub fn sse_response() -> (mpsc::UnboundedSender<String>, Response) {
+	let (tx, rx) = mpsc::unbounded_channel::<String>();
+	let stream = futures::stream::unfold(rx, |mut rx| async move {
+		let event = rx.recv().await?;
+		Some((Ok::<_, std::io::Error>(event), rx))
+	});
+	let res = Response::builder()
+		.header(header::CONTENT_TYPE, "text/event-stream")
+		.body(Body::from_stream(stream.boxed()))
+		.unwrap_or_default();
+	(tx, res)
+}
+
+/// A JSON response whose body is only sent once given to the returned sender (e.g., a slow tool call).
+pub fn delayed_json_response() -> (oneshot::Sender<Value>, Response) {
+	let (tx, rx) = oneshot::channel::<Value>();
+	let stream = futures::stream::once(async move {
+		rx.await
+			.map(|value| value.to_string())
+			.map_err(|_| std::io::Error::other("Delayed response dropped"))
+	});
+	let res = Response::builder()
+		.header(header::CONTENT_TYPE, "application/json")
+		.body(Body::from_stream(stream.boxed()))
+		.unwrap_or_default();
+	(tx, res)
+}
+
+/// A raw SSE event (the `id` and `event` lines only when set).
+pub fn sse_event(id: Option<&str>, event: Option<&str>, data: &str) -> String {
+	let mut text = String::new();
+	if let Some(id) = id {
+		text.push_str(&format!("id: {id}\n"));
+	}
+	if let Some(event) = event {
+		text.push_str(&format!("event: {event}\n"));
+	}
+	text.push_str(&format!("data: {data}\n\n"));
+	text
+}
+
+// endregion: --- Responses
+
+// region:    --- OAuth
+
+/// The access token given by the `fx_oauth_reply` token endpoint.
+pub const FX_ACCESS_TOKEN: &str = "access-1";
+
+/// An `OAuthClient` with a pre-registered client, whose authorization handler plays the user authorizing.
+pub fn fx_oauth_client() -> OAuthClient {
+	OAuthClient::new("Demo Client", "http://127.0.0.1/callback")
+		.with_client_credentials("client-1", None)
+		.with_authorization_handler(|authorization_url: String| async move {
+			let url = reqwest::Url::parse(&authorization_url).map_err(|err| err.to_string())?;
+			let state = url
+				.query_pairs()
+				.find_map(|(name, value)| (name == "state").then(|| value.to_string()))
+				.unwrap_or_default();
+			Ok(format!("http://127.0.0.1/callback?code=code-1&state={state}"))
+		})
+}
+
+/// The replies of an authorization server on the MCP server origin (with the default endpoints),
+/// the MCP server requests without the `FX_ACCESS_TOKEN` being rejected.
+///
+/// - GET `/.well-known/oauth-authorization-server`: 404 (so, the default `/token` endpoint).
+/// - POST `/token`: the `FX_ACCESS_TOKEN`.
+/// - Other requests without the bearer token: 401.
+/// - None for the authorized requests (to be served by the MCP server replies).
+pub fn fx_oauth_reply(req: &FakeHttpRequest) -> Option<Response> {
+	if req.path.starts_with("/.well-known/") {
+		return Some(status_response(StatusCode::NOT_FOUND));
+	}
+	if req.path == "/token" {
+		return Some(json_response(
+			json!({"access_token": FX_ACCESS_TOKEN, "token_type": "Bearer", "expires_in": 3600}),
+		));
+	}
+	if req.header("authorization") != Some(&format!("Bearer {FX_ACCESS_TOKEN}")) {
+		let mut res = status_response(StatusCode::UNAUTHORIZED);
+		res.headers_mut()
+			.insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+		return Some(res);
+	}
+	None
+}
+
+// endregion: --- OAuth
@@ -1,11 +1,27 @@
 // region:    --- Modules
 
-use agentic::mcp::client::{Client, ClientStdioTransportConfig};
+mod fake_http;
+
+pub use fake_http::*;
+
+use agentic::mcp::client::{Client, ClientEvent, ClientStdioTransportConfig, NotificationStream};
+use agentic::mcp::server::{ResourceProvider, Server, ToolArgs};
+use agentic::mcp::testing::{MockExpectation, MockServer};
+use agentic::mcp::{
+	CallToolResult, Error, GetPromptResult, MessageContent, Prompt, PromptArgument, PromptMessage, Resource,
+	ResourceContents, Role, ToolInputProperty, ToolInputSchema,
+};
+use serde_json::{Value, json};
+use std::time::{Duration, Instant};
 
 pub type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
 
 // endregion: --- Modules
 
+/// When set, the stdio tests run against the `@modelcontextprotocol/server-everything` (requires nodejs),
+/// otherwise against the `agentic-mock-server` scripted like it.
+const NPX_ENV: &str = "AGENTIC_TEST_NPX";
+
 pub async fn mock_new_stdio_client_and_connect() -> Result<Client> {
 	let (mut client, transport_config) = mock_new_stdio_client()?;
 
@@ -17,12 +33,160 @@ pub async fn mock_new_stdio_client_and_connect() -> Result<Client> {
 
 pub fn mock_new_stdio_client() -> Result<(Client, ClientStdioTransportConfig)> {
 	let mut client = Client::new("Demo Client", "0.1.0");
-	let transport_config = ClientStdioTransportConfig::new(
-		// cmd and args (this MCP Server requires nodejs to be installed)
-		"npx",
-		["-y", "@modelcontextprotocol/server-everything"],
-		None,
-	);
+	let transport_config = if std::env::var(NPX_ENV).is_ok() {
+		ClientStdioTransportConfig::new(
+			// cmd and args (this MCP Server requires nodejs to be installed)
+			"npx",
+			["-y", "@modelcontextprotocol/server-everything"],
+			None,
+		)
+	} else {
+		let script = serde_json::to_string(mock_everything_server().script())?;
+		ClientStdioTransportConfig::new(env!("CARGO_BIN_EXE_agentic-mock-server"), Vec::<String>::new(), None)
+			.with_env("AGENTIC_MOCK_SCRIPT", script)
+	};
 
 	Ok((client, transport_config))
 }
+
+/// A mock of the `server-everything` (for what the tests use).
+pub fn mock_everything_server() -> MockServer {
+	let tool_names = [
+		"echo",
+		"add",
+		"printEnv",
+		"longRunningOperation",
+		"sampleLLM",
+		"getTinyImage",
+		"annotatedMessage",
+		"getResourceReference",
+	];
+	let tools = tool_names
+		.iter()
+		.map(|name| json!({"name": name, "inputSchema": {"type": "object"}}))
+		.collect::<Vec<_>>();
+
+	MockServer::new("example-servers/everything", "1.0.0")
+		.on_request(MockExpectation::new("tools/list").respond(json!({ "tools": tools })))
+		.on_request(
+			MockExpectation::new("tools/call")
+				.with_params(json!({"name": "add", "arguments": {"a": 1, "b": 2.5}}))
+				.respond(CallToolResult::from_text("The sum of 1 and 2.5 is 3.5.")),
+		)
+}
+
+// region:    --- Server Fixtures
+
+/// The resource of the `fx_server()` (a counter, always "0").
+pub const COUNTER_URI: &str = "counter://value";
+
+/// An in-crate server with an `add` tool, a counter resource, and a `greet` prompt.
+pub fn fx_server() -> Server {
+	let mut server = Server::new("Demo Server", "0.1.0");
+	server.register_tool("add", "Adds two numbers", add);
+	server.register_resource_provider(CounterProvider);
+	let prompt = Prompt::new("greet").append_argument(PromptArgument::new("name").with_required(true));
+	server.register_prompt(prompt, greet);
+	server
+}
+
+/// The `initialize` result of the raw fake servers (e.g., the WebSocket ones closing with a code).
+pub fn fx_initialize_result() -> Value {
+	json!({
+		"protocolVersion": agentic::mcp::LATEST_PROTOCOL_VERSION,
+		"capabilities": {},
+		"serverInfo": {"name": "Fake Server", "version": "0.1.0"}
+	})
+}
+
+#[derive(serde::Deserialize)]
+struct AddArgs {
+	a: f64,
+	b: f64,
+}
+
+impl ToolArgs for AddArgs {
+	fn input_schema() -> ToolInputSchema {
+		ToolInputSchema::object()
+			.append_required_property("a", ToolInputProperty::number())
+			.append_required_property("b", ToolInputProperty::number())
+	}
+}
+
+async fn add(args: AddArgs) -> agentic::mcp::Result<CallToolResult> {
+	Ok(CallToolResult::from_text((args.a + args.b).to_string()))
+}
+
+#[derive(serde::Deserialize)]
+struct GreetArgs {
+	name: String,
+}
+
+async fn greet(args: GreetArgs) -> agentic::mcp::Result<GetPromptResult> {
+	let text = format!("Say hello to {}.", args.name);
+	Ok(GetPromptResult::new(vec![PromptMessage::new(
+		Role::User,
+		MessageContent::new_text(text),
+	)]))
+}
+
+struct CounterProvider;
+
+impl ResourceProvider for CounterProvider {
+	async fn list_resources(&self) -> agentic::mcp::Result<Vec<Resource>> {
+		Ok(vec![Resource::new(COUNTER_URI, "Counter")])
+	}
+
+	async fn read_resource(&self, uri: &str) -> agentic::mcp::Result<Vec<ResourceContents>> {
+		if uri != COUNTER_URI {
+			return Err(Error::resource_not_found(uri));
+		}
+		Ok(vec![ResourceContents::Text {
+			uri: uri.to_string(),
+			mime_type: Some("text/plain".to_string()),
+			text: "0".to_string(),
+		}])
+	}
+}
+
+// endregion: --- Server Fixtures
+
+// region:    --- Wait Helpers
+
+/// The max wait of the helpers below (everything is local, so only reached on a failure).
+const WAIT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Polls `check` until it returns Some (e.g., for the messages received by a `MockServer`).
+pub async fn wait_until<T>(mut check: impl FnMut() -> Option<T>) -> Result<T> {
+	let deadline = Instant::now() + WAIT_TIMEOUT;
+	loop {
+		if let Some(value) = check() {
+			return Ok(value);
+		}
+		if Instant::now() > deadline {
+			return Err("wait_until timed out".into());
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+	}
+}
+
+/// Receives the client events until `is_last` matches one (included).
+pub async fn recv_events_until(
+	events: &NotificationStream<ClientEvent>,
+	is_last: impl Fn(&ClientEvent) -> bool,
+) -> Result<Vec<ClientEvent>> {
+	let mut received = Vec::new();
+	loop {
+		let event = tokio::time::timeout(WAIT_TIMEOUT, events.recv())
+			.await
+			.map_err(|_| format!("recv_events_until timed out. Received: {received:?}"))?
+			.ok_or("Client events closed")?;
+		let done = is_last(&event);
+		received.push(event);
+		if done {
+			return Ok(received);
+		}
+	}
+}
+
+// endregion: --- Wait Helpers
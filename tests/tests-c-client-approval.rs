@@ -0,0 +1,106 @@
+mod support;
+
+use agentic::mcp::client::{Approval, ApprovalHandler, Client};
+use agentic::mcp::testing::{MockExpectation, MockServer};
+use agentic::mcp::{
+	CallToolParams, CallToolResult, CreateMessageParams, CreateMessageResult, Error, McpRequest, SamplingContent, Tool,
+	ToolAnnotations, ToolInputSchema,
+};
+use serde_json::json;
+use support::Result;
+
+#[tokio::test]
+async fn test_c_client_approval_unlisted_tools() -> Result<()> {
+	// -- Fixtures & Setup
+	let lookup =
+		Tool::new("lookup", ToolInputSchema::new()).with_annotations(ToolAnnotations::new().with_read_only_hint(true));
+	let delete = Tool::new("delete", ToolInputSchema::new());
+	let mock = MockServer::new("Mock Server", "0.1.0")
+		.on_request(MockExpectation::new("tools/list").respond(json!({"tools": [lookup, delete]})))
+		.on_request(MockExpectation::new("tools/call").respond(CallToolResult::from_text("found")));
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.set_approval_handler(DefaultApproval);
+	client.connect(mock.serve_in_memory()).await?;
+
+	// -- Exec
+	// NOTE: No `tools/list` sent before the calls (the client lists them on the cache miss).
+	let lookup_res = client.send_request(CallToolParams::new("lookup")).await?;
+	let delete_res = client.send_request(CallToolParams::new("delete")).await;
+	let unknown_res = client.send_request(CallToolParams::new("unknown")).await;
+
+	// -- Check
+	// The read-only tool is approved by its annotations.
+	assert_eq!(serde_json::to_value(&lookup_res.result)?["content"][0]["text"], "found");
+	// The others are reviewed, and denied by the default review.
+	assert!(matches!(delete_res, Err(Error::UserRejected { .. })), "{delete_res:?}");
+	assert!(
+		matches!(unknown_res, Err(Error::UserRejected { .. })),
+		"{unknown_res:?}"
+	);
+	let methods = mock
+		.received()
+		.iter()
+		.filter_map(|msg| msg["method"].as_str().map(String::from))
+		.filter(|method| method.starts_with("tools/"))
+		.collect::<Vec<_>>();
+	// Listed once for the first call (cached), then again for the unknown tool only.
+	assert_eq!(methods, vec!["tools/list", "tools/call", "tools/list"]);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_client_approval_exec_sampling_handler() -> Result<()> {
+	// -- Fixtures & Setup
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.register_sampling_handler(
+		async |params: CreateMessageParams| -> agentic::mcp::Result<CreateMessageResult> {
+			let system_prompt = params.system_prompt.unwrap_or_default();
+			Ok(CreateMessageResult::new_assistant(
+				SamplingContent::new_text(system_prompt),
+				"mock-model",
+			))
+		},
+	);
+	client.set_approval_handler(SamplingApproval);
+	let fx_req = |id: i64, system_prompt: &str| {
+		let params = CreateMessageParams::new(Vec::new(), 10).with_system_prompt(system_prompt);
+		McpRequest::new(id, params)
+	};
+
+	// -- Exec
+	let edited = client.exec_sampling_handler(fx_req(1, "original")).await?;
+	let denied = client.exec_sampling_handler(fx_req(2, "deny")).await;
+
+	// -- Check
+	assert_eq!(serde_json::to_value(&edited.result)?["content"]["text"], "edited");
+	let Err(Error::UserRejected { reason }) = denied else {
+		return Err(format!("Should be UserRejected, was: {denied:?}").into());
+	};
+	assert_eq!(reason.as_deref(), Some("Not this one"));
+
+	Ok(())
+}
+
+// region:    --- Support
+
+/// The default policies and reviews.
+struct DefaultApproval;
+
+impl ApprovalHandler for DefaultApproval {}
+
+/// Denies the "deny" system prompt, and edits the others.
+struct SamplingApproval;
+
+impl ApprovalHandler for SamplingApproval {
+	async fn review_sampling(&self, params: &CreateMessageParams) -> Approval<CreateMessageParams> {
+		if params.system_prompt.as_deref() == Some("deny") {
+			return Approval::deny("Not this one");
+		}
+		let mut params = params.clone();
+		params.system_prompt = Some("edited".to_string());
+		Approval::Edit(params)
+	}
+}
+
+// endregion: --- Support
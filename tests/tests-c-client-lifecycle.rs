@@ -0,0 +1,175 @@
+mod support;
+
+use agentic::mcp::client::{Client, ClientEvent, ClientState, NotificationStream};
+use agentic::mcp::testing::{MockAction, MockExpectation, MockServer};
+use agentic::mcp::{Error, ListToolsParams, LoggingMessageNotificationParams, ProtocolVersion};
+use serde_json::{Value, json};
+use std::time::Duration;
+use support::{Result, recv_events_until, wait_until};
+
+#[tokio::test]
+async fn test_c_client_lifecycle_initialized_sent() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0");
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let events = client.subscribe_events();
+
+	// -- Exec
+	client.connect(mock.serve_in_memory()).await?;
+	let states = recv_events_until(&events, |event| {
+		matches!(event, ClientEvent::StateChanged(ClientState::Ready))
+	})
+	.await?;
+	let received = wait_until(|| {
+		let received = mock.received();
+		(received.len() >= 2).then_some(received)
+	})
+	.await?;
+
+	// -- Check
+	assert_eq!(client.state(), ClientState::Ready);
+	let states = states
+		.into_iter()
+		.filter_map(|event| match event {
+			ClientEvent::StateChanged(state) => Some(state),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(
+		states,
+		vec![ClientState::Connecting, ClientState::Initializing, ClientState::Ready]
+	);
+	assert_eq!(received[0]["method"], "initialize");
+	assert_eq!(received[1]["method"], "notifications/initialized");
+	assert!(received[1].get("id").is_none(), "initialized should be a notification");
+	assert_eq!(mock.received_requests("notifications/initialized").len(), 1);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_client_lifecycle_request_before_connect() -> Result<()> {
+	// -- Fixtures & Setup
+	let client = Client::new("Demo Client", "0.1.0");
+
+	// -- Exec
+	let res = client.send_request(ListToolsParams::default()).await;
+
+	// -- Check
+	let Err(Error::ClientNotReady { method, state }) = res else {
+		return Err(format!("Should be ClientNotReady, was: {res:?}").into());
+	};
+	assert_eq!(method, "tools/list");
+	assert_eq!(state, ClientState::New);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_client_lifecycle_protocol_version_mismatch() -> Result<()> {
+	// -- Fixtures & Setup
+	let init_result = json!({
+		"protocolVersion": "2023-01-01",
+		"capabilities": {},
+		"serverInfo": {"name": "Old Server", "version": "0.1.0"}
+	});
+	let mock =
+		MockServer::new("Mock Server", "0.1.0").on_request(MockExpectation::new("initialize").respond(init_result));
+	let mut client = Client::new("Demo Client", "0.1.0");
+
+	// -- Exec
+	let res = client.connect(mock.serve_in_memory()).await;
+	let after_res = client.send_request(ListToolsParams::default()).await;
+
+	// -- Check
+	let Err(Error::ProtocolVersionMismatch {
+		server_version,
+		supported_versions,
+	}) = res
+	else {
+		return Err(format!("Should be ProtocolVersionMismatch, was: {res:?}").into());
+	};
+	assert_eq!(server_version, "2023-01-01");
+	assert_eq!(supported_versions, vec!["2025-03-26", "2024-11-05"]);
+	// The client disconnected (and did not send the initialized notification).
+	assert_eq!(client.state(), ClientState::Closed);
+	assert!(client.protocol_version().is_none());
+	assert!(
+		matches!(
+			after_res,
+			Err(Error::ClientNotReady {
+				state: ClientState::Closed,
+				..
+			})
+		),
+		"{after_res:?}"
+	);
+	assert!(mock.received_requests("notifications/initialized").is_empty());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_client_lifecycle_batch_with_batching_version() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0").on_initialized(MockAction::send(fx_log_batch()));
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let logs = client.subscribe_notification::<LoggingMessageNotificationParams>();
+
+	// -- Exec
+	client.connect(mock.serve_in_memory()).await?;
+	let first = recv_log_data(&logs).await?;
+	let second = recv_log_data(&logs).await?;
+
+	// -- Check
+	assert_eq!(client.protocol_version(), Some(ProtocolVersion::V2025_03_26));
+	assert_eq!(first, "batched-1");
+	assert_eq!(second, "batched-2");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_client_lifecycle_batch_without_batching_version() -> Result<()> {
+	// -- Fixtures & Setup
+	let single = json!({"level": "info", "data": "single"});
+	let mock = MockServer::new("Mock Server", "0.1.0")
+		.on_initialized(MockAction::send(fx_log_batch()))
+		.on_initialized(MockAction::notify("notifications/message", single));
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.set_protocol_versions(vec![ProtocolVersion::V2024_11_05]);
+	let logs = client.subscribe_notification::<LoggingMessageNotificationParams>();
+
+	// -- Exec
+	client.connect(mock.serve_in_memory()).await?;
+	let first = recv_log_data(&logs).await?;
+
+	// -- Check
+	// The batch is ignored (2024-11-05 does not support batching), so, the single notification comes first.
+	assert_eq!(client.protocol_version(), Some(ProtocolVersion::V2024_11_05));
+	assert_eq!(
+		mock.received_requests("initialize")[0].pointer("/params/protocolVersion"),
+		Some(&json!("2024-11-05"))
+	);
+	assert_eq!(first, "single");
+	assert!(logs.try_recv().is_none());
+
+	Ok(())
+}
+
+// region:    --- Support
+
+/// A JSON-RPC batch of two `notifications/message`.
+fn fx_log_batch() -> Value {
+	let log = |data: &str| json!({"jsonrpc": "2.0", "method": "notifications/message", "params": {"level": "info", "data": data}});
+	json!([log("batched-1"), log("batched-2")])
+}
+
+async fn recv_log_data(logs: &NotificationStream<LoggingMessageNotificationParams>) -> Result<Value> {
+	let log = tokio::time::timeout(Duration::from_secs(2), logs.recv())
+		.await?
+		.ok_or("Notifications closed")?;
+	Ok(log.data)
+}
+
+// endregion: --- Support
@@ -0,0 +1,241 @@
+mod support;
+
+use agentic::mcp::client::{
+	Client, ClientEvent, ClientState, CommRx, CommTx, CustomClientTransport, InFlightPolicy, ReconnectPolicy,
+	TransportError, TransportTrx,
+};
+use agentic::mcp::testing::{MockAction, MockExpectation, MockServer};
+use agentic::mcp::{Error, LoggingLevel, PingParams, SetLevelParams, SubscribeParams};
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use support::{Result, recv_events_until, wait_until};
+use tokio::sync::Notify;
+
+#[tokio::test]
+async fn test_c_reconnect_reinitialize_and_replay() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("mock-server", "0.1.0")
+		.on_request(MockExpectation::new("resources/subscribe").respond(json!({})))
+		.on_request(MockExpectation::new("logging/setLevel").respond(json!({})));
+	let (mut client, conn) = fx_reconnect_client(&mock, InFlightPolicy::Fail).await?;
+	let events = client.subscribe_events();
+	client.send_request(SubscribeParams::new("file:///a.txt")).await?;
+	client.send_request(SetLevelParams::new(LoggingLevel::Warning)).await?;
+
+	// -- Exec
+	conn.drop_conn();
+	let events = recv_events_until(&events, |event| matches!(event, ClientEvent::Reconnected { .. })).await?;
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	assert_eq!(
+		fx_lifecycle_events(&events),
+		["Disconnected", "Reconnecting(1)", "Reconnected(1)"]
+	);
+	assert_eq!(client.state(), ClientState::Ready);
+	assert_eq!(mock.received_requests("initialize").len(), 2);
+	assert_eq!(mock.received_requests("notifications/initialized").len(), 2);
+	let subscribes = mock.received_requests("resources/subscribe");
+	assert_eq!(subscribes.len(), 2);
+	assert_eq!(subscribes[1]["params"]["uri"], "file:///a.txt");
+	let set_levels = mock.received_requests("logging/setLevel");
+	assert_eq!(set_levels.len(), 2);
+	assert_eq!(set_levels[1]["params"]["level"], "warning");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_reconnect_in_flight_fail() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = fx_slow_ping_mock();
+	let (client, conn) = fx_reconnect_client(&mock, InFlightPolicy::Fail).await?;
+	let events = client.subscribe_events();
+
+	// -- Exec
+	let ping = tokio::spawn({
+		let client = client.clone();
+		async move { client.send_request(PingParams::default()).await }
+	});
+	wait_until(|| (!mock.received_requests("ping").is_empty()).then_some(())).await?;
+	conn.drop_conn();
+	let res = ping.await?;
+	recv_events_until(&events, |event| matches!(event, ClientEvent::Reconnected { .. })).await?;
+
+	// -- Check
+	assert!(matches!(res, Err(Error::ClientDisconnected { .. })), "{res:?}");
+	// Not sent again.
+	assert_eq!(mock.received_requests("ping").len(), 1);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_reconnect_in_flight_retry() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = fx_slow_ping_mock();
+	let (client, conn) = fx_reconnect_client(&mock, InFlightPolicy::Retry).await?;
+
+	// -- Exec
+	let ping = tokio::spawn({
+		let client = client.clone();
+		async move { client.send_request(PingParams::default()).await }
+	});
+	wait_until(|| (!mock.received_requests("ping").is_empty()).then_some(())).await?;
+	conn.drop_conn();
+	let res = ping.await?;
+
+	// -- Check
+	assert!(res.is_ok(), "{res:?}");
+	let pings = mock.received_requests("ping");
+	assert_eq!(pings.len(), 2);
+	// Sent again with the same id.
+	assert_eq!(pings[0]["id"], pings[1]["id"]);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_reconnect_failed() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("mock-server", "0.1.0");
+	let (client, conn) = fx_reconnect_client(&mock, InFlightPolicy::Fail).await?;
+	let events = client.subscribe_events();
+
+	// -- Exec
+	conn.refuse.store(true, Ordering::SeqCst);
+	conn.drop_conn();
+	let events = recv_events_until(&events, |event| matches!(event, ClientEvent::ReconnectFailed { .. })).await?;
+
+	// -- Check
+	assert_eq!(
+		fx_lifecycle_events(&events),
+		["Disconnected", "Reconnecting(1)", "Reconnecting(2)", "ReconnectFailed(2)"]
+	);
+	let Some(ClientEvent::ReconnectFailed { reason, .. }) = events.last() else {
+		return Err("Should end with ReconnectFailed".into());
+	};
+	assert!(reason.contains("Connection refused"), "{reason}");
+	assert_eq!(client.state(), ClientState::Closed);
+	assert_eq!(mock.received_requests("initialize").len(), 1);
+
+	Ok(())
+}
+
+// region:    --- Support
+
+/// The connection of the `FxReconnectTransport` (shared by all of its reconnects).
+#[derive(Default)]
+struct FxConn {
+	/// Drops the current connection (the client sees it lost).
+	dropped: Notify,
+	/// When true, the (re)connects fail.
+	refuse: AtomicBool,
+}
+
+impl FxConn {
+	fn drop_conn(&self) {
+		self.dropped.notify_waiters();
+	}
+}
+
+/// Serves a new session of the mock on each (re)connect, relayed until `FxConn::drop_conn`.
+struct FxReconnectTransport {
+	mock: MockServer,
+	conn: Arc<FxConn>,
+}
+
+impl CustomClientTransport for FxReconnectTransport {
+	async fn start(&mut self, transport_trx: TransportTrx) -> core::result::Result<(), TransportError> {
+		if self.conn.refuse.load(Ordering::SeqCst) {
+			return Err(TransportError::custom("Connection refused"));
+		}
+
+		let TransportTrx {
+			c2s_rx,
+			s2c_tx,
+			s2c_aux_tx,
+			..
+		} = transport_trx;
+		let (mock_c2s_tx, mock_c2s_rx) = flume::unbounded::<String>();
+		let (mock_s2c_tx, mock_s2c_rx) = flume::unbounded::<String>();
+		let mock = self.mock.clone();
+		tokio::spawn(async move { mock.serve_channels(CommRx::from(mock_c2s_rx), CommTx::from(mock_s2c_tx)).await });
+
+		// NOTE: Dropping the channels (on `drop_conn`) loses the messages in flight, like a lost connection.
+		let conn = self.conn.clone();
+		let mock_c2s_tx = CommTx::from(mock_c2s_tx);
+		let mock_s2c_rx = CommRx::from(mock_s2c_rx);
+		tokio::spawn(async move {
+			let _s2c_aux_tx = s2c_aux_tx;
+			let dropped = conn.dropped.notified();
+			tokio::pin!(dropped);
+			loop {
+				tokio::select! {
+					_ = &mut dropped => break,
+					msg = c2s_rx.recv() => match msg {
+						Ok(msg) => { let _ = mock_c2s_tx.send(msg).await; }
+						Err(_) => break,
+					},
+					msg = mock_s2c_rx.recv() => match msg {
+						Ok(msg) => { let _ = s2c_tx.send(msg).await; }
+						Err(_) => break,
+					},
+				}
+			}
+		});
+
+		Ok(())
+	}
+
+	fn new_from_config(&self) -> Option<Self> {
+		Some(Self {
+			mock: self.mock.clone(),
+			conn: self.conn.clone(),
+		})
+	}
+}
+
+/// A client connected to the mock, reconnecting at most twice (without delay).
+async fn fx_reconnect_client(mock: &MockServer, in_flight: InFlightPolicy) -> Result<(Client, Arc<FxConn>)> {
+	let conn = Arc::new(FxConn::default());
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let policy = ReconnectPolicy::default()
+		.with_max_attempts(Some(2))
+		.with_initial_delay(Duration::from_millis(10))
+		.with_in_flight(in_flight);
+	client.set_reconnect_policy(Some(policy));
+	let transport = FxReconnectTransport {
+		mock: mock.clone(),
+		conn: conn.clone(),
+	};
+	client.connect(transport).await?;
+	Ok((client, conn))
+}
+
+/// A mock replying to the `ping` after 200ms (so that it is in flight when the connection gets dropped).
+fn fx_slow_ping_mock() -> MockServer {
+	MockServer::new("mock-server", "0.1.0").on_request(
+		MockExpectation::new("ping")
+			.with_action(MockAction::delay(200))
+			.respond(json!({})),
+	)
+}
+
+/// The disconnect and reconnect events (e.g., `Reconnecting(1)`), without the state changes.
+fn fx_lifecycle_events(events: &[ClientEvent]) -> Vec<String> {
+	events
+		.iter()
+		.filter_map(|event| match event {
+			ClientEvent::Disconnected { .. } => Some("Disconnected".to_string()),
+			ClientEvent::Reconnecting { attempt, .. } => Some(format!("Reconnecting({attempt})")),
+			ClientEvent::Reconnected { attempts } => Some(format!("Reconnected({attempts})")),
+			ClientEvent::ReconnectFailed { attempts, .. } => Some(format!("ReconnectFailed({attempts})")),
+			ClientEvent::StateChanged(_) => None,
+		})
+		.collect()
+}
+
+// endregion: --- Support
@@ -0,0 +1,123 @@
+mod support;
+
+use agentic::mcp::client::Client;
+use agentic::mcp::testing::{MockAction, MockExpectation, MockServer};
+use agentic::mcp::{CallToolParams, CallToolResult, Error, McpRequest, PingParams};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use support::{Result, wait_until};
+
+#[tokio::test]
+async fn test_c_client_requests_progress() -> Result<()> {
+	// -- Fixtures & Setup
+	let progress = |progress: u32| json!({"progressToken": 7, "progress": progress, "total": 2});
+	let mock = MockServer::new("Mock Server", "0.1.0").on_request(
+		MockExpectation::new("tools/call")
+			.with_action(MockAction::notify("notifications/progress", progress(1)))
+			.with_action(MockAction::delay(50))
+			.with_action(MockAction::notify("notifications/progress", progress(2)))
+			.respond(CallToolResult::from_text("done")),
+	);
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(mock.serve_in_memory()).await?;
+
+	// -- Exec
+	let handle = client
+		.send_request_with_progress(McpRequest::new(7, CallToolParams::new("slow")))
+		.await?;
+	let mut progresses = Vec::new();
+	// NOTE: Ends when the response arrives (the progress token routing is removed).
+	while let Some(params) = tokio::time::timeout(Duration::from_secs(2), handle.progress().recv()).await? {
+		progresses.push(params.progress);
+	}
+	let res = handle.result().await?;
+
+	// -- Check
+	assert_eq!(progresses, vec![1.0, 2.0]);
+	assert_eq!(serde_json::to_value(&res.result)?["content"][0]["text"], "done");
+	let sent = &mock.received_requests("tools/call")[0];
+	assert_eq!(sent.pointer("/params/_meta/progressToken"), Some(&json!(7)));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_client_requests_progress_timeout() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0").on_request(
+		MockExpectation::new("tools/call")
+			.with_action(MockAction::notify(
+				"notifications/progress",
+				json!({"progressToken": 8, "progress": 1}),
+			))
+			.with_action(MockAction::delay(5_000))
+			.respond(CallToolResult::from_text("too late")),
+	);
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(mock.serve_in_memory()).await?;
+	let start = Instant::now();
+
+	// -- Exec
+	let handle = client
+		.send_request_with_progress(McpRequest::new(8, CallToolParams::new("slow")))
+		.await?
+		.with_timeout(Some(Duration::from_millis(200)));
+	let mut progress_count = 0;
+	// NOTE: Ends at the timeout (the server never answers in time).
+	while tokio::time::timeout(Duration::from_secs(2), handle.progress().recv())
+		.await?
+		.is_some()
+	{
+		progress_count += 1;
+	}
+	let drained_in = start.elapsed();
+	let res = handle.result().await;
+
+	// -- Check
+	assert_eq!(progress_count, 1);
+	assert!(drained_in < Duration::from_secs(1), "{drained_in:?}");
+	let Err(Error::Timeout { rpc_id, method }) = res else {
+		return Err(format!("Should be Timeout, was: {res:?}").into());
+	};
+	assert_eq!(rpc_id.to_string(), "8");
+	assert_eq!(method, "tools/call");
+	assert!(
+		start.elapsed() < Duration::from_secs(1),
+		"the timeout counts from the send"
+	);
+	let cancelled = wait_until(|| mock.received_requests("notifications/cancelled").pop()).await?;
+	assert_eq!(cancelled.pointer("/params/requestId"), Some(&json!(8)));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_client_requests_timeout() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0").on_request(
+		MockExpectation::new("tools/call")
+			.with_action(MockAction::delay(5_000))
+			.respond(CallToolResult::from_text("too late")),
+	);
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(mock.serve_in_memory()).await?;
+	let req = McpRequest::new("slow-1", CallToolParams::new("slow"));
+
+	// -- Exec
+	let res = client.send_request_with_timeout(req, Duration::from_millis(100)).await;
+	let cancelled = wait_until(|| mock.received_requests("notifications/cancelled").pop()).await?;
+
+	// -- Check
+	let Err(Error::Timeout { rpc_id, method }) = res else {
+		return Err(format!("Should be Timeout, was: {res:?}").into());
+	};
+	assert_eq!(rpc_id.to_string(), "slow-1");
+	assert_eq!(method, "tools/call");
+	assert_eq!(cancelled.pointer("/params/requestId"), Some(&json!("slow-1")));
+	let reason = cancelled.pointer("/params/reason").and_then(|v| v.as_str()).unwrap_or_default();
+	assert!(reason.contains("timed out"), "{reason}");
+	// The client is still usable.
+	client.send_request(PingParams::default()).await?;
+
+	Ok(())
+}
@@ -0,0 +1,321 @@
+mod support;
+
+use agentic::mcp::client::{Client, ClientHttpTransportConfig, ClientState, TransportError};
+use agentic::mcp::{
+	CallToolParams, Error, ListToolsParams, LoggingMessageNotificationParams, McpRequest, PingParams, ProtocolVersion,
+};
+use axum::http::{HeaderValue, Method, StatusCode};
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use support::{
+	FX_ACCESS_TOKEN, FX_SESSION_ID, FakeHttpServer, Result, delayed_json_response, fx_mcp_reply, fx_oauth_client,
+	fx_oauth_reply, sse_event, sse_response, status_response, wait_until,
+};
+use tokio::sync::{mpsc, oneshot};
+
+#[tokio::test]
+async fn test_c_http_protocol_version_header() -> Result<()> {
+	// -- Fixtures & Setup
+	let server = FakeHttpServer::start(fx_mcp_reply).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.set_protocol_versions(vec![ProtocolVersion::V2025_06_18, ProtocolVersion::V2025_03_26]);
+
+	// -- Exec
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	assert_eq!(client.protocol_version(), Some(ProtocolVersion::V2025_06_18));
+	let initialize = &server.requests_of("initialize")[0];
+	assert_eq!(initialize.header("mcp-protocol-version"), None);
+	for method in ["notifications/initialized", "ping"] {
+		let req = &server.requests_of(method)[0];
+		assert_eq!(req.header("mcp-protocol-version"), Some("2025-06-18"), "{method}");
+		assert_eq!(req.header("mcp-session-id"), Some(FX_SESSION_ID), "{method}");
+	}
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_no_protocol_version_header() -> Result<()> {
+	// -- Fixtures & Setup
+	let server = FakeHttpServer::start(fx_mcp_reply).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+
+	// -- Exec
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	// The default 2025-03-26 does not have the header.
+	assert_eq!(client.protocol_version(), Some(ProtocolVersion::V2025_03_26));
+	assert!(server.requests().iter().all(|req| req.header("mcp-protocol-version").is_none()));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_get_stream() -> Result<()> {
+	// -- Fixtures & Setup
+	let get_tx: Arc<Mutex<Option<mpsc::UnboundedSender<String>>>> = Arc::default();
+	let handler_get_tx = get_tx.clone();
+	let server = FakeHttpServer::start(move |req| {
+		if req.method != Method::GET {
+			return fx_mcp_reply(req);
+		}
+		let (tx, res) = sse_response();
+		*handler_get_tx.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+		res
+	})
+	.await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let logs = client.subscribe_notification::<LoggingMessageNotificationParams>();
+
+	// -- Exec
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+	let tx = wait_until(|| get_tx.lock().unwrap_or_else(|e| e.into_inner()).clone()).await?;
+	let log =
+		json!({"jsonrpc": "2.0", "method": "notifications/message", "params": {"level": "info", "data": "from get"}});
+	tx.send(sse_event(Some("1"), None, &log.to_string()))?;
+	let log = tokio::time::timeout(Duration::from_secs(2), logs.recv())
+		.await?
+		.ok_or("Notifications closed")?;
+
+	// -- Check
+	assert_eq!(log.data, json!("from get"));
+	let get = &server.requests_with(Method::GET)[0];
+	assert_eq!(get.header("accept"), Some("text/event-stream"));
+	assert_eq!(get.header("mcp-session-id"), Some(FX_SESSION_ID));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_resume_with_last_event_id() -> Result<()> {
+	// -- Fixtures & Setup
+	let server = FakeHttpServer::start(|req| {
+		match (req.method.clone(), req.rpc_method(), req.header("last-event-id")) {
+			// The ping stream only primes the event id, then disconnects (the sender is dropped).
+			(Method::POST, Some("ping"), _) => {
+				let (tx, res) = sse_response();
+				let _ = tx.send(sse_event(Some("e1"), None, ""));
+				res
+			}
+			// The resumed stream sends the response.
+			(Method::GET, _, Some("e1")) => {
+				let (tx, res) = sse_response();
+				let ping_res = json!({"jsonrpc": "2.0", "id": 5, "result": {}});
+				let _ = tx.send(sse_event(Some("e2"), None, &ping_res.to_string()));
+				res
+			}
+			_ => fx_mcp_reply(req),
+		}
+	})
+	.await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+
+	// -- Exec
+	let res = client
+		.send_request_with_timeout(McpRequest::new(5, PingParams::default()), Duration::from_secs(2))
+		.await?;
+
+	// -- Check
+	assert_eq!(res.id.to_string(), "5");
+	let resumed = server
+		.requests_with(Method::GET)
+		.into_iter()
+		.filter(|req| req.header("last-event-id").is_some())
+		.collect::<Vec<_>>();
+	assert_eq!(resumed.len(), 1);
+	assert_eq!(resumed[0].header("last-event-id"), Some("e1"));
+	assert_eq!(resumed[0].header("mcp-session-id"), Some(FX_SESSION_ID));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_session_expired_reinitialize() -> Result<()> {
+	// -- Fixtures & Setup
+	let session_count = Arc::new(AtomicUsize::new(0));
+	let server = FakeHttpServer::start(move |req| {
+		if req.rpc_method() == Some("initialize") {
+			let session_id = format!("session-{}", session_count.fetch_add(1, Ordering::SeqCst) + 1);
+			let mut res = fx_mcp_reply(req);
+			if let Ok(session_id) = HeaderValue::try_from(session_id) {
+				res.headers_mut().insert("mcp-session-id", session_id);
+			}
+			return res;
+		}
+		// The first session expires before the ping.
+		if req.rpc_method() == Some("ping") && req.header("mcp-session-id") == Some("session-1") {
+			return status_response(StatusCode::NOT_FOUND);
+		}
+		fx_mcp_reply(req)
+	})
+	.await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+
+	// -- Exec
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	assert_eq!(server.requests_of("initialize").len(), 2);
+	assert_eq!(server.requests_of("notifications/initialized").len(), 2);
+	let ping_sessions = server
+		.requests_of("ping")
+		.iter()
+		.map(|req| req.header("mcp-session-id").map(String::from))
+		.collect::<Vec<_>>();
+	assert_eq!(
+		ping_sessions,
+		vec![Some("session-1".to_string()), Some("session-2".to_string())]
+	);
+	let reinitialized = &server.requests_of("notifications/initialized")[1];
+	assert_eq!(reinitialized.header("mcp-session-id"), Some("session-2"));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_delete_on_close() -> Result<()> {
+	// -- Fixtures & Setup
+	let server = FakeHttpServer::start(fx_mcp_reply).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+
+	// -- Exec
+	client.close().await?;
+
+	// -- Check
+	// NOTE: Checked right after the close (which waits for the DELETE response).
+	let deletes = server.requests_with(Method::DELETE);
+	assert_eq!(deletes.len(), 1);
+	assert_eq!(deletes[0].header("mcp-session-id"), Some(FX_SESSION_ID));
+	assert_eq!(client.state(), ClientState::Closed);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_cancel_slow_json_request() -> Result<()> {
+	// -- Fixtures & Setup
+	let slow_res_tx: Arc<Mutex<Option<oneshot::Sender<Value>>>> = Arc::default();
+	let server_slow_res_tx = slow_res_tx.clone();
+	let server = FakeHttpServer::start(move |req| {
+		if req.rpc_method() == Some("tools/call") {
+			let (tx, res) = delayed_json_response();
+			*server_slow_res_tx.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+			return res;
+		}
+		fx_mcp_reply(req)
+	})
+	.await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+	let req = McpRequest::new("slow-1", CallToolParams::new("slow"));
+
+	// -- Exec
+	let res = client.send_request_with_timeout(req, Duration::from_millis(100)).await;
+	let cancelled = wait_until(|| server.requests_of("notifications/cancelled").pop()).await?;
+
+	// -- Check
+	let Err(Error::Timeout { rpc_id, .. }) = res else {
+		return Err(format!("Should be Timeout, was: {res:?}").into());
+	};
+	assert_eq!(rpc_id.to_string(), "slow-1");
+	let cancelled = cancelled.body.ok_or("Should have a body")?;
+	assert_eq!(cancelled.pointer("/params/requestId"), Some(&json!("slow-1")));
+	// The cancel did not wait for the slow response (still pending).
+	let slow_res_tx = slow_res_tx.lock().unwrap_or_else(|e| e.into_inner()).take();
+	assert!(slow_res_tx.is_some_and(|tx| !tx.is_closed()));
+	// The client is still usable.
+	client.send_request(PingParams::default()).await?;
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_request_http_status_error() -> Result<()> {
+	// -- Fixtures & Setup
+	let server = FakeHttpServer::start(|req| {
+		if req.rpc_method() == Some("tools/list") {
+			return status_response(StatusCode::SERVICE_UNAVAILABLE);
+		}
+		fx_mcp_reply(req)
+	})
+	.await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientHttpTransportConfig::new(&server.url)).await?;
+
+	// -- Exec
+	let res = client.send_request(ListToolsParams::default()).await;
+
+	// -- Check
+	let Err(Error::TransportRequest { method, cause, .. }) = res else {
+		return Err(format!("Should be TransportRequest, was: {res:?}").into());
+	};
+	assert_eq!(method, "tools/list");
+	assert!(
+		matches!(cause, TransportError::HttpStatus { status: 503, .. }),
+		"{cause:?}"
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_request_connection_refused_error() -> Result<()> {
+	// -- Fixtures & Setup
+	// NOTE: A port just released, so nothing listens on it.
+	let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+	let url = format!("http://{}/mcp", listener.local_addr()?);
+	drop(listener);
+	let mut client = Client::new("Demo Client", "0.1.0");
+
+	// -- Exec
+	let res = client.connect(ClientHttpTransportConfig::new(&url)).await;
+
+	// -- Check
+	let Err(Error::TransportRequest { method, cause, .. }) = res else {
+		return Err(format!("Should be TransportRequest, was: {res:?}").into());
+	};
+	assert_eq!(method, "initialize");
+	let TransportError::Reqwest(cause) = cause else {
+		return Err(format!("Should be Reqwest, was: {cause:?}").into());
+	};
+	assert!(cause.is_connect(), "{cause:?}");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_http_oauth_unauthorized_retry() -> Result<()> {
+	// -- Fixtures & Setup
+	let server = FakeHttpServer::start(|req| fx_oauth_reply(req).unwrap_or_else(|| fx_mcp_reply(req))).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let config = ClientHttpTransportConfig::new(&server.url).with_oauth(fx_oauth_client());
+
+	// -- Exec
+	client.connect(config).await?;
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	let bearer = format!("Bearer {FX_ACCESS_TOKEN}");
+	let initializes = server.requests_of("initialize");
+	assert_eq!(initializes.len(), 2);
+	// The first initialize gets the 401, and the retry carries the token.
+	assert_eq!(initializes[0].header("authorization"), None);
+	assert_eq!(initializes[1].header("authorization"), Some(bearer.as_str()));
+	assert_eq!(server.requests().iter().filter(|req| req.path == "/token").count(), 1);
+	for method in ["notifications/initialized", "ping"] {
+		let req = &server.requests_of(method)[0];
+		assert_eq!(req.header("authorization"), Some(bearer.as_str()), "{method}");
+	}
+
+	Ok(())
+}
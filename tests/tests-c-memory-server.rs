@@ -0,0 +1,149 @@
+mod support;
+
+use agentic::mcp::client::{Client, ClientMemoryTransportConfig};
+use agentic::mcp::testing::{MockAction, MockServer};
+use agentic::mcp::{
+	CallToolParams, CreateMessageParams, CreateMessageResult, GetPromptParams, ListPromptsParams, ListResourcesParams,
+	ListToolsParams, ReadResourceParams, ResourceContents, ResourceUpdatedNotificationParams, SubscribeParams,
+};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use support::{COUNTER_URI, Result, fx_server};
+
+#[tokio::test]
+async fn test_c_memory_server_tools() -> Result<()> {
+	// -- Fixtures & Setup
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientMemoryTransportConfig::from_server(fx_server())).await?;
+
+	// -- Exec
+	let tools = client.send_request(ListToolsParams::default()).await?.result.tools;
+	let params = CallToolParams::new("add").append_argument("a", 1).append_argument("b", 2.5);
+	let res = client.send_request(params).await?.result;
+
+	// -- Check
+	assert_eq!(
+		client.server_info().map(|info| info.name).as_deref(),
+		Some("Demo Server")
+	);
+	assert_eq!(tools.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["add"]);
+	assert_eq!(serde_json::to_value(&res)?["content"][0]["text"], "3.5");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_memory_server_prompts() -> Result<()> {
+	// -- Fixtures & Setup
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientMemoryTransportConfig::from_server(fx_server())).await?;
+
+	// -- Exec
+	let prompts = client.send_request(ListPromptsParams::default()).await?.result.prompts;
+	let prompt_res = client
+		.send_request(GetPromptParams::new("greet").append_argument("name", "Mike"))
+		.await?
+		.result;
+	let missing_arg_res = client.send_request(GetPromptParams::new("greet")).await;
+
+	// -- Check
+	assert_eq!(
+		prompts.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+		vec!["greet"]
+	);
+	let text = prompt_res.messages.first().and_then(|m| m.content.as_text());
+	assert_eq!(text, Some("Say hello to Mike."));
+	let missing_arg_err = missing_arg_res.err().ok_or("Should fail without the required argument")?;
+	assert!(missing_arg_err.to_string().contains("name"), "{missing_arg_err}");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_memory_server_resources() -> Result<()> {
+	// -- Fixtures & Setup
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(ClientMemoryTransportConfig::from_server(fx_server())).await?;
+
+	// -- Exec
+	let resources = client.send_request(ListResourcesParams::default()).await?.result.resources;
+	let contents = client.send_request(ReadResourceParams::new(COUNTER_URI)).await?.result.contents;
+	let unknown_res = client.send_request(ReadResourceParams::new("counter://nope")).await;
+
+	// -- Check
+	assert_eq!(
+		resources.iter().map(|r| r.uri.as_str()).collect::<Vec<_>>(),
+		vec![COUNTER_URI]
+	);
+	let Some(ResourceContents::Text { uri, text, .. }) = contents.first() else {
+		return Err("Should have a text content".into());
+	};
+	assert_eq!(uri, COUNTER_URI);
+	assert_eq!(text, "0");
+	assert!(unknown_res.is_err(), "unknown resource should fail");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_memory_server_resource_updated() -> Result<()> {
+	// -- Fixtures & Setup
+	let server = fx_server();
+	let notifier = server.resource_notifier();
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let updates = client.subscribe_notification::<ResourceUpdatedNotificationParams>();
+	client.connect(ClientMemoryTransportConfig::from_server(server)).await?;
+
+	// -- Exec
+	client.send_request(SubscribeParams::new(COUNTER_URI)).await?;
+	notifier.notify_updated(COUNTER_URI);
+
+	// -- Check
+	let update = tokio::time::timeout(Duration::from_secs(2), updates.recv())
+		.await?
+		.ok_or("Should have an update")?;
+	assert_eq!(update.uri, COUNTER_URI);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_memory_fake_sampling() -> Result<()> {
+	// -- Fixtures & Setup
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.register_sampling_handler(
+		async |_params: CreateMessageParams| -> agentic::mcp::Result<CreateMessageResult> {
+			Ok(CreateMessageResult::new_assistant(
+				"Because of the sunset.",
+				"mock-model",
+			))
+		},
+	);
+	// The scripted fake server (over the in-memory duplex): asks for a sampling once initialized.
+	let mock = MockServer::new("Fake Server", "0.1.0").on_initialized(MockAction::request(
+		"sampling/createMessage",
+		json!({
+			"messages": [{"role": "user", "content": {"type": "text", "text": "Why is the sky red?"}}],
+			"maxTokens": 100
+		}),
+	));
+
+	// -- Exec
+	client.connect(mock.serve_in_memory()).await?;
+	let deadline = Instant::now() + Duration::from_secs(2);
+	let sampling_res = loop {
+		if let Some(res) = mock.received().into_iter().find(|msg| msg["id"] == "mock-1") {
+			break res;
+		}
+		if Instant::now() > deadline {
+			return Err("Should have the sampling response".into());
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+	};
+
+	// -- Check
+	assert_eq!(sampling_res["result"]["model"], "mock-model");
+	assert_eq!(sampling_res["result"]["content"]["text"], "Because of the sunset.");
+
+	Ok(())
+}
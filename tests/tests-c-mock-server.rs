@@ -0,0 +1,132 @@
+use agentic::RpcId;
+use agentic::mcp::client::Client;
+use agentic::mcp::testing::{MockAction, MockExpectation, MockServer};
+use agentic::mcp::{
+	CallToolParams, CallToolResult, CreateMessageParams, CreateMessageResult, Error, LoggingMessageNotificationParams,
+	McpError,
+};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+#[tokio::test]
+async fn test_c_mock_tool_call() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0").on_tool_call("echo", CallToolResult::from_text("hello"));
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(mock.serve_in_memory()).await?;
+
+	// -- Exec
+	let res = client
+		.send_request(CallToolParams::new("echo").append_argument("message", "hello"))
+		.await?;
+
+	// -- Check
+	let text = res.result.content.first().and_then(|c| c.as_text()).ok_or("Should have text")?;
+	assert_eq!(text, "hello");
+	let calls = mock.received_requests("tools/call");
+	assert_eq!(calls.len(), 1);
+	assert_eq!(calls[0]["params"]["arguments"]["message"], "hello");
+	assert!(client.server_capabilities().ok_or("Should have capabilities")?.tools.is_some());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_mock_error_then_result() -> Result<()> {
+	// -- Fixtures & Setup
+	let mcp_error = McpError::new(RpcId::Null, Error::custom("Tool busy").into_rpc_error());
+	let mock = MockServer::new("Mock Server", "0.1.0")
+		.on_request(
+			MockExpectation::new("tools/call")
+				.with_params(json!({"name": "flaky"}))
+				.with_times(1)
+				.respond_error(mcp_error),
+		)
+		.on_tool_call("flaky", CallToolResult::from_text("done"));
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(mock.serve_in_memory()).await?;
+
+	// -- Exec
+	let first = client.send_request(CallToolParams::new("flaky")).await;
+	let second = client.send_request(CallToolParams::new("flaky")).await?;
+
+	// -- Check
+	let first_err = first.err().ok_or("First call should fail")?;
+	assert!(first_err.to_string().contains("Tool busy"), "{first_err}");
+	assert_eq!(second.result.content.first().and_then(|c| c.as_text()), Some("done"));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_mock_delay() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0").on_request(
+		MockExpectation::new("tools/call")
+			.with_action(MockAction::delay(200))
+			.respond(CallToolResult::from_text("late")),
+	);
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.connect(mock.serve_in_memory()).await?;
+
+	// -- Exec
+	let start = Instant::now();
+	client.send_request(CallToolParams::new("slow")).await?;
+
+	// -- Check
+	assert!(start.elapsed() >= Duration::from_millis(200));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_mock_on_initialized_notify_and_sampling() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0")
+		.on_initialized(MockAction::notify(
+			"notifications/message",
+			json!({"level": "info", "data": "Welcome"}),
+		))
+		.on_initialized(MockAction::request(
+			"sampling/createMessage",
+			json!({
+				"messages": [{"role": "user", "content": {"type": "text", "text": "Why is the sky red?"}}],
+				"maxTokens": 100
+			}),
+		));
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client.register_sampling_handler(
+		async |_params: CreateMessageParams| -> agentic::mcp::Result<CreateMessageResult> {
+			Ok(CreateMessageResult::new_assistant(
+				"Because of the sunset.",
+				"mock-model",
+			))
+		},
+	);
+	let messages = client.subscribe_notification::<LoggingMessageNotificationParams>();
+
+	// -- Exec
+	client.connect(mock.serve_in_memory()).await?;
+	let message = tokio::time::timeout(Duration::from_secs(2), messages.recv())
+		.await?
+		.ok_or("Should have a log message")?;
+	let deadline = Instant::now() + Duration::from_secs(2);
+	let sampling_res = loop {
+		let received = mock.received();
+		if let Some(res) = received.into_iter().find(|msg| msg["id"] == "mock-1") {
+			break res;
+		}
+		if Instant::now() > deadline {
+			return Err("Should have the sampling response".into());
+		}
+		tokio::time::sleep(Duration::from_millis(10)).await;
+	};
+
+	// -- Check
+	assert_eq!(message.data, "Welcome");
+	assert_eq!(sampling_res["result"]["model"], "mock-model");
+
+	Ok(())
+}
@@ -0,0 +1,60 @@
+mod support;
+
+use agentic::mcp::client::{Client, NotificationStream};
+use agentic::mcp::testing::{MockAction, MockServer};
+use agentic::mcp::{LoggingLevel, LoggingMessageNotificationParams, ServerNotification};
+use serde_json::json;
+use std::time::Duration;
+use support::Result;
+
+#[tokio::test]
+async fn test_c_notifications_typed_and_unknown() -> Result<()> {
+	// -- Fixtures & Setup
+	let mock = MockServer::new("Mock Server", "0.1.0")
+		.on_initialized(MockAction::notify("x-vendor/custom", json!({"value": 42})))
+		.on_initialized(MockAction::notify(
+			"notifications/message",
+			json!({"level": "warning", "logger": "db", "data": "slow query"}),
+		));
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let logs = client.subscribe_notification::<LoggingMessageNotificationParams>();
+	let unknowns = client.subscribe_unknown_notifications();
+	let all = client.subscribe_notifications();
+
+	// -- Exec
+	client.connect(mock.serve_in_memory()).await?;
+	let log = recv(&logs).await?;
+	let unknown = recv(&unknowns).await?;
+	let first = recv(&all).await?;
+	let second = recv(&all).await?;
+
+	// -- Check
+	// Typed, only the `notifications/message`.
+	assert_eq!(log.level, LoggingLevel::Warning);
+	assert_eq!(log.logger.as_deref(), Some("db"));
+	assert_eq!(log.data, json!("slow query"));
+	assert!(logs.try_recv().is_none());
+	// Raw fallback, only the unknown method.
+	assert_eq!(unknown.method, "x-vendor/custom");
+	assert_eq!(unknown.params, Some(json!({"value": 42})));
+	assert!(unknowns.try_recv().is_none());
+	// All, in order (the unknown one as is).
+	assert!(
+		matches!(first, ServerNotification::Other(ref n) if n.method == "x-vendor/custom"),
+		"{first:?}"
+	);
+	assert!(matches!(second, ServerNotification::LoggingMessage(_)), "{second:?}");
+
+	Ok(())
+}
+
+// region:    --- Support
+
+async fn recv<T>(stream: &NotificationStream<T>) -> Result<T> {
+	let item = tokio::time::timeout(Duration::from_secs(2), stream.recv())
+		.await?
+		.ok_or("Notifications closed")?;
+	Ok(item)
+}
+
+// endregion: --- Support
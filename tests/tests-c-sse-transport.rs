@@ -0,0 +1,177 @@
+mod support;
+
+use agentic::mcp::PingParams;
+use agentic::mcp::client::{Client, ClientEvent, ClientHttpTransportConfig, ClientSseTransportConfig, ClientState};
+use axum::http::{Method, StatusCode};
+use axum::response::Response;
+use std::sync::{Arc, Mutex};
+use support::{
+	FX_ACCESS_TOKEN, FakeHttpRequest, FakeHttpServer, Result, fx_mcp_response, fx_oauth_client, fx_oauth_reply,
+	recv_events_until, sse_event, sse_response, status_response,
+};
+use tokio::sync::mpsc;
+
+const FX_ENDPOINT: &str = "/messages?sessionId=1";
+
+#[tokio::test]
+async fn test_c_sse_endpoint_and_post() -> Result<()> {
+	// -- Fixtures & Setup
+	let stream_tx: FxStreamTx = Arc::default();
+	let server = FakeHttpServer::start(fx_legacy_sse_reply(stream_tx.clone())).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let config = ClientSseTransportConfig::new(&server.url)
+		.with_header("x-tenant", "acme")
+		.with_bearer_token("secret");
+
+	// -- Exec
+	client.connect(config).await?;
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	let get = &server.requests_with(Method::GET)[0];
+	assert_eq!(get.path, "/mcp");
+	assert_eq!(get.header("accept"), Some("text/event-stream"));
+	let posts = server.requests_with(Method::POST);
+	let methods: Vec<_> = posts.iter().filter_map(|req| req.rpc_method()).collect();
+	assert_eq!(methods, ["initialize", "notifications/initialized", "ping"]);
+	for req in std::iter::once(get).chain(posts.iter()) {
+		assert_eq!(req.header("x-tenant"), Some("acme"), "{} {}", req.method, req.path);
+		assert_eq!(
+			req.header("authorization"),
+			Some("Bearer secret"),
+			"{} {}",
+			req.method,
+			req.path
+		);
+	}
+	assert!(posts.iter().all(|req| req.path == FX_ENDPOINT));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_sse_http_fallback_on_4xx() -> Result<()> {
+	// -- Fixtures & Setup
+	let stream_tx: FxStreamTx = Arc::default();
+	let server = FakeHttpServer::start(fx_legacy_sse_reply(stream_tx.clone())).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let config = ClientHttpTransportConfig::new(&server.url).with_legacy_sse_fallback(true);
+
+	// -- Exec
+	client.connect(config).await?;
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	let requests: Vec<_> = server
+		.requests()
+		.iter()
+		.map(|req| {
+			(
+				req.method.to_string(),
+				req.path.clone(),
+				req.rpc_method().map(String::from),
+			)
+		})
+		.collect();
+	let initialize = Some("initialize".to_string());
+	assert_eq!(
+		&requests[..3],
+		[
+			// The Streamable HTTP initialize (405)
+			("POST".to_string(), "/mcp".to_string(), initialize.clone()),
+			("GET".to_string(), "/mcp".to_string(), None),
+			("POST".to_string(), FX_ENDPOINT.to_string(), initialize),
+		]
+	);
+	assert_eq!(server.requests_of("ping")[0].path, FX_ENDPOINT);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_sse_stream_end_disconnected() -> Result<()> {
+	// -- Fixtures & Setup
+	let stream_tx: FxStreamTx = Arc::default();
+	let server = FakeHttpServer::start(fx_legacy_sse_reply(stream_tx.clone())).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let events = client.subscribe_events();
+	client.connect(ClientSseTransportConfig::new(&server.url)).await?;
+
+	// -- Exec
+	// Ends the SSE stream.
+	stream_tx.lock().unwrap_or_else(|e| e.into_inner()).take();
+	let events = recv_events_until(&events, |event| matches!(event, ClientEvent::Disconnected { .. })).await?;
+
+	// -- Check
+	let Some(ClientEvent::Disconnected { reason, exit_status }) = events.last() else {
+		return Err("Should have a disconnected event".into());
+	};
+	assert!(reason.contains("Transport disconnected"), "{reason}");
+	assert!(exit_status.is_none());
+	assert_eq!(client.state(), ClientState::Closed);
+	assert!(client.send_request(PingParams::default()).await.is_err());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_sse_oauth_unauthorized_retry() -> Result<()> {
+	// -- Fixtures & Setup
+	let stream_tx: FxStreamTx = Arc::default();
+	let legacy_sse_reply = fx_legacy_sse_reply(stream_tx.clone());
+	let server = FakeHttpServer::start(move |req| fx_oauth_reply(req).unwrap_or_else(|| legacy_sse_reply(req))).await?;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let config = ClientSseTransportConfig::new(&server.url).with_oauth(fx_oauth_client());
+
+	// -- Exec
+	client.connect(config).await?;
+	client.send_request(PingParams::default()).await?;
+
+	// -- Check
+	let bearer = format!("Bearer {FX_ACCESS_TOKEN}");
+	let gets = server.requests_with(Method::GET);
+	let gets: Vec<_> = gets.iter().filter(|req| req.path == "/mcp").collect();
+	assert_eq!(gets.len(), 2);
+	// The first GET gets the 401, and the retry carries the token.
+	assert_eq!(gets[0].header("authorization"), None);
+	assert_eq!(gets[1].header("authorization"), Some(bearer.as_str()));
+	let posts = server.requests_with(Method::POST);
+	let posts: Vec<_> = posts.iter().filter(|req| req.path == FX_ENDPOINT).collect();
+	assert_eq!(posts.len(), 3);
+	assert!(posts.iter().all(|req| req.header("authorization") == Some(bearer.as_str())));
+
+	Ok(())
+}
+
+// region:    --- Support
+
+type FxStreamTx = Arc<Mutex<Option<mpsc::UnboundedSender<String>>>>;
+
+/// A legacy HTTP+SSE server.
+///
+/// - GET: opens the stream (kept in `stream_tx`), starting with the `endpoint` event.
+/// - POST on the endpoint: 202, the response (see `fx_mcp_response`) being sent on the stream.
+/// - Other POSTs (e.g., the Streamable HTTP initialize): 405.
+fn fx_legacy_sse_reply(stream_tx: FxStreamTx) -> impl Fn(&FakeHttpRequest) -> Response + Send + Sync + 'static {
+	move |req| {
+		if req.method == Method::GET {
+			let (tx, res) = sse_response();
+			let _ = tx.send(sse_event(None, Some("endpoint"), FX_ENDPOINT));
+			*stream_tx.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+			return res;
+		}
+		if req.method != Method::POST || req.path != FX_ENDPOINT {
+			return status_response(StatusCode::METHOD_NOT_ALLOWED);
+		}
+
+		if let Some(response) = fx_mcp_response(req) {
+			let tx = stream_tx.lock().unwrap_or_else(|e| e.into_inner()).clone();
+			if let Some(tx) = tx {
+				let _ = tx.send(sse_event(None, Some("message"), &response.to_string()));
+			}
+		}
+		status_response(StatusCode::ACCEPTED)
+	}
+}
+
+// endregion: --- Support
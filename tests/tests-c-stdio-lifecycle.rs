@@ -1,6 +1,8 @@
 mod support;
 
-use support::Result;
+use agentic::mcp::PingParams;
+use agentic::mcp::client::{Client, ClientEvent, ClientState, ClientStdioTransportConfig};
+use support::{Result, recv_events_until};
 
 #[tokio::test]
 async fn test_c_stdio_livecycle_connect() -> Result<()> {
@@ -16,3 +18,40 @@ async fn test_c_stdio_livecycle_connect() -> Result<()> {
 
 	Ok(())
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_c_stdio_livecycle_exit_mid_request() -> Result<()> {
+	// -- Fixtures & Setup
+	// Answers the initialize, then exits with 3 on the next request (after the initialized notification).
+	let script = r#"
+		read l
+		id=$(printf '%s' "$l" | sed -e 's/.*"id":\([^,}]*\).*/\1/')
+		printf '{"jsonrpc":"2.0","id":%s,"result":{"protocolVersion":"2025-03-26","capabilities":{},"serverInfo":{"name":"sh","version":"0"}}}\n' "$id"
+		read l
+		read l
+		exit 3
+	"#;
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let events = client.subscribe_events();
+	client
+		.connect(ClientStdioTransportConfig::new("sh", ["-c", script], None))
+		.await?;
+
+	// -- Exec
+	let res = client.send_request(PingParams::default()).await;
+	let events = recv_events_until(&events, |event| matches!(event, ClientEvent::Disconnected { .. })).await?;
+
+	// -- Check
+	match res {
+		Err(agentic::mcp::Error::ClientDisconnected { reason }) => assert!(reason.contains("exited"), "{reason}"),
+		other => return Err(format!("Should be ClientDisconnected, got {other:?}").into()),
+	}
+	let Some(ClientEvent::Disconnected { exit_status, .. }) = events.last() else {
+		return Err("Should have a disconnected event".into());
+	};
+	assert_eq!(exit_status.and_then(|status| status.code()), Some(3));
+	assert_eq!(client.state(), ClientState::Closed);
+
+	Ok(())
+}
@@ -0,0 +1,209 @@
+mod support;
+
+use agentic::mcp::client::{Client, ClientEvent, ClientWebSocketTransportConfig};
+use agentic::mcp::server::ServerWebSocketTransportConfig;
+use agentic::mcp::{CallToolParams, ListToolsParams};
+use futures::{SinkExt as _, StreamExt as _};
+use serde_json::{Value, json};
+use std::time::Duration;
+use support::{Result, fx_initialize_result, fx_server};
+use tokio::io::AsyncReadExt as _;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+#[tokio::test]
+async fn test_c_websocket_server_tools() -> Result<()> {
+	// -- Fixtures & Setup
+	let listener = TcpListener::bind("127.0.0.1:0").await?;
+	let addr = listener.local_addr()?;
+	let server = fx_server();
+	tokio::spawn(async move { server.serve(ServerWebSocketTransportConfig::from_listener(listener)).await });
+	let mut client = Client::new("Demo Client", "0.1.0");
+	client
+		.connect(ClientWebSocketTransportConfig::new(format!("ws://{addr}")))
+		.await?;
+
+	// -- Exec
+	let tools = client.send_request(ListToolsParams::default()).await?.result.tools;
+	let params = CallToolParams::new("add").append_argument("a", 1).append_argument("b", 2.5);
+	let res = client.send_request(params).await?.result;
+	client.close().await?;
+
+	// -- Check
+	assert_eq!(
+		client.server_info().map(|info| info.name).as_deref(),
+		Some("Demo Server")
+	);
+	assert_eq!(tools.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["add"]);
+	assert_eq!(serde_json::to_value(&res)?["content"][0]["text"], "3.5");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_websocket_close_code_and_header() -> Result<()> {
+	// -- Fixtures & Setup
+	let listener = TcpListener::bind("127.0.0.1:0").await?;
+	let addr = listener.local_addr()?;
+	// The raw server: checks the bearer token, answers the initialize, then closes with the code 4001.
+	let fake = tokio::spawn(async move {
+		let (stream, _) = listener.accept().await?;
+		#[allow(clippy::result_large_err)] // The tungstenite callback signature.
+		let check_auth = |req: &Request, res: Response| -> core::result::Result<Response, ErrorResponse> {
+			match req.headers().get("authorization").and_then(|v| v.to_str().ok()) {
+				Some("Bearer secret") => Ok(res),
+				_ => Err(fx_error_response(StatusCode::UNAUTHORIZED)),
+			}
+		};
+		let mut ws = tokio_tungstenite::accept_hdr_async(stream, check_auth).await?;
+		while let Some(Message::Text(text)) = ws.next().await.transpose()? {
+			let msg: Value = serde_json::from_str(&text)?;
+			match msg["method"].as_str() {
+				Some("initialize") => {
+					let res = json!({"jsonrpc": "2.0", "id": msg["id"], "result": fx_initialize_result()});
+					ws.send(Message::text(res.to_string())).await?;
+				}
+				Some("notifications/initialized") => {
+					let frame = CloseFrame {
+						code: CloseCode::from(4001),
+						reason: "Session expired".into(),
+					};
+					ws.close(Some(frame)).await?;
+					break;
+				}
+				_ => (),
+			}
+		}
+		Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+	});
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let events = client.subscribe_events();
+	let config = ClientWebSocketTransportConfig::new(format!("ws://{addr}")).with_bearer_token("secret");
+
+	// -- Exec
+	client.connect(config).await?;
+	let reason = tokio::time::timeout(Duration::from_secs(2), async {
+		while let Some(event) = events.recv().await {
+			if let ClientEvent::Disconnected { reason, .. } = event {
+				return Some(reason);
+			}
+		}
+		None
+	})
+	.await?
+	.ok_or("Should have a disconnected event")?;
+
+	// -- Check
+	fake.await?.map_err(|err| err.to_string())?;
+	assert!(reason.contains("WebSocketClosed"), "{reason}");
+	assert!(reason.contains("4001"), "{reason}");
+	assert!(reason.contains("Session expired"), "{reason}");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_websocket_unauthorized() -> Result<()> {
+	// -- Fixtures & Setup
+	let listener = TcpListener::bind("127.0.0.1:0").await?;
+	let addr = listener.local_addr()?;
+	tokio::spawn(async move {
+		let (stream, _) = listener.accept().await?;
+		#[allow(clippy::result_large_err)] // The tungstenite callback signature.
+		let reject = |_req: &Request, _res: Response| -> core::result::Result<Response, ErrorResponse> {
+			Err(fx_error_response(StatusCode::UNAUTHORIZED))
+		};
+		let _ = tokio_tungstenite::accept_hdr_async(stream, reject).await;
+		Ok::<(), std::io::Error>(())
+	});
+	let mut client = Client::new("Demo Client", "0.1.0");
+
+	// -- Exec
+	let res = client
+		.connect(ClientWebSocketTransportConfig::new(format!("ws://{addr}")))
+		.await;
+
+	// -- Check
+	let err = res.err().ok_or("Connect should fail")?;
+	assert!(err.to_string().contains("Unauthorized"), "{err}");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_websocket_keepalive_timeout() -> Result<()> {
+	// -- Fixtures & Setup
+	let listener = TcpListener::bind("127.0.0.1:0").await?;
+	let addr = listener.local_addr()?;
+	// The raw server: answers the initialize, then stops reading (so, never answers the pings).
+	tokio::spawn(async move {
+		let (stream, _) = listener.accept().await?;
+		let mut ws = tokio_tungstenite::accept_async(stream).await?;
+		if let Some(Message::Text(text)) = ws.next().await.transpose()? {
+			let msg: Value = serde_json::from_str(&text)?;
+			let res = json!({"jsonrpc": "2.0", "id": msg["id"], "result": fx_initialize_result()});
+			ws.send(Message::text(res.to_string())).await?;
+		}
+		tokio::time::sleep(Duration::from_secs(5)).await;
+		Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+	});
+	let mut client = Client::new("Demo Client", "0.1.0");
+	let events = client.subscribe_events();
+	let config = ClientWebSocketTransportConfig::new(format!("ws://{addr}"))
+		.with_ping_interval(Some(Duration::from_millis(100)))
+		.with_pong_timeout(Duration::from_millis(200));
+
+	// -- Exec
+	client.connect(config).await?;
+	let reason = tokio::time::timeout(Duration::from_secs(2), async {
+		while let Some(event) = events.recv().await {
+			if let ClientEvent::Disconnected { reason, .. } = event {
+				return Some(reason);
+			}
+		}
+		None
+	})
+	.await?
+	.ok_or("Should have a disconnected event")?;
+
+	// -- Check
+	assert!(reason.contains("WebSocketKeepaliveTimeout"), "{reason}");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_c_websocket_server_handshake_timeout() -> Result<()> {
+	// -- Fixtures & Setup
+	let listener = TcpListener::bind("127.0.0.1:0").await?;
+	let addr = listener.local_addr()?;
+	let server = fx_server();
+	let config =
+		ServerWebSocketTransportConfig::from_listener(listener).with_handshake_timeout(Duration::from_millis(100));
+	tokio::spawn(async move { server.serve(config).await });
+
+	// -- Exec
+	// A stalled peer: connects, but never sends the handshake request.
+	let mut stream = TcpStream::connect(addr).await?;
+	let mut buf = Vec::new();
+	let read_len = tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut buf)).await??;
+
+	// -- Check
+	assert_eq!(read_len, 0, "the server should drop the stalled connection");
+
+	Ok(())
+}
+
+// region:    --- Support
+
+fn fx_error_response(status: StatusCode) -> ErrorResponse {
+	let mut res = ErrorResponse::new(Some(status.to_string()));
+	*res.status_mut() = status;
+	res
+}
+
+// endregion: --- Support